@@ -0,0 +1,38 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use sp_runtime::codec::Codec;
+use sp_std::vec::Vec;
+use support::{SwapLimit, SwapRoute};
+
+sp_api::decl_runtime_apis! {
+	pub trait AggregatedDexApi<CurrencyId, Balance> where
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// Split the swap across DEX and Taiga routes for the best price.
+		fn get_split_swap_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			limit: SwapLimit<Balance>,
+		) -> Option<Vec<SwapRoute<CurrencyId, Balance>>>;
+	}
+}
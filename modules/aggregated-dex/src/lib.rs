@@ -17,6 +17,9 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! # Aggregated DEX Module
+//!
+//! Swaps through DEX and Taiga (StableAsset) pools, either along a single aggregated swap path or
+//! split across multiple routes which don't share any pool.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -24,11 +27,14 @@
 
 use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
-use nutsfinance_stable_asset::traits::StableAsset as StableAssetT;
-use primitives::{Balance, CurrencyId};
-use sp_runtime::traits::{Convert, Zero};
-use sp_std::{marker::PhantomData, vec::Vec};
-use support::{AggregatedSwapPath, DEXManager, RebasedStableAssetError, Swap, SwapLimit};
+use nutsfinance_stable_asset::{traits::StableAsset as StableAssetT, StableAssetPoolId};
+use primitives::{Balance, CurrencyId, TradingPair};
+use sp_runtime::{
+	traits::{Convert, Zero},
+	ArithmeticError,
+};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+use support::{AggregatedSwapPath, DEXManager, RebasedStableAssetError, Swap, SwapLimit, SwapRoute};
 
 mod mock;
 mod tests;
@@ -39,6 +45,16 @@ pub use weights::WeightInfo;
 
 pub type SwapPath = AggregatedSwapPath<CurrencyId>;
 
+/// The number of parts the amount is divided into when splitting a swap across routes.
+pub const SPLIT_SWAP_PARTS: u32 = 10;
+
+/// The pool used by a swap path.
+#[derive(PartialEq)]
+enum SwapPool {
+	Dex(TradingPair),
+	Taiga(StableAssetPoolId),
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -68,6 +84,10 @@ pub mod module {
 		#[pallet::constant]
 		type SwapPathLimit: Get<u32>;
 
+		/// The maximum number of routes a swap is split across
+		#[pallet::constant]
+		type MaxSplitSwapRoutes: Get<u32>;
+
 		type WeightInfo: WeightInfo;
 	}
 
@@ -101,7 +121,9 @@ pub mod module {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Swap with aggregated DEX at exact supply amount.
+		/// Swap with aggregated DEX at exact supply amount. If splitting the swap across the
+		/// routes found by `get_split_swap_routes` gets more target amount, the split routes are
+		/// swapped instead of `paths`.
 		///
 		/// - `paths`: aggregated swap path.
 		/// - `supply_amount`: exact supply amount.
@@ -111,7 +133,7 @@ pub mod module {
 				SwapPath::Dex(v) => u + (v.len() as u32),
 				SwapPath::Taiga(_, _, _) => u + 1
 			})
-		))]
+		).saturating_add(<T as Config>::WeightInfo::split_swap_with_exact_supply(T::MaxSplitSwapRoutes::get())))]
 		#[transactional]
 		pub fn swap_with_exact_supply(
 			origin: OriginFor<T>,
@@ -122,16 +144,24 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let paths: BoundedVec<SwapPath, T::SwapPathLimit> =
 				paths.try_into().map_err(|_| Error::<T>::InvalidSwapPath)?;
-			let _ = Self::do_aggregated_swap(&who, &paths, SwapLimit::ExactSupply(supply_amount, min_target_amount))?;
+			let _ =
+				Self::do_swap_with_best_routes(&who, &paths, SwapLimit::ExactSupply(supply_amount, min_target_amount))?;
 			Ok(())
 		}
 
+		/// Swap with aggregated DEX to get exact target amount. If splitting the swap across the
+		/// routes found by `get_split_swap_routes` pays less supply amount, the split routes are
+		/// swapped instead of `paths`.
+		///
+		/// - `paths`: aggregated swap path.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
 		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(
 			paths.iter().fold(0, |u, swap_path| match swap_path {
 				SwapPath::Dex(v) => u + (v.len() as u32),
 				SwapPath::Taiga(_, _, _) => u + 1
 			})
-		))]
+		).saturating_add(<T as Config>::WeightInfo::split_swap_with_exact_target(T::MaxSplitSwapRoutes::get())))]
 		#[transactional]
 		pub fn swap_with_exact_target(
 			origin: OriginFor<T>,
@@ -142,7 +172,8 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let paths: BoundedVec<SwapPath, T::SwapPathLimit> =
 				paths.try_into().map_err(|_| Error::<T>::InvalidSwapPath)?;
-			let _ = Self::do_aggregated_swap(&who, &paths, SwapLimit::ExactTarget(max_supply_amount, target_amount))?;
+			let _ =
+				Self::do_swap_with_best_routes(&who, &paths, SwapLimit::ExactTarget(max_supply_amount, target_amount))?;
 			Ok(())
 		}
 
@@ -177,6 +208,58 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Swap at exact supply amount, split across the routes found by `get_split_swap_routes`.
+		/// All routes are swapped atomically.
+		///
+		/// - `supply_currency_id`: the currency to supply.
+		/// - `target_currency_id`: the currency to get.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: acceptable minimum target amount of all routes.
+		#[pallet::weight(<T as Config>::WeightInfo::split_swap_with_exact_supply(T::MaxSplitSwapRoutes::get()))]
+		#[transactional]
+		pub fn split_swap_with_exact_supply(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let _ = Self::do_split_swap(
+				&who,
+				supply_currency_id,
+				target_currency_id,
+				SwapLimit::ExactSupply(supply_amount, min_target_amount),
+			)?;
+			Ok(())
+		}
+
+		/// Swap to get exact target amount, split across the routes found by
+		/// `get_split_swap_routes`. All routes are swapped atomically.
+		///
+		/// - `supply_currency_id`: the currency to supply.
+		/// - `target_currency_id`: the currency to get.
+		/// - `target_amount`: exact target amount of all routes.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
+		#[pallet::weight(<T as Config>::WeightInfo::split_swap_with_exact_target(T::MaxSplitSwapRoutes::get()))]
+		#[transactional]
+		pub fn split_swap_with_exact_target(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] target_amount: Balance,
+			#[pallet::compact] max_supply_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let _ = Self::do_split_swap(
+				&who,
+				supply_currency_id,
+				target_currency_id,
+				SwapLimit::ExactTarget(max_supply_amount, target_amount),
+			)?;
+			Ok(())
+		}
 	}
 }
 
@@ -379,6 +462,309 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 	}
+
+	/// Swap by `paths`, or by the split swap routes between the same currencies if they get more
+	/// target amount for `ExactSupply`, or pay less supply amount for `ExactTarget`.
+	#[transactional]
+	fn do_swap_with_best_routes(
+		who: &T::AccountId,
+		paths: &[SwapPath],
+		limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let (supply_currency_id, target_currency_id) = Self::check_swap_paths(paths)?;
+		let split_result = Self::get_split_swap_routes(supply_currency_id, target_currency_id, limit)
+			.and_then(|routes| Self::get_split_swap_amount(&routes));
+
+		if let Some((split_supply_amount, split_target_amount)) = split_result {
+			let is_split_better = match (Self::get_aggregated_swap_amount(paths, limit), limit) {
+				(None, _) => true,
+				(Some((_, target_amount)), SwapLimit::ExactSupply(_, _)) => split_target_amount > target_amount,
+				(Some((supply_amount, _)), SwapLimit::ExactTarget(_, _)) => split_supply_amount < supply_amount,
+			};
+			if is_split_better {
+				return Self::do_split_swap(who, supply_currency_id, target_currency_id, limit);
+			}
+		}
+
+		Self::do_aggregated_swap(who, paths, limit)
+	}
+
+	fn get_swap_pools(paths: &[SwapPath]) -> Option<Vec<SwapPool>> {
+		let mut pools = vec![];
+		for path in paths {
+			match path {
+				SwapPath::Dex(dex_path) => {
+					for pair in dex_path.windows(2) {
+						pools.push(SwapPool::Dex(TradingPair::from_currency_ids(pair[0], pair[1])?));
+					}
+				}
+				SwapPath::Taiga(pool_id, _, _) => pools.push(SwapPool::Taiga(*pool_id)),
+			}
+		}
+		Some(pools)
+	}
+
+	/// The candidate routes for a split swap: the direct DEX path, the DEX paths through
+	/// `DexSwapJointList`, the best Taiga pool and the governance configured aggregated swap paths.
+	/// Routes sharing a pool with a previous route are skipped, so that every route can be quoted
+	/// independently. At most `MaxSplitSwapRoutes` routes are quoted, and those which can't swap
+	/// the part amount are dropped.
+	fn get_split_swap_candidates(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+		part_limit: SwapLimit<Balance>,
+	) -> Vec<Vec<SwapPath>> {
+		let mut candidates: Vec<Vec<SwapPath>> =
+			vec![vec![SwapPath::Dex(vec![supply_currency_id, target_currency_id])]];
+
+		for path_joint in T::DexSwapJointList::get() {
+			if !path_joint.is_empty() {
+				let mut dex_path = vec![];
+				if supply_currency_id != path_joint[0] {
+					dex_path.push(supply_currency_id);
+				}
+				dex_path.extend(path_joint.clone());
+				if target_currency_id != path_joint[path_joint.len() - 1] {
+					dex_path.push(target_currency_id);
+				}
+				candidates.push(vec![SwapPath::Dex(dex_path)]);
+			}
+		}
+
+		let input_amount = match limit {
+			SwapLimit::ExactSupply(supply_amount, _) => supply_amount,
+			SwapLimit::ExactTarget(max_supply_amount, _) => max_supply_amount,
+		};
+		if let Some((pool_id, input_index, output_index, _)) =
+			T::StableAsset::get_best_route(supply_currency_id, target_currency_id, input_amount)
+		{
+			candidates.push(vec![SwapPath::Taiga(pool_id, input_index, output_index)]);
+		}
+
+		if let Some(paths) = Self::aggregated_swap_paths((supply_currency_id, target_currency_id)) {
+			candidates.push(paths.into_inner());
+		}
+
+		// cap the candidates before quoting them, to bound the cost of the split swap.
+		let max_routes = T::MaxSplitSwapRoutes::get() as usize;
+		let mut used_pools: Vec<SwapPool> = vec![];
+		let mut routes: Vec<Vec<SwapPath>> = vec![];
+		for paths in candidates {
+			if routes.len() >= max_routes {
+				break;
+			}
+			if let Some(pools) = Self::get_swap_pools(&paths) {
+				if pools.iter().all(|pool| !used_pools.contains(pool)) {
+					used_pools.extend(pools);
+					routes.push(paths);
+				}
+			}
+		}
+
+		routes
+			.into_iter()
+			.filter(|paths| Self::get_aggregated_swap_amount(paths, part_limit).is_some())
+			.collect()
+	}
+
+	/// Split the swap across the candidate routes to get the most target amount for `ExactSupply`,
+	/// or pay the least supply amount for `ExactTarget`. The amount is divided into
+	/// `SPLIT_SWAP_PARTS` parts, and each part goes to the route with the best marginal price.
+	/// For `ExactTarget`, the target amount of each route is the exact amount allocated to it.
+	pub fn get_split_swap_routes(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<Vec<SwapRoute<CurrencyId, Balance>>> {
+		let amount = match limit {
+			SwapLimit::ExactSupply(supply_amount, _) => supply_amount,
+			SwapLimit::ExactTarget(_, target_amount) => target_amount,
+		};
+		if amount.is_zero() {
+			return None;
+		}
+
+		let part_amount = amount / Balance::from(SPLIT_SWAP_PARTS);
+		let last_part_amount = amount.saturating_sub(part_amount.saturating_mul(Balance::from(SPLIT_SWAP_PARTS - 1)));
+		let quote = |paths: &[SwapPath], amount: Balance| match limit {
+			SwapLimit::ExactSupply(_, _) => {
+				Self::get_aggregated_swap_amount(paths, SwapLimit::ExactSupply(amount, Zero::zero()))
+			}
+			SwapLimit::ExactTarget(_, _) => {
+				Self::get_aggregated_swap_amount(paths, SwapLimit::ExactTarget(Balance::max_value(), amount))
+			}
+		};
+		let part_limit = match limit {
+			SwapLimit::ExactSupply(_, _) => SwapLimit::ExactSupply(last_part_amount, Zero::zero()),
+			SwapLimit::ExactTarget(_, _) => SwapLimit::ExactTarget(Balance::max_value(), last_part_amount),
+		};
+
+		let candidates = Self::get_split_swap_candidates(supply_currency_id, target_currency_id, limit, part_limit);
+		// (allocated amount, supply amount, target amount) of each candidate route
+		let mut allocations: Vec<(Balance, Balance, Balance)> = vec![Default::default(); candidates.len()];
+
+		for part in 0..SPLIT_SWAP_PARTS {
+			let amount = if part == SPLIT_SWAP_PARTS - 1 {
+				last_part_amount
+			} else {
+				part_amount
+			};
+			if amount.is_zero() {
+				continue;
+			}
+
+			// (index, supply amount, target amount, marginal supply amount, marginal target amount)
+			let mut best: Option<(usize, Balance, Balance, Balance, Balance)> = None;
+			for (index, paths) in candidates.iter().enumerate() {
+				let (allocated_amount, allocated_supply, allocated_target) = allocations[index];
+				if let Some((supply_amount, target_amount)) = quote(paths, allocated_amount.saturating_add(amount)) {
+					let marginal_supply = supply_amount.saturating_sub(allocated_supply);
+					let marginal_target = target_amount.saturating_sub(allocated_target);
+					let is_better = best.map_or(true, |(_, _, _, best_supply, best_target)| match limit {
+						SwapLimit::ExactSupply(_, _) => marginal_target > best_target,
+						SwapLimit::ExactTarget(_, _) => marginal_supply < best_supply,
+					});
+					if is_better {
+						best = Some((index, supply_amount, target_amount, marginal_supply, marginal_target));
+					}
+				}
+			}
+
+			let (index, supply_amount, target_amount, _, _) = best?;
+			allocations[index] = (
+				allocations[index].0.saturating_add(amount),
+				supply_amount,
+				target_amount,
+			);
+		}
+
+		let routes: Vec<SwapRoute<CurrencyId, Balance>> = candidates
+			.into_iter()
+			.zip(allocations)
+			.filter(|(_, (allocated_amount, _, _))| !allocated_amount.is_zero())
+			.map(|(path, (allocated_amount, supply_amount, target_amount))| SwapRoute {
+				path,
+				supply_amount,
+				target_amount: match limit {
+					SwapLimit::ExactSupply(_, _) => target_amount,
+					SwapLimit::ExactTarget(_, _) => allocated_amount,
+				},
+			})
+			.collect();
+
+		let (total_supply_amount, total_target_amount) = Self::get_split_swap_amount(&routes)?;
+		match limit {
+			SwapLimit::ExactSupply(_, min_target_amount) => {
+				if total_target_amount < min_target_amount {
+					return None;
+				}
+			}
+			SwapLimit::ExactTarget(max_supply_amount, _) => {
+				if total_supply_amount > max_supply_amount {
+					return None;
+				}
+			}
+		}
+
+		Some(routes)
+	}
+
+	/// The total supply amount and target amount of the split swap routes.
+	pub fn get_split_swap_amount(routes: &[SwapRoute<CurrencyId, Balance>]) -> Option<(Balance, Balance)> {
+		routes.iter().try_fold(
+			(Balance::zero(), Balance::zero()),
+			|(total_supply_amount, total_target_amount), route| {
+				Some((
+					total_supply_amount.checked_add(route.supply_amount)?,
+					total_target_amount.checked_add(route.target_amount)?,
+				))
+			},
+		)
+	}
+
+	/// Split the swap across the routes found by `get_split_swap_routes` and swap them atomically.
+	///
+	/// For `ExactTarget`, the routes through Taiga are swapped first, as their target amount may
+	/// exceed the allocated amount by rounding. The DEX routes are swapped at exact target amount
+	/// afterwards and take up the excess, so that the total target amount is exact.
+	#[transactional]
+	fn do_split_swap(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let mut routes =
+			Self::get_split_swap_routes(supply_currency_id, target_currency_id, limit).ok_or(Error::<T>::CannotSwap)?;
+
+		let mut total_supply_amount: Balance = Zero::zero();
+		let mut total_target_amount: Balance = Zero::zero();
+
+		match limit {
+			SwapLimit::ExactSupply(_, min_target_amount) => {
+				for route in routes {
+					let (actual_supply, actual_target) = Self::do_aggregated_swap(
+						who,
+						&route.path,
+						SwapLimit::ExactSupply(route.supply_amount, Zero::zero()),
+					)?;
+
+					total_supply_amount = total_supply_amount
+						.checked_add(actual_supply)
+						.ok_or(ArithmeticError::Overflow)?;
+					total_target_amount = total_target_amount
+						.checked_add(actual_target)
+						.ok_or(ArithmeticError::Overflow)?;
+				}
+
+				// the result must meet the min target amount.
+				ensure!(total_target_amount >= min_target_amount, Error::<T>::CannotSwap);
+			}
+			SwapLimit::ExactTarget(max_supply_amount, _) => {
+				routes.sort_by_key(|route| matches!(route.path.as_slice(), [SwapPath::Dex(_)]));
+				let mut allocated_target_amount: Balance = Zero::zero();
+
+				for route in routes {
+					let (actual_supply, actual_target) = match route.path.as_slice() {
+						[SwapPath::Dex(dex_path)] => {
+							let excess_amount = total_target_amount.saturating_sub(allocated_target_amount);
+							let target_amount = route.target_amount.saturating_sub(excess_amount);
+							if target_amount.is_zero() {
+								(Zero::zero(), Zero::zero())
+							} else {
+								T::DEX::swap_with_specific_path(
+									who,
+									dex_path,
+									SwapLimit::ExactTarget(route.supply_amount, target_amount),
+								)?
+							}
+						}
+						_ => Self::do_aggregated_swap(
+							who,
+							&route.path,
+							SwapLimit::ExactTarget(route.supply_amount, route.target_amount),
+						)?,
+					};
+
+					allocated_target_amount = allocated_target_amount
+						.checked_add(route.target_amount)
+						.ok_or(ArithmeticError::Overflow)?;
+					total_supply_amount = total_supply_amount
+						.checked_add(actual_supply)
+						.ok_or(ArithmeticError::Overflow)?;
+					total_target_amount = total_target_amount
+						.checked_add(actual_target)
+						.ok_or(ArithmeticError::Overflow)?;
+				}
+
+				// the result must meet the max supply amount.
+				ensure!(total_supply_amount <= max_supply_amount, Error::<T>::CannotSwap);
+			}
+		}
+
+		Ok((total_supply_amount, total_target_amount))
+	}
 }
 
 /// Swap by Acala DEX which has specific joints.
@@ -739,6 +1125,37 @@ impl<T: Config> Swap<T::AccountId, Balance, CurrencyId> for AggregatedSwap<T> {
 	}
 }
 
+/// Split the swap across multiple routes of DEX and Taiga which don't share any pool, to reduce
+/// the slippage of large swaps.
+pub struct SplitSwap<T>(PhantomData<T>);
+impl<T: Config> Swap<T::AccountId, Balance, CurrencyId> for SplitSwap<T> {
+	fn get_swap_amount(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<(Balance, Balance)> {
+		let routes = Pallet::<T>::get_split_swap_routes(supply_currency_id, target_currency_id, limit)?;
+		Pallet::<T>::get_split_swap_amount(&routes)
+	}
+
+	fn swap(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		Pallet::<T>::do_split_swap(who, supply_currency_id, target_currency_id, limit)
+	}
+
+	fn swap_by_aggregated_path(
+		who: &T::AccountId,
+		swap_path: &[SwapPath],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Pallet::<T>::do_aggregated_swap(who, swap_path, limit)
+	}
+}
+
 pub struct RebasedStableAssetErrorConvertor<T>(PhantomData<T>);
 impl<T: Config> Convert<RebasedStableAssetError, DispatchError> for RebasedStableAssetErrorConvertor<T> {
 	fn convert(e: RebasedStableAssetError) -> DispatchError {
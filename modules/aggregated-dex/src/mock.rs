@@ -190,6 +190,7 @@ impl nutsfinance_stable_asset::Config for Runtime {
 
 parameter_types! {
 	pub static DexSwapJointList: Vec<Vec<CurrencyId>> = vec![];
+	pub static MaxSplitSwapRoutes: u32 = 3;
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 }
 
@@ -199,6 +200,7 @@ impl Config for Runtime {
 	type GovernanceOrigin = EnsureSignedBy<Admin, AccountId>;
 	type DexSwapJointList = DexSwapJointList;
 	type SwapPathLimit = ConstU32<3>;
+	type MaxSplitSwapRoutes = MaxSplitSwapRoutes;
 	type WeightInfo = ();
 }
 
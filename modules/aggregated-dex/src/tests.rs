@@ -1301,3 +1301,303 @@ fn aggregated_swap_swap_work() {
 		);
 	});
 }
+
+fn initial_split_swap_pools() {
+	assert_ok!(inject_liquidity(DOT, AUSD, 100_000_000_000u128, 1_000_000_000_000u128));
+	assert_ok!(inject_liquidity(DOT, LDOT, 100_000_000_000u128, 1_000_000_000_000u128));
+	assert_ok!(inject_liquidity(
+		LDOT,
+		AUSD,
+		1_000_000_000_000u128,
+		1_000_000_000_000u128
+	));
+	// the joint AUSD results in the path DOT-AUSD, which shares the pool with the direct path.
+	set_dex_swap_joint_list(vec![vec![LDOT], vec![AUSD]]);
+}
+
+#[test]
+fn get_split_swap_routes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(DOT, AUSD, SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			None
+		);
+
+		initial_split_swap_pools();
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(DOT, AUSD, SwapLimit::ExactSupply(0, 0)),
+			None
+		);
+
+		// the whole amount by a single path
+		assert_eq!(
+			Dex::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Some((10_000_000_000u128, 90_909_090_909u128))
+		);
+		assert_eq!(
+			Dex::get_swap_amount(&vec![DOT, LDOT, AUSD], SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Some((10_000_000_000u128, 83_333_333_333u128))
+		);
+
+		let routes = vec![
+			SwapRoute {
+				path: vec![SwapPath::Dex(vec![DOT, AUSD])],
+				supply_amount: 7_000_000_000u128,
+				target_amount: 65_420_560_747u128,
+			},
+			SwapRoute {
+				path: vec![SwapPath::Dex(vec![DOT, LDOT, AUSD])],
+				supply_amount: 3_000_000_000u128,
+				target_amount: 28_301_886_792u128,
+			},
+		];
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(DOT, AUSD, SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Some(routes.clone())
+		);
+		assert_eq!(
+			AggregatedDex::get_split_swap_amount(&routes),
+			Some((10_000_000_000u128, 93_722_447_539u128))
+		);
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(
+				DOT,
+				AUSD,
+				SwapLimit::ExactSupply(10_000_000_000u128, 93_722_447_540u128)
+			),
+			None
+		);
+
+		let routes = vec![
+			SwapRoute {
+				path: vec![SwapPath::Dex(vec![DOT, AUSD])],
+				supply_amount: 7_526_881_721u128,
+				target_amount: 70_000_000_000u128,
+			},
+			SwapRoute {
+				path: vec![SwapPath::Dex(vec![DOT, LDOT, AUSD])],
+				supply_amount: 3_191_489_362u128,
+				target_amount: 30_000_000_000u128,
+			},
+		];
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(DOT, AUSD, SwapLimit::ExactTarget(u128::MAX, 100_000_000_000u128)),
+			Some(routes.clone())
+		);
+		assert_eq!(
+			AggregatedDex::get_split_swap_amount(&routes),
+			Some((10_718_371_083u128, 100_000_000_000u128))
+		);
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(
+				DOT,
+				AUSD,
+				SwapLimit::ExactTarget(10_718_371_082u128, 100_000_000_000u128)
+			),
+			None
+		);
+
+		// the routes are bounded by MaxSplitSwapRoutes
+		MaxSplitSwapRoutes::set(1);
+		assert_eq!(
+			AggregatedDex::get_split_swap_routes(DOT, AUSD, SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Some(vec![SwapRoute {
+				path: vec![SwapPath::Dex(vec![DOT, AUSD])],
+				supply_amount: 10_000_000_000u128,
+				target_amount: 90_909_090_909u128,
+			}])
+		);
+	});
+}
+
+#[test]
+fn split_swap_with_exact_supply_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_supply(Origin::signed(ALICE), DOT, AUSD, 10_000_000_000u128, 0),
+			Error::<Runtime>::CannotSwap
+		);
+
+		initial_split_swap_pools();
+
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_supply(Origin::none(), DOT, AUSD, 10_000_000_000u128, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_supply(Origin::signed(ALICE), DOT, AUSD, 0, 0),
+			Error::<Runtime>::CannotSwap
+		);
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_supply(
+				Origin::signed(ALICE),
+				DOT,
+				AUSD,
+				10_000_000_000u128,
+				93_722_447_540u128
+			),
+			Error::<Runtime>::CannotSwap
+		);
+
+		assert_ok!(AggregatedDex::split_swap_with_exact_supply(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			10_000_000_000u128,
+			93_722_447_539u128
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 90_000_000_000u128);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 93_722_447_539u128);
+		assert_eq!(
+			Dex::get_liquidity_pool(DOT, AUSD),
+			(107_000_000_000u128, 934_579_439_253u128)
+		);
+	});
+}
+
+#[test]
+fn split_swap_with_exact_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initial_split_swap_pools();
+
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_target(
+				Origin::none(),
+				DOT,
+				AUSD,
+				100_000_000_000u128,
+				10_718_371_083u128
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AggregatedDex::split_swap_with_exact_target(
+				Origin::signed(ALICE),
+				DOT,
+				AUSD,
+				100_000_000_000u128,
+				10_718_371_082u128
+			),
+			Error::<Runtime>::CannotSwap
+		);
+
+		assert_ok!(AggregatedDex::split_swap_with_exact_target(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			100_000_000_000u128,
+			10_718_371_083u128
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 89_281_628_917u128);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100_000_000_000u128);
+	});
+}
+
+#[test]
+fn swap_with_exact_supply_by_split_routes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initial_split_swap_pools();
+
+		// splitting the swap gets more target amount than the given path
+		assert_ok!(AggregatedDex::swap_with_exact_supply(
+			Origin::signed(ALICE),
+			vec![SwapPath::Dex(vec![DOT, AUSD])],
+			10_000_000_000u128,
+			93_722_447_539u128
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 90_000_000_000u128);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 93_722_447_539u128);
+		assert_eq!(
+			Dex::get_liquidity_pool(DOT, AUSD),
+			(107_000_000_000u128, 934_579_439_253u128)
+		);
+	});
+}
+
+#[test]
+fn swap_with_exact_target_by_split_routes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initial_split_swap_pools();
+
+		// the given path alone costs more than the max supply amount
+		assert_ok!(AggregatedDex::swap_with_exact_target(
+			Origin::signed(ALICE),
+			vec![SwapPath::Dex(vec![DOT, AUSD])],
+			100_000_000_000u128,
+			10_718_371_083u128
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 89_281_628_917u128);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100_000_000_000u128);
+	});
+}
+
+#[test]
+fn split_swap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			SplitSwap::<Runtime>::swap(&ALICE, DOT, AUSD, SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Error::<Runtime>::CannotSwap
+		);
+
+		initial_split_swap_pools();
+		assert_eq!(
+			SplitSwap::<Runtime>::get_swap_amount(DOT, AUSD, SwapLimit::ExactSupply(10_000_000_000u128, 0)),
+			Some((10_000_000_000u128, 93_722_447_539u128))
+		);
+		assert_eq!(
+			SplitSwap::<Runtime>::get_swap_amount(DOT, AUSD, SwapLimit::ExactTarget(u128::MAX, 100_000_000_000u128)),
+			Some((10_718_371_083u128, 100_000_000_000u128))
+		);
+
+		assert_noop!(
+			SplitSwap::<Runtime>::swap(
+				&ALICE,
+				DOT,
+				AUSD,
+				SwapLimit::ExactSupply(10_000_000_000u128, 93_722_447_540u128)
+			),
+			Error::<Runtime>::CannotSwap
+		);
+		assert_eq!(
+			SplitSwap::<Runtime>::swap(
+				&ALICE,
+				DOT,
+				AUSD,
+				SwapLimit::ExactSupply(10_000_000_000u128, 93_722_447_539u128)
+			),
+			Ok((10_000_000_000u128, 93_722_447_539u128))
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 90_000_000_000u128);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 93_722_447_539u128);
+	});
+}
+
+#[test]
+fn split_swap_by_exact_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initial_split_swap_pools();
+
+		assert_noop!(
+			SplitSwap::<Runtime>::swap(
+				&ALICE,
+				DOT,
+				AUSD,
+				SwapLimit::ExactTarget(10_718_371_082u128, 100_000_000_000u128)
+			),
+			Error::<Runtime>::CannotSwap
+		);
+		assert_eq!(
+			SplitSwap::<Runtime>::swap(
+				&ALICE,
+				DOT,
+				AUSD,
+				SwapLimit::ExactTarget(10_718_371_083u128, 100_000_000_000u128)
+			),
+			Ok((10_718_371_083u128, 100_000_000_000u128))
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 89_281_628_917u128);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100_000_000_000u128);
+	});
+}
@@ -49,6 +49,8 @@ pub trait WeightInfo {
 	fn swap_with_exact_supply(u: u32, ) -> Weight;
 	fn swap_with_exact_target(u: u32, ) -> Weight;
 	fn update_aggregated_swap_paths(u: u32, ) -> Weight;
+	fn split_swap_with_exact_supply(r: u32, ) -> Weight;
+	fn split_swap_with_exact_target(r: u32, ) -> Weight;
 }
 
 /// Weights for module_aggregated_dex using the Acala node and recommended hardware.
@@ -79,6 +81,24 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
 	}
+	fn split_swap_with_exact_supply(r: u32, ) -> Weight {
+		(19_836_000 as Weight)
+			// Standard Error: 386_000
+			.saturating_add((164_207_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn split_swap_with_exact_target(r: u32, ) -> Weight {
+		(22_471_000 as Weight)
+			// Standard Error: 453_000
+			.saturating_add((171_938_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -108,4 +128,22 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
 	}
+	fn split_swap_with_exact_supply(r: u32, ) -> Weight {
+		(19_836_000 as Weight)
+			// Standard Error: 386_000
+			.saturating_add((164_207_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	fn split_swap_with_exact_target(r: u32, ) -> Weight {
+		(22_471_000 as Weight)
+			// Standard Error: 453_000
+			.saturating_add((171_938_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
 }
@@ -110,6 +110,10 @@ impl module_evm::Config for Runtime {
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = ConstU64<100>;
+	type CodeUpgradeDelay = ConstU64<10>;
+	type MinBaseFeePerGas = ConstU128<0>;
+	type BlockGasTarget = ConstU64<10_000_000>;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 
 	type Runner = module_evm::runner::stack::Runner<Self>;
@@ -0,0 +1,1496 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Concentrated DEX Module
+//!
+//! ## Overview
+//!
+//! Concentrated liquidity pools alongside the constant product pools of the
+//! DEX module, the swap mechanism refers to the design of Uniswap V3.
+//! Liquidity providers supply liquidity to a price range between two ticks,
+//! which is much more capital efficient than supplying to the whole price
+//! curve. Every position is minted as an NFT of the position class registered
+//! by governance, the owner of the NFT owns the liquidity and the trading fees
+//! accrued by the position.
+//!
+//! The module implements `DEXManager` and `Swap`, and `DexWithConcentratedPools`
+//! combines the concentrated liquidity pools with the `DEXManager` of the DEX
+//! module, so the aggregated DEX and the transaction payment fee swap can route
+//! through whichever pools give the better price.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::type_complexity)]
+#![allow(clippy::unused_unit)]
+
+use codec::MaxEncodedLen;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{tokens::nonfungibles::Inspect, Currency as _, ExistenceRequirement},
+	transactional, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use math::{MAX_SQRT_PRICE, MAX_TICK, MIN_SQRT_PRICE, MIN_TICK};
+use module_nft::{ClassIdOf, TokenIdOf};
+use orml_traits::MultiCurrency;
+use primitives::{nft::ClassProperty, Balance, CurrencyId, TradingPair};
+use scale_info::TypeInfo;
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*, vec};
+use support::{AggregatedSwapPath, DEXManager, Price, Swap, SwapLimit};
+
+pub mod math;
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// State of a concentrated liquidity pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct PoolInfo {
+	/// The sqrt price of the first currency in the second currency, Q64.64.
+	pub sqrt_price: u128,
+	/// The greatest tick whose sqrt price is not greater than `sqrt_price`.
+	pub tick: i32,
+	/// Trading fee rate, (numerator, denominator).
+	pub fee_rate: (u32, u32),
+	/// Ticks of positions must be multiples of the tick spacing.
+	pub tick_spacing: u32,
+	/// The liquidity of positions whose range covers the current price.
+	pub liquidity: u128,
+	/// The accumulated fee of the first currency per unit of liquidity,
+	/// Q128.128, wraps on overflow.
+	pub fee_growth_global_0: U256,
+	/// The accumulated fee of the second currency per unit of liquidity,
+	/// Q128.128, wraps on overflow.
+	pub fee_growth_global_1: U256,
+}
+
+/// State of an initialized tick.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, MaxEncodedLen, TypeInfo)]
+pub struct TickInfo {
+	/// The total liquidity of positions using this tick as a boundary.
+	pub liquidity_gross: u128,
+	/// The liquidity added to the pool when the price crosses this tick
+	/// upwards.
+	pub liquidity_net: i128,
+	/// The fee growth of the first currency on the other side of this tick
+	/// from the current tick.
+	pub fee_growth_outside_0: U256,
+	/// The fee growth of the second currency on the other side of this tick
+	/// from the current tick.
+	pub fee_growth_outside_1: U256,
+}
+
+/// A liquidity position, owned by the owner of the position NFT.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct Position {
+	/// The trading pair of the pool.
+	pub trading_pair: TradingPair,
+	/// The lower tick of the price range.
+	pub tick_lower: i32,
+	/// The upper tick of the price range.
+	pub tick_upper: i32,
+	/// The liquidity of the position.
+	pub liquidity: u128,
+	/// The fee growth of the first currency inside the price range when the
+	/// fees were last settled.
+	pub fee_growth_inside_0_last: U256,
+	/// The fee growth of the second currency inside the price range when the
+	/// fees were last settled.
+	pub fee_growth_inside_1_last: U256,
+}
+
+/// Result of swapping in a single pool.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+struct SwapResult {
+	/// The supply amount, fee included.
+	amount_in: Balance,
+	/// The target amount.
+	amount_out: Balance,
+	/// The pool state after the swap.
+	pool: PoolInfo,
+	/// The crossed ticks with the global fee growths when crossing them.
+	crossed_ticks: Vec<(i32, U256, U256)>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + module_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for transfer currencies
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The limit for length of trading path
+		#[pallet::constant]
+		type TradingPathLimit: Get<u32>;
+
+		/// The maximum number of initialized ticks of a pool.
+		#[pallet::constant]
+		type MaxInitializedTicks: Get<u32>;
+
+		/// The maximum number of ticks a swap can cross along the whole path.
+		#[pallet::constant]
+		type MaxTickCrossings: Get<u32>;
+
+		/// The minimum liquidity of a position, keeps the initialized ticks of
+		/// pools from being filled by dust positions.
+		#[pallet::constant]
+		type MinimumLiquidity: Get<u128>;
+
+		/// The concentrated DEX's module id, keep all assets in concentrated
+		/// DEX.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which may register the position class and create pools.
+		type ListingOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Currency id is not allowed
+		InvalidCurrencyId,
+		/// The pool of the trading pair already exists
+		PoolAlreadyExists,
+		/// The pool of the trading pair doesn't exist
+		PoolNotFound,
+		/// The fee rate is invalid
+		InvalidFeeRate,
+		/// The tick spacing is invalid
+		InvalidTickSpacing,
+		/// The price is out of the supported range
+		InvalidPrice,
+		/// The tick range is invalid
+		InvalidTickRange,
+		/// The pool has too many initialized ticks
+		TooManyTicks,
+		/// The liquidity is zero or exceeds the liquidity of the position
+		InvalidLiquidity,
+		/// The liquidity increment is less than the minimum acceptable
+		/// liquidity
+		UnacceptableLiquidityIncrement,
+		/// The withdrawn amounts are less than the minimum acceptable amounts
+		UnacceptableLiquidityWithdrawn,
+		/// The liquidity is not enough to swap
+		InsufficientLiquidity,
+		/// The supply amount is more than max supply amount
+		ExcessiveSupplyAmount,
+		/// The target amount is less than min target amount
+		InsufficientTargetAmount,
+		/// The supply amount is zero
+		ZeroSupplyAmount,
+		/// The target amount is zero
+		ZeroTargetAmount,
+		/// Invalid trading path length
+		InvalidTradingPathLength,
+		/// Target currency is same as the supply currency, or the path uses a
+		/// pool more than once
+		InvalidTradingPath,
+		/// The position class hasn't been registered
+		PositionClassNotSet,
+		/// The position class has been registered
+		PositionClassAlreadySet,
+		/// The position doesn't exist
+		PositionNotFound,
+		/// The caller is not the owner of the position
+		NoPermission,
+		/// The position still has liquidity
+		PositionNotEmpty,
+		/// The operation is not supported by concentrated liquidity pools
+		NotSupported,
+		/// The liquidity of the position is below the minimum liquidity
+		BelowMinimumLiquidity,
+		/// The swap crosses more ticks than allowed
+		TooManyTickCrossings,
+		/// The position class is burnable
+		BurnablePositionClass,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The NFT class of positions is registered.
+		PositionClassSet { class_id: ClassIdOf<T> },
+		/// A concentrated liquidity pool is created.
+		PoolCreated {
+			trading_pair: TradingPair,
+			sqrt_price: u128,
+			tick: i32,
+			fee_rate: (u32, u32),
+			tick_spacing: u32,
+		},
+		/// A position is minted.
+		PositionMinted {
+			owner: T::AccountId,
+			position_id: TokenIdOf<T>,
+			trading_pair: TradingPair,
+			tick_lower: i32,
+			tick_upper: i32,
+			liquidity: u128,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// Liquidity of a position is withdrawn.
+		LiquidityDecreased {
+			owner: T::AccountId,
+			position_id: TokenIdOf<T>,
+			liquidity: u128,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// Trading fees accrued by a position are collected.
+		FeesCollected {
+			owner: T::AccountId,
+			position_id: TokenIdOf<T>,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// A position without liquidity is burned.
+		PositionBurned {
+			owner: T::AccountId,
+			position_id: TokenIdOf<T>,
+		},
+		/// Use supply currency to swap target currency.
+		Swap {
+			trader: T::AccountId,
+			path: Vec<CurrencyId>,
+			liquidity_changes: Vec<Balance>,
+		},
+	}
+
+	/// The NFT class of positions.
+	///
+	/// PositionClassId: ClassId
+	#[pallet::storage]
+	#[pallet::getter(fn position_class_id)]
+	pub type PositionClassId<T: Config> = StorageValue<_, ClassIdOf<T>, OptionQuery>;
+
+	/// Concentrated liquidity pools.
+	///
+	/// Pools: map TradingPair => PoolInfo
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, TradingPair, PoolInfo, OptionQuery>;
+
+	/// Initialized ticks of pools.
+	///
+	/// Ticks: double_map TradingPair, Tick => TickInfo
+	#[pallet::storage]
+	#[pallet::getter(fn ticks)]
+	pub type Ticks<T: Config> = StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, i32, TickInfo, ValueQuery>;
+
+	/// Sorted initialized ticks of pools.
+	///
+	/// InitializedTicks: map TradingPair => Vec<Tick>
+	#[pallet::storage]
+	#[pallet::getter(fn initialized_ticks)]
+	pub type InitializedTicks<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, BoundedVec<i32, T::MaxInitializedTicks>, ValueQuery>;
+
+	/// Liquidity positions.
+	///
+	/// Positions: map PositionId => Position
+	#[pallet::storage]
+	#[pallet::getter(fn positions)]
+	pub type Positions<T: Config> = StorageMap<_, Twox64Concat, TokenIdOf<T>, Position, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Trading with concentrated DEX, swap with exact supply amount
+		///
+		/// - `path`: trading path.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: acceptable minimum target amount.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::swap_with_exact_supply(path.len() as u32, T::MaxTickCrossings::get())
+		)]
+		#[transactional]
+		pub fn swap_with_exact_supply(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (_, _, crossed_ticks) =
+				Self::do_swap(&who, &path, SwapLimit::ExactSupply(supply_amount, min_target_amount))?;
+			Ok(Some(<T as Config>::WeightInfo::swap_with_exact_supply(
+				path.len() as u32,
+				crossed_ticks,
+			))
+			.into())
+		}
+
+		/// Trading with concentrated DEX, swap with exact target amount
+		///
+		/// - `path`: trading path.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::swap_with_exact_target(path.len() as u32, T::MaxTickCrossings::get())
+		)]
+		#[transactional]
+		pub fn swap_with_exact_target(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] target_amount: Balance,
+			#[pallet::compact] max_supply_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (_, _, crossed_ticks) =
+				Self::do_swap(&who, &path, SwapLimit::ExactTarget(max_supply_amount, target_amount))?;
+			Ok(Some(<T as Config>::WeightInfo::swap_with_exact_target(
+				path.len() as u32,
+				crossed_ticks,
+			))
+			.into())
+		}
+
+		/// Mint a position NFT which supplies liquidity to the price range
+		/// `[tick_lower, tick_upper)` of the pool.
+		///
+		/// Ticks are in the price of the first currency of the trading pair in
+		/// the second currency.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `tick_lower`: the lower tick of the price range.
+		/// - `tick_upper`: the upper tick of the price range.
+		/// - `max_amount_a`: maximum amount of currency_id_a is allowed to inject to the pool.
+		/// - `max_amount_b`: maximum amount of currency_id_b is allowed to inject to the pool.
+		/// - `min_liquidity`: minimum acceptable liquidity of the position.
+		#[pallet::weight(<T as Config>::WeightInfo::mint_position())]
+		#[transactional]
+		pub fn mint_position(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			tick_lower: i32,
+			tick_upper: i32,
+			#[pallet::compact] max_amount_a: Balance,
+			#[pallet::compact] max_amount_b: Balance,
+			#[pallet::compact] min_liquidity: u128,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_mint_position(
+				&who,
+				currency_id_a,
+				currency_id_b,
+				tick_lower,
+				tick_upper,
+				max_amount_a,
+				max_amount_b,
+				min_liquidity,
+			)?;
+			Ok(())
+		}
+
+		/// Withdraw liquidity from a position, the trading fees accrued by the
+		/// position are collected at the same time.
+		///
+		/// - `position_id`: the token id of the position NFT.
+		/// - `liquidity`: liquidity amount to withdraw.
+		/// - `min_amount_0`: minimum acceptable withdrawn amount of the first currency.
+		/// - `min_amount_1`: minimum acceptable withdrawn amount of the second currency.
+		#[pallet::weight(<T as Config>::WeightInfo::decrease_liquidity())]
+		#[transactional]
+		pub fn decrease_liquidity(
+			origin: OriginFor<T>,
+			position_id: TokenIdOf<T>,
+			#[pallet::compact] liquidity: u128,
+			#[pallet::compact] min_amount_0: Balance,
+			#[pallet::compact] min_amount_1: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_decrease_liquidity(&who, position_id, liquidity, min_amount_0, min_amount_1)?;
+			Ok(())
+		}
+
+		/// Collect the trading fees accrued by a position.
+		///
+		/// - `position_id`: the token id of the position NFT.
+		#[pallet::weight(<T as Config>::WeightInfo::collect_fees())]
+		#[transactional]
+		pub fn collect_fees(origin: OriginFor<T>, position_id: TokenIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_collect_fees(&who, position_id)?;
+			Ok(())
+		}
+
+		/// Burn the NFT of a position without liquidity.
+		///
+		/// - `position_id`: the token id of the position NFT.
+		#[pallet::weight(<T as Config>::WeightInfo::burn_position())]
+		#[transactional]
+		pub fn burn_position(origin: OriginFor<T>, position_id: TokenIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_burn_position(&who, position_id)
+		}
+
+		/// Create a concentrated liquidity pool.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `initial_price`: the initial price of currency_id_a in currency_id_b.
+		/// - `fee_rate`: trading fee rate, (numerator, denominator).
+		/// - `tick_spacing`: ticks of positions must be multiples of the tick spacing.
+		#[pallet::weight((<T as Config>::WeightInfo::create_pool(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			initial_price: Price,
+			fee_rate: (u32, u32),
+			tick_spacing: u32,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			Self::do_create_pool(currency_id_a, currency_id_b, initial_price, fee_rate, tick_spacing)
+		}
+
+		/// Register the NFT class of positions, can only be set once.
+		///
+		/// The class must be mintable and transferable, and owned by an account
+		/// this module can mint on behalf of. It must not be burnable, position
+		/// NFTs are only burned by `burn_position`.
+		///
+		/// - `class_id`: the NFT class id.
+		#[pallet::weight((<T as Config>::WeightInfo::set_position_class(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_position_class(origin: OriginFor<T>, class_id: ClassIdOf<T>) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(Self::position_class_id().is_none(), Error::<T>::PositionClassAlreadySet);
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(module_nft::Error::<T>::ClassIdNotFound)?;
+			ensure!(
+				!class_info.data.properties.0.contains(ClassProperty::Burnable),
+				Error::<T>::BurnablePositionClass
+			);
+
+			PositionClassId::<T>::put(class_id);
+			Self::deposit_event(Event::PositionClassSet { class_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn account_id() -> T::AccountId {
+		<T as Config>::PalletId::get().into_account_truncating()
+	}
+
+	fn add_delta(liquidity: u128, delta: i128) -> Result<u128, ArithmeticError> {
+		if delta >= 0 {
+			liquidity
+				.checked_add(delta.unsigned_abs())
+				.ok_or(ArithmeticError::Overflow)
+		} else {
+			liquidity
+				.checked_sub(delta.unsigned_abs())
+				.ok_or(ArithmeticError::Underflow)
+		}
+	}
+
+	fn do_create_pool(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		initial_price: Price,
+		fee_rate: (u32, u32),
+		tick_spacing: u32,
+	) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(!Pools::<T>::contains_key(trading_pair), Error::<T>::PoolAlreadyExists);
+		ensure!(
+			!fee_rate.1.is_zero() && fee_rate.0 < fee_rate.1,
+			Error::<T>::InvalidFeeRate
+		);
+		ensure!(
+			!tick_spacing.is_zero() && tick_spacing <= MAX_TICK.unsigned_abs(),
+			Error::<T>::InvalidTickSpacing
+		);
+
+		let price = if currency_id_a == trading_pair.first() {
+			initial_price
+		} else {
+			initial_price.reciprocal().ok_or(Error::<T>::InvalidPrice)?
+		};
+		let sqrt_price = ((U256::from(price.into_inner()) << 128) / U256::from(Price::accuracy())).integer_sqrt();
+		let sqrt_price = u128::try_from(sqrt_price).map_err(|_| Error::<T>::InvalidPrice)?;
+		let tick = math::sqrt_price_to_tick(sqrt_price).ok_or(Error::<T>::InvalidPrice)?;
+
+		Pools::<T>::insert(
+			trading_pair,
+			PoolInfo {
+				sqrt_price,
+				tick,
+				fee_rate,
+				tick_spacing,
+				liquidity: Zero::zero(),
+				fee_growth_global_0: Zero::zero(),
+				fee_growth_global_1: Zero::zero(),
+			},
+		);
+		Self::deposit_event(Event::PoolCreated {
+			trading_pair,
+			sqrt_price,
+			tick,
+			fee_rate,
+			tick_spacing,
+		});
+		Ok(())
+	}
+
+	fn ensure_position_owner(who: &T::AccountId, position_id: TokenIdOf<T>) -> Result<ClassIdOf<T>, DispatchError> {
+		let class_id = Self::position_class_id().ok_or(Error::<T>::PositionClassNotSet)?;
+		let owner = <module_nft::Pallet<T> as Inspect<T::AccountId>>::owner(&class_id, &position_id)
+			.ok_or(Error::<T>::PositionNotFound)?;
+		ensure!(owner == *who, Error::<T>::NoPermission);
+		Ok(class_id)
+	}
+
+	fn mint_position_nft(who: &T::AccountId) -> Result<TokenIdOf<T>, DispatchError> {
+		let class_id = Self::position_class_id().ok_or(Error::<T>::PositionClassNotSet)?;
+		let class_owner = <module_nft::Pallet<T> as Inspect<T::AccountId>>::collection_owner(&class_id)
+			.ok_or(module_nft::Error::<T>::ClassIdNotFound)?;
+
+		// the class owner pays the token deposit to the minter when minting, so the
+		// minter funds it first.
+		<T as module_nft::Config>::Currency::transfer(
+			who,
+			&class_owner,
+			<T as module_nft::Config>::CreateTokenDeposit::get(),
+			ExistenceRequirement::AllowDeath,
+		)?;
+		let token_ids =
+			module_nft::Pallet::<T>::do_mint(&class_owner, who, class_id, Default::default(), Default::default(), 1)?;
+		token_ids
+			.first()
+			.copied()
+			.ok_or_else(|| module_nft::Error::<T>::TokenIdNotFound.into())
+	}
+
+	fn validate_tick_range(pool: &PoolInfo, tick_lower: i32, tick_upper: i32) -> DispatchResult {
+		let tick_spacing = pool.tick_spacing as i32;
+		ensure!(
+			tick_lower < tick_upper
+				&& tick_lower >= MIN_TICK
+				&& tick_upper <= MAX_TICK
+				&& tick_lower % tick_spacing == 0
+				&& tick_upper % tick_spacing == 0,
+			Error::<T>::InvalidTickRange
+		);
+		Ok(())
+	}
+
+	/// Update the liquidity using `tick` as a boundary, `upper` indicates
+	/// whether `tick` is the upper tick of the position. Returns true if the
+	/// tick isn't used by any position anymore.
+	fn update_tick(
+		trading_pair: &TradingPair,
+		pool: &PoolInfo,
+		tick: i32,
+		liquidity_delta: i128,
+		upper: bool,
+	) -> Result<bool, DispatchError> {
+		let mut info = Self::ticks(trading_pair, tick);
+		let liquidity_gross = Self::add_delta(info.liquidity_gross, liquidity_delta)?;
+
+		if info.liquidity_gross.is_zero() && !liquidity_gross.is_zero() {
+			// by convention, all fee growth before initializing a tick happened below it.
+			if tick <= pool.tick {
+				info.fee_growth_outside_0 = pool.fee_growth_global_0;
+				info.fee_growth_outside_1 = pool.fee_growth_global_1;
+			}
+			InitializedTicks::<T>::try_mutate(trading_pair, |ticks| -> DispatchResult {
+				if let Err(index) = ticks.binary_search(&tick) {
+					ticks.try_insert(index, tick).map_err(|_| Error::<T>::TooManyTicks)?;
+				}
+				Ok(())
+			})?;
+		}
+
+		info.liquidity_gross = liquidity_gross;
+		info.liquidity_net = if upper {
+			info.liquidity_net.checked_sub(liquidity_delta)
+		} else {
+			info.liquidity_net.checked_add(liquidity_delta)
+		}
+		.ok_or(ArithmeticError::Overflow)?;
+		Ticks::<T>::insert(trading_pair, tick, info);
+
+		Ok(liquidity_gross.is_zero())
+	}
+
+	fn clear_tick(trading_pair: &TradingPair, tick: i32) {
+		Ticks::<T>::remove(trading_pair, tick);
+		InitializedTicks::<T>::mutate(trading_pair, |ticks| {
+			if let Ok(index) = ticks.binary_search(&tick) {
+				ticks.remove(index);
+			}
+		});
+	}
+
+	fn get_fee_growth_inside(
+		trading_pair: &TradingPair,
+		pool: &PoolInfo,
+		tick_lower: i32,
+		tick_upper: i32,
+	) -> (U256, U256) {
+		let wrapping_sub = |a: U256, b: U256| a.overflowing_sub(b).0;
+		let lower = Self::ticks(trading_pair, tick_lower);
+		let upper = Self::ticks(trading_pair, tick_upper);
+
+		let (below_0, below_1) = if pool.tick >= tick_lower {
+			(lower.fee_growth_outside_0, lower.fee_growth_outside_1)
+		} else {
+			(
+				wrapping_sub(pool.fee_growth_global_0, lower.fee_growth_outside_0),
+				wrapping_sub(pool.fee_growth_global_1, lower.fee_growth_outside_1),
+			)
+		};
+		let (above_0, above_1) = if pool.tick < tick_upper {
+			(upper.fee_growth_outside_0, upper.fee_growth_outside_1)
+		} else {
+			(
+				wrapping_sub(pool.fee_growth_global_0, upper.fee_growth_outside_0),
+				wrapping_sub(pool.fee_growth_global_1, upper.fee_growth_outside_1),
+			)
+		};
+
+		(
+			wrapping_sub(wrapping_sub(pool.fee_growth_global_0, below_0), above_0),
+			wrapping_sub(wrapping_sub(pool.fee_growth_global_1, below_1), above_1),
+		)
+	}
+
+	/// Apply `liquidity_delta` to the position, its ticks and the pool.
+	/// Returns the currency amounts of the liquidity change, rounded up when
+	/// adding liquidity and down when removing, and the fees accrued by the
+	/// position since they were last settled.
+	fn modify_position(
+		position: &mut Position,
+		liquidity_delta: i128,
+	) -> Result<((Balance, Balance), (Balance, Balance)), DispatchError> {
+		let trading_pair = position.trading_pair;
+		let mut pool = Self::pools(trading_pair).ok_or(Error::<T>::PoolNotFound)?;
+
+		let (clear_lower, clear_upper) = if liquidity_delta != 0 {
+			(
+				Self::update_tick(&trading_pair, &pool, position.tick_lower, liquidity_delta, false)?,
+				Self::update_tick(&trading_pair, &pool, position.tick_upper, liquidity_delta, true)?,
+			)
+		} else {
+			(false, false)
+		};
+
+		let (fee_growth_inside_0, fee_growth_inside_1) =
+			Self::get_fee_growth_inside(&trading_pair, &pool, position.tick_lower, position.tick_upper);
+		let accrued_fee = |fee_growth_inside: U256, fee_growth_inside_last: U256| -> Result<Balance, DispatchError> {
+			let fee = math::mul_div(
+				fee_growth_inside.overflowing_sub(fee_growth_inside_last).0,
+				position.liquidity.into(),
+				U256::one() << 128,
+				false,
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+			Ok(Balance::try_from(fee).map_err(|_| ArithmeticError::Overflow)?)
+		};
+		let fees = (
+			accrued_fee(fee_growth_inside_0, position.fee_growth_inside_0_last)?,
+			accrued_fee(fee_growth_inside_1, position.fee_growth_inside_1_last)?,
+		);
+		position.fee_growth_inside_0_last = fee_growth_inside_0;
+		position.fee_growth_inside_1_last = fee_growth_inside_1;
+		position.liquidity = Self::add_delta(position.liquidity, liquidity_delta)?;
+
+		let sqrt_price_lower = math::tick_to_sqrt_price(position.tick_lower).ok_or(Error::<T>::InvalidTickRange)?;
+		let sqrt_price_upper = math::tick_to_sqrt_price(position.tick_upper).ok_or(Error::<T>::InvalidTickRange)?;
+		let amounts = math::get_amounts_for_liquidity(
+			pool.sqrt_price,
+			sqrt_price_lower,
+			sqrt_price_upper,
+			liquidity_delta.unsigned_abs(),
+			liquidity_delta > 0,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		if pool.tick >= position.tick_lower && pool.tick < position.tick_upper {
+			pool.liquidity = Self::add_delta(pool.liquidity, liquidity_delta)?;
+			Pools::<T>::insert(trading_pair, pool);
+		}
+
+		if clear_lower {
+			Self::clear_tick(&trading_pair, position.tick_lower);
+		}
+		if clear_upper {
+			Self::clear_tick(&trading_pair, position.tick_upper);
+		}
+
+		Ok((amounts, fees))
+	}
+
+	fn do_mint_position(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		tick_lower: i32,
+		tick_upper: i32,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_liquidity: u128,
+	) -> Result<(TokenIdOf<T>, Balance, Balance, u128), DispatchError> {
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let pool = Self::pools(trading_pair).ok_or(Error::<T>::PoolNotFound)?;
+		Self::validate_tick_range(&pool, tick_lower, tick_upper)?;
+
+		let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.first() {
+			(max_amount_a, max_amount_b)
+		} else {
+			(max_amount_b, max_amount_a)
+		};
+		let liquidity = math::get_liquidity_for_amounts(
+			pool.sqrt_price,
+			math::tick_to_sqrt_price(tick_lower).ok_or(Error::<T>::InvalidTickRange)?,
+			math::tick_to_sqrt_price(tick_upper).ok_or(Error::<T>::InvalidTickRange)?,
+			max_amount_0,
+			max_amount_1,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+		ensure!(!liquidity.is_zero(), Error::<T>::InvalidLiquidity);
+		ensure!(liquidity >= min_liquidity, Error::<T>::UnacceptableLiquidityIncrement);
+		ensure!(
+			liquidity >= T::MinimumLiquidity::get(),
+			Error::<T>::BelowMinimumLiquidity
+		);
+
+		let mut position = Position {
+			trading_pair,
+			tick_lower,
+			tick_upper,
+			liquidity: Zero::zero(),
+			fee_growth_inside_0_last: Zero::zero(),
+			fee_growth_inside_1_last: Zero::zero(),
+		};
+		let liquidity_delta = i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?;
+		let ((amount_0, amount_1), _) = Self::modify_position(&mut position, liquidity_delta)?;
+		ensure!(
+			amount_0 <= max_amount_0 && amount_1 <= max_amount_1,
+			Error::<T>::ExcessiveSupplyAmount
+		);
+
+		let module_account_id = Self::account_id();
+		<T as Config>::Currency::transfer(trading_pair.first(), who, &module_account_id, amount_0)?;
+		<T as Config>::Currency::transfer(trading_pair.second(), who, &module_account_id, amount_1)?;
+
+		let position_id = Self::mint_position_nft(who)?;
+		Positions::<T>::insert(position_id, position);
+
+		Self::deposit_event(Event::PositionMinted {
+			owner: who.clone(),
+			position_id,
+			trading_pair,
+			tick_lower,
+			tick_upper,
+			liquidity,
+			amount_0,
+			amount_1,
+		});
+
+		if currency_id_a == trading_pair.first() {
+			Ok((position_id, amount_0, amount_1, liquidity))
+		} else {
+			Ok((position_id, amount_1, amount_0, liquidity))
+		}
+	}
+
+	fn pay_fees(
+		who: &T::AccountId,
+		position_id: TokenIdOf<T>,
+		trading_pair: &TradingPair,
+		(fee_0, fee_1): (Balance, Balance),
+	) -> DispatchResult {
+		if fee_0.is_zero() && fee_1.is_zero() {
+			return Ok(());
+		}
+
+		let module_account_id = Self::account_id();
+		<T as Config>::Currency::transfer(trading_pair.first(), &module_account_id, who, fee_0)?;
+		<T as Config>::Currency::transfer(trading_pair.second(), &module_account_id, who, fee_1)?;
+
+		Self::deposit_event(Event::FeesCollected {
+			owner: who.clone(),
+			position_id,
+			amount_0: fee_0,
+			amount_1: fee_1,
+		});
+		Ok(())
+	}
+
+	fn do_decrease_liquidity(
+		who: &T::AccountId,
+		position_id: TokenIdOf<T>,
+		liquidity: u128,
+		min_amount_0: Balance,
+		min_amount_1: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::ensure_position_owner(who, position_id)?;
+
+		Positions::<T>::try_mutate(
+			position_id,
+			|maybe_position| -> Result<(Balance, Balance), DispatchError> {
+				let position = maybe_position.as_mut().ok_or(Error::<T>::PositionNotFound)?;
+				ensure!(
+					!liquidity.is_zero() && liquidity <= position.liquidity,
+					Error::<T>::InvalidLiquidity
+				);
+				let remaining_liquidity = position.liquidity - liquidity;
+				ensure!(
+					remaining_liquidity.is_zero() || remaining_liquidity >= T::MinimumLiquidity::get(),
+					Error::<T>::BelowMinimumLiquidity
+				);
+
+				let liquidity_delta = i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?;
+				let ((amount_0, amount_1), fees) = Self::modify_position(position, -liquidity_delta)?;
+				ensure!(
+					amount_0 >= min_amount_0 && amount_1 >= min_amount_1,
+					Error::<T>::UnacceptableLiquidityWithdrawn
+				);
+
+				let module_account_id = Self::account_id();
+				<T as Config>::Currency::transfer(position.trading_pair.first(), &module_account_id, who, amount_0)?;
+				<T as Config>::Currency::transfer(position.trading_pair.second(), &module_account_id, who, amount_1)?;
+
+				Self::deposit_event(Event::LiquidityDecreased {
+					owner: who.clone(),
+					position_id,
+					liquidity,
+					amount_0,
+					amount_1,
+				});
+				Self::pay_fees(who, position_id, &position.trading_pair, fees)?;
+
+				Ok((amount_0, amount_1))
+			},
+		)
+	}
+
+	fn do_collect_fees(who: &T::AccountId, position_id: TokenIdOf<T>) -> Result<(Balance, Balance), DispatchError> {
+		Self::ensure_position_owner(who, position_id)?;
+
+		Positions::<T>::try_mutate(
+			position_id,
+			|maybe_position| -> Result<(Balance, Balance), DispatchError> {
+				let position = maybe_position.as_mut().ok_or(Error::<T>::PositionNotFound)?;
+				let (_, fees) = Self::modify_position(position, 0)?;
+				Self::pay_fees(who, position_id, &position.trading_pair, fees)?;
+				Ok(fees)
+			},
+		)
+	}
+
+	fn do_burn_position(who: &T::AccountId, position_id: TokenIdOf<T>) -> DispatchResult {
+		let class_id = Self::ensure_position_owner(who, position_id)?;
+		let position = Self::positions(position_id).ok_or(Error::<T>::PositionNotFound)?;
+		ensure!(position.liquidity.is_zero(), Error::<T>::PositionNotEmpty);
+
+		Positions::<T>::remove(position_id);
+		module_nft::Pallet::<T>::do_force_burn(who.clone(), (class_id, position_id))?;
+
+		Self::deposit_event(Event::PositionBurned {
+			owner: who.clone(),
+			position_id,
+		});
+		Ok(())
+	}
+
+	/// Simulate a swap in the pool of `trading_pair`. `amount` is the supply
+	/// amount for exact supply swaps, or the target amount for exact target
+	/// swaps. The swap can cross at most `max_tick_crossings` ticks.
+	fn compute_swap(
+		trading_pair: &TradingPair,
+		zero_for_one: bool,
+		amount: Balance,
+		exact_supply: bool,
+		max_tick_crossings: u32,
+	) -> Result<SwapResult, DispatchError> {
+		let mut pool = Self::pools(trading_pair).ok_or(Error::<T>::PoolNotFound)?;
+		let initialized_ticks = Self::initialized_ticks(trading_pair);
+		let mut amount_remaining = amount;
+		let mut amount_calculated: Balance = Zero::zero();
+		let mut crossed_ticks = vec![];
+
+		while !amount_remaining.is_zero() {
+			let next_tick = if zero_for_one {
+				initialized_ticks.iter().rev().find(|tick| **tick <= pool.tick)
+			} else {
+				initialized_ticks.iter().find(|tick| **tick > pool.tick)
+			}
+			.copied();
+			let sqrt_price_target = match next_tick {
+				Some(tick) => math::tick_to_sqrt_price(tick).ok_or(Error::<T>::InvalidTickRange)?,
+				None if zero_for_one => MIN_SQRT_PRICE,
+				None => MAX_SQRT_PRICE,
+			};
+
+			let step = math::compute_swap_step(
+				pool.sqrt_price,
+				sqrt_price_target,
+				pool.liquidity,
+				amount_remaining,
+				exact_supply,
+				pool.fee_rate,
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+			let step_amount_in = step
+				.amount_in
+				.checked_add(step.fee_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			let (step_amount_specified, step_amount_calculated) = if exact_supply {
+				(step_amount_in, step.amount_out)
+			} else {
+				(step.amount_out, step_amount_in)
+			};
+			amount_remaining = amount_remaining
+				.checked_sub(step_amount_specified)
+				.ok_or(ArithmeticError::Underflow)?;
+			amount_calculated = amount_calculated
+				.checked_add(step_amount_calculated)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			if !pool.liquidity.is_zero() {
+				let fee_growth =
+					math::mul_div(step.fee_amount.into(), U256::one() << 128, pool.liquidity.into(), false)
+						.ok_or(ArithmeticError::Overflow)?;
+				if zero_for_one {
+					pool.fee_growth_global_0 = pool.fee_growth_global_0.overflowing_add(fee_growth).0;
+				} else {
+					pool.fee_growth_global_1 = pool.fee_growth_global_1.overflowing_add(fee_growth).0;
+				}
+			}
+
+			let sqrt_price_start = pool.sqrt_price;
+			pool.sqrt_price = step.sqrt_price_next;
+
+			if step.sqrt_price_next == sqrt_price_target {
+				// reached the price bound, the liquidity is used up.
+				let tick = next_tick.ok_or(Error::<T>::InsufficientLiquidity)?;
+				ensure!(
+					(crossed_ticks.len() as u32) < max_tick_crossings,
+					Error::<T>::TooManyTickCrossings
+				);
+				let liquidity_net = Self::ticks(trading_pair, tick).liquidity_net;
+				crossed_ticks.push((tick, pool.fee_growth_global_0, pool.fee_growth_global_1));
+
+				if zero_for_one {
+					let liquidity_delta = liquidity_net.checked_neg().ok_or(ArithmeticError::Overflow)?;
+					pool.liquidity = Self::add_delta(pool.liquidity, liquidity_delta)?;
+					pool.tick = tick - 1;
+				} else {
+					pool.liquidity = Self::add_delta(pool.liquidity, liquidity_net)?;
+					pool.tick = tick;
+				}
+			} else {
+				ensure!(amount_remaining.is_zero(), Error::<T>::InsufficientLiquidity);
+				if step.sqrt_price_next != sqrt_price_start {
+					pool.tick = math::sqrt_price_to_tick(pool.sqrt_price).ok_or(Error::<T>::InvalidPrice)?;
+				}
+			}
+		}
+
+		let (amount_in, amount_out) = if exact_supply {
+			(amount, amount_calculated)
+		} else {
+			(amount_calculated, amount)
+		};
+
+		Ok(SwapResult {
+			amount_in,
+			amount_out,
+			pool,
+			crossed_ticks,
+		})
+	}
+
+	fn apply_swap(trading_pair: &TradingPair, result: &SwapResult) {
+		for (tick, fee_growth_global_0, fee_growth_global_1) in result.crossed_ticks.iter() {
+			Ticks::<T>::mutate(trading_pair, tick, |info| {
+				info.fee_growth_outside_0 = fee_growth_global_0.overflowing_sub(info.fee_growth_outside_0).0;
+				info.fee_growth_outside_1 = fee_growth_global_1.overflowing_sub(info.fee_growth_outside_1).0;
+			});
+		}
+		Pools::<T>::insert(trading_pair, result.pool);
+	}
+
+	fn validate_path(path: &[CurrencyId]) -> Result<Vec<(TradingPair, bool)>, DispatchError> {
+		let path_length = path.len();
+		ensure!(
+			path_length >= 2 && path_length <= T::TradingPathLimit::get().saturated_into(),
+			Error::<T>::InvalidTradingPathLength
+		);
+		ensure!(path.get(0) != path.get(path_length - 1), Error::<T>::InvalidTradingPath);
+
+		let mut pairs: Vec<(TradingPair, bool)> = vec![];
+		for currency_ids in path.windows(2) {
+			let trading_pair = TradingPair::from_currency_ids(currency_ids[0], currency_ids[1])
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			// every hop is simulated against the pool state before the swap.
+			ensure!(
+				!pairs.iter().any(|(pair, _)| *pair == trading_pair),
+				Error::<T>::InvalidTradingPath
+			);
+			pairs.push((trading_pair, currency_ids[0] == trading_pair.first()));
+		}
+
+		Ok(pairs)
+	}
+
+	/// Simulate a swap along `path`, returns the swap result of every hop.
+	fn compute_swap_by_path(
+		path: &[CurrencyId],
+		amount: Balance,
+		exact_supply: bool,
+	) -> Result<Vec<(TradingPair, SwapResult)>, DispatchError> {
+		let pairs = Self::validate_path(path)?;
+		let mut results = Vec::with_capacity(pairs.len());
+		let mut amount = amount;
+		let mut max_tick_crossings = T::MaxTickCrossings::get();
+
+		if exact_supply {
+			for (trading_pair, zero_for_one) in pairs {
+				let result = Self::compute_swap(&trading_pair, zero_for_one, amount, true, max_tick_crossings)?;
+				ensure!(!result.amount_out.is_zero(), Error::<T>::ZeroTargetAmount);
+				amount = result.amount_out;
+				max_tick_crossings -= result.crossed_ticks.len() as u32;
+				results.push((trading_pair, result));
+			}
+		} else {
+			for (trading_pair, zero_for_one) in pairs.into_iter().rev() {
+				let result = Self::compute_swap(&trading_pair, zero_for_one, amount, false, max_tick_crossings)?;
+				ensure!(!result.amount_in.is_zero(), Error::<T>::ZeroSupplyAmount);
+				amount = result.amount_in;
+				max_tick_crossings -= result.crossed_ticks.len() as u32;
+				results.push((trading_pair, result));
+			}
+			results.reverse();
+		}
+
+		Ok(results)
+	}
+
+	fn get_swap_amounts(path: &[CurrencyId], limit: SwapLimit<Balance>) -> Result<Vec<Balance>, DispatchError> {
+		let results = match limit {
+			SwapLimit::ExactSupply(exact_supply_amount, _) => {
+				Self::compute_swap_by_path(path, exact_supply_amount, true)?
+			}
+			SwapLimit::ExactTarget(_, exact_target_amount) => {
+				Self::compute_swap_by_path(path, exact_target_amount, false)?
+			}
+		};
+
+		let mut amounts = vec![results[0].1.amount_in];
+		amounts.extend(results.iter().map(|(_, result)| result.amount_out));
+		Ok(amounts)
+	}
+
+	/// Ensured atomic. Returns the supply amount, the target amount and the
+	/// number of crossed ticks.
+	#[transactional]
+	fn do_swap(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance, u32), DispatchError> {
+		let results = match limit {
+			SwapLimit::ExactSupply(exact_supply_amount, _) => {
+				Self::compute_swap_by_path(path, exact_supply_amount, true)?
+			}
+			SwapLimit::ExactTarget(_, exact_target_amount) => {
+				Self::compute_swap_by_path(path, exact_target_amount, false)?
+			}
+		};
+		let supply_amount = results[0].1.amount_in;
+		let target_amount = results[results.len() - 1].1.amount_out;
+		match limit {
+			SwapLimit::ExactSupply(_, minimum_target_amount) => ensure!(
+				target_amount >= minimum_target_amount,
+				Error::<T>::InsufficientTargetAmount
+			),
+			SwapLimit::ExactTarget(maximum_supply_amount, _) => ensure!(
+				supply_amount <= maximum_supply_amount,
+				Error::<T>::ExcessiveSupplyAmount
+			),
+		}
+
+		let module_account_id = Self::account_id();
+		<T as Config>::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
+		for (trading_pair, result) in results.iter() {
+			Self::apply_swap(trading_pair, result);
+		}
+		<T as Config>::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
+
+		let mut liquidity_changes = vec![supply_amount];
+		liquidity_changes.extend(results.iter().map(|(_, result)| result.amount_out));
+		Self::deposit_event(Event::Swap {
+			trader: who.clone(),
+			path: path.to_vec(),
+			liquidity_changes,
+		});
+		let crossed_ticks = results
+			.iter()
+			.map(|(_, result)| result.crossed_ticks.len() as u32)
+			.sum();
+		Ok((supply_amount, target_amount, crossed_ticks))
+	}
+
+	fn swap_with_path(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::do_swap(who, path, limit).map(|(supply_amount, target_amount, _)| (supply_amount, target_amount))
+	}
+}
+
+impl<T: Config> DEXManager<T::AccountId, Balance, CurrencyId> for Pallet<T> {
+	/// Returns the virtual reserves of the liquidity in range at the current
+	/// price, which reflect the pool price like the constant product pools.
+	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		let virtual_reserves = || -> Option<(Balance, Balance)> {
+			let trading_pair = TradingPair::from_currency_ids(currency_id_a, currency_id_b)?;
+			let pool = Self::pools(trading_pair)?;
+			let amount_0 = math::mul_div(pool.liquidity.into(), U256::one() << 64, pool.sqrt_price.into(), false)?;
+			let amount_1 = math::mul_div(pool.liquidity.into(), pool.sqrt_price.into(), U256::one() << 64, false)?;
+			let (amount_0, amount_1) = (Balance::try_from(amount_0).ok()?, Balance::try_from(amount_1).ok()?);
+
+			if currency_id_a == trading_pair.first() {
+				Some((amount_0, amount_1))
+			} else {
+				Some((amount_1, amount_0))
+			}
+		};
+
+		virtual_reserves().unwrap_or_default()
+	}
+
+	/// Positions are NFTs, there is no liquidity token.
+	fn get_liquidity_token_address(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> Option<H160> {
+		None
+	}
+
+	fn get_swap_amount(path: &[CurrencyId], limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+		let amounts = Self::get_swap_amounts(path, limit).ok()?;
+		let (supply_amount, target_amount) = (amounts[0], amounts[amounts.len() - 1]);
+		match limit {
+			SwapLimit::ExactSupply(_, minimum_target_amount) if target_amount >= minimum_target_amount => {
+				Some((supply_amount, target_amount))
+			}
+			SwapLimit::ExactTarget(maximum_supply_amount, _) if supply_amount <= maximum_supply_amount => {
+				Some((supply_amount, target_amount))
+			}
+			_ => None,
+		}
+	}
+
+	fn get_best_price_swap_path(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+		alternative_path_joint_list: Vec<Vec<CurrencyId>>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		let default_swap_path = vec![supply_currency_id, target_currency_id];
+		let mut maybe_best = <Self as DEXManager<_, _, _>>::get_swap_amount(&default_swap_path, limit)
+			.map(|(supply_amount, target_amount)| (default_swap_path, supply_amount, target_amount));
+
+		for path_joint in alternative_path_joint_list {
+			if !path_joint.is_empty() {
+				let mut swap_path = vec![];
+
+				if supply_currency_id != path_joint[0] {
+					swap_path.push(supply_currency_id);
+				}
+
+				swap_path.extend(path_joint.clone());
+
+				if target_currency_id != path_joint[path_joint.len() - 1] {
+					swap_path.push(target_currency_id);
+				}
+
+				if let Some((supply_amount, target_amount)) =
+					<Self as DEXManager<_, _, _>>::get_swap_amount(&swap_path, limit)
+				{
+					if let Some((_, previous_supply, previous_target)) = maybe_best {
+						if supply_amount > previous_supply || target_amount < previous_target {
+							continue;
+						}
+					}
+
+					maybe_best = Some((swap_path, supply_amount, target_amount));
+				}
+			}
+		}
+
+		maybe_best
+	}
+
+	fn swap_with_specific_path(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::swap_with_path(who, path, limit)
+	}
+
+	/// Mint a full range position NFT to `who`, the returned share is the
+	/// liquidity of the position.
+	#[transactional]
+	fn add_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_share_increment: Balance,
+		stake_increment_share: bool,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		ensure!(!stake_increment_share, Error::<T>::NotSupported);
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let tick_spacing = Self::pools(trading_pair).ok_or(Error::<T>::PoolNotFound)?.tick_spacing as i32;
+
+		let (_, amount_a, amount_b, liquidity) = Self::do_mint_position(
+			who,
+			currency_id_a,
+			currency_id_b,
+			MIN_TICK / tick_spacing * tick_spacing,
+			MAX_TICK / tick_spacing * tick_spacing,
+			max_amount_a,
+			max_amount_b,
+			min_share_increment,
+		)?;
+		Ok((amount_a, amount_b, liquidity))
+	}
+
+	/// Liquidity is owned by position NFTs, use `decrease_liquidity` instead.
+	fn remove_liquidity(
+		_who: &T::AccountId,
+		_currency_id_a: CurrencyId,
+		_currency_id_b: CurrencyId,
+		_remove_share: Balance,
+		_min_withdrawn_a: Balance,
+		_min_withdrawn_b: Balance,
+		_by_unstake: bool,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Err(Error::<T>::NotSupported.into())
+	}
+}
+
+impl<T: Config> Swap<T::AccountId, Balance, CurrencyId> for Pallet<T> {
+	fn get_swap_amount(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Option<(Balance, Balance)> {
+		<Self as DEXManager<_, _, _>>::get_swap_amount(&[supply_currency_id, target_currency_id], limit)
+	}
+
+	fn swap(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::swap_with_path(who, &[supply_currency_id, target_currency_id], limit)
+	}
+
+	fn swap_by_path(
+		who: &T::AccountId,
+		swap_path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Self::swap_with_path(who, swap_path, limit)
+	}
+
+	// only a single path of concentrated liquidity pools is supported.
+	fn swap_by_aggregated_path(
+		who: &T::AccountId,
+		swap_path: &[AggregatedSwapPath<CurrencyId>],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		match swap_path {
+			[AggregatedSwapPath::Dex(path)] => Self::swap_with_path(who, path, limit),
+			_ => Err(Error::<T>::NotSupported.into()),
+		}
+	}
+}
+
+/// `DEXManager` of the constant product pools of `Dex` and the concentrated
+/// liquidity pools. Swaps go to whichever pools give the better price, the
+/// liquidity is managed by `Dex`.
+pub struct DexWithConcentratedPools<T, Dex>(PhantomData<(T, Dex)>);
+
+impl<T, Dex> DexWithConcentratedPools<T, Dex>
+where
+	T: Config,
+	Dex: DEXManager<T::AccountId, Balance, CurrencyId>,
+{
+	/// Returns true if the swap amounts `a` are better than `b`, ties go to
+	/// `b`.
+	fn is_better(limit: SwapLimit<Balance>, a: (Balance, Balance), b: (Balance, Balance)) -> bool {
+		match limit {
+			SwapLimit::ExactSupply(..) => a.1 > b.1,
+			SwapLimit::ExactTarget(..) => a.0 < b.0,
+		}
+	}
+
+	fn use_concentrated_pools(path: &[CurrencyId], limit: SwapLimit<Balance>) -> bool {
+		match (
+			<Pallet<T> as DEXManager<_, _, _>>::get_swap_amount(path, limit),
+			Dex::get_swap_amount(path, limit),
+		) {
+			(Some(concentrated), Some(dex)) => Self::is_better(limit, concentrated, dex),
+			(Some(_), None) => true,
+			_ => false,
+		}
+	}
+}
+
+impl<T, Dex> DEXManager<T::AccountId, Balance, CurrencyId> for DexWithConcentratedPools<T, Dex>
+where
+	T: Config,
+	Dex: DEXManager<T::AccountId, Balance, CurrencyId>,
+{
+	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		Dex::get_liquidity_pool(currency_id_a, currency_id_b)
+	}
+
+	fn get_liquidity_token_address(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<H160> {
+		Dex::get_liquidity_token_address(currency_id_a, currency_id_b)
+	}
+
+	fn get_swap_amount(path: &[CurrencyId], limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+		if Self::use_concentrated_pools(path, limit) {
+			<Pallet<T> as DEXManager<_, _, _>>::get_swap_amount(path, limit)
+		} else {
+			Dex::get_swap_amount(path, limit)
+		}
+	}
+
+	fn get_best_price_swap_path(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+		alternative_path_joint_list: Vec<Vec<CurrencyId>>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		let maybe_concentrated = <Pallet<T> as DEXManager<_, _, _>>::get_best_price_swap_path(
+			supply_currency_id,
+			target_currency_id,
+			limit,
+			alternative_path_joint_list.clone(),
+		);
+		let maybe_dex = Dex::get_best_price_swap_path(
+			supply_currency_id,
+			target_currency_id,
+			limit,
+			alternative_path_joint_list,
+		);
+
+		match (maybe_concentrated, maybe_dex) {
+			(Some(concentrated), Some(dex)) => {
+				if Self::is_better(limit, (concentrated.1, concentrated.2), (dex.1, dex.2)) {
+					Some(concentrated)
+				} else {
+					Some(dex)
+				}
+			}
+			(concentrated, dex) => concentrated.or(dex),
+		}
+	}
+
+	fn swap_with_specific_path(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		if Self::use_concentrated_pools(path, limit) {
+			Pallet::<T>::swap_with_path(who, path, limit)
+		} else {
+			Dex::swap_with_specific_path(who, path, limit)
+		}
+	}
+
+	fn add_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_share_increment: Balance,
+		stake_increment_share: bool,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		Dex::add_liquidity(
+			who,
+			currency_id_a,
+			currency_id_b,
+			max_amount_a,
+			max_amount_b,
+			min_share_increment,
+			stake_increment_share,
+		)
+	}
+
+	fn remove_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		remove_share: Balance,
+		min_withdrawn_a: Balance,
+		min_withdrawn_b: Balance,
+		by_unstake: bool,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Dex::remove_liquidity(
+			who,
+			currency_id_a,
+			currency_id_b,
+			remove_share,
+			min_withdrawn_a,
+			min_withdrawn_b,
+			by_unstake,
+		)
+	}
+}
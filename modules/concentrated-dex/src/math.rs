@@ -0,0 +1,389 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fixed point math of the concentrated liquidity pools.
+//!
+//! Prices are kept as `sqrt(amount_1 / amount_0)` in Q64.64 format, where
+//! `amount_0` and `amount_1` are the amounts of the first and second currency
+//! of the trading pair. Every tick is a 0.01% price step: `price(tick) =
+//! 1.0001 ^ tick`.
+
+use primitives::Balance;
+use sp_core::{U256, U512};
+use sp_std::convert::TryFrom;
+
+/// The minimum tick, `sqrt(1.0001 ^ MIN_TICK)` is about 2^-32.
+pub const MIN_TICK: i32 = -443_636;
+/// The maximum tick, `sqrt(1.0001 ^ MAX_TICK)` is about 2^32.
+pub const MAX_TICK: i32 = 443_636;
+/// The sqrt price of `MIN_TICK`.
+pub const MIN_SQRT_PRICE: u128 = 4_295_048_017;
+/// The sqrt price of `MAX_TICK`.
+pub const MAX_SQRT_PRICE: u128 = 79_226_673_515_401_279_992_447_579_062;
+
+/// `2^128 / sqrt(1.0001) ^ (2 ^ i)`
+const TICK_RATIOS: [u128; 19] = [
+	0xfffcb933bd6fad37aa2d162d1a594001,
+	0xfff97272373d413259a46990580e2139,
+	0xfff2e50f5f656932ef12357cf3c7fdcb,
+	0xffe5caca7e10e4e61c3624eaa0941ccf,
+	0xffcb9843d60f6159c9db58835c926643,
+	0xff973b41fa98c081472e6896dfb254bf,
+	0xff2ea16466c96a3843ec78b326b52860,
+	0xfe5dee046a99a2a811c461f1969c3052,
+	0xfcbe86c7900a88aedcffc83b479aa3a3,
+	0xf987a7253ac413176f2b074cf7815e53,
+	0xf3392b0822b70005940c7a398e4b70f2,
+	0xe7159475a2c29b7443b29c7fa6e889d8,
+	0xd097f3bdfd2022b8845ad8f792aa5825,
+	0xa9f746462d870fdf8a65dc1f90e061e4,
+	0x70d869a156d2a1b890bb3df62baf32f6,
+	0x31be135f97d08fd981231505542fcfa5,
+	0x09aa508b5b7a84e1c677de54f3e99bc8,
+	0x005d6af8dedb81196699c329225ee604,
+	0x00002216e584f5fa1ea926041bedfe97,
+];
+
+/// Result of swapping within a single tick range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapStep {
+	/// The sqrt price after the step.
+	pub sqrt_price_next: u128,
+	/// The supply amount consumed by the step, excluding the fee.
+	pub amount_in: Balance,
+	/// The target amount produced by the step.
+	pub amount_out: Balance,
+	/// The fee charged on the supply amount.
+	pub fee_amount: Balance,
+}
+
+/// Calculate `a * b / denominator` without intermediate overflow.
+pub fn mul_div(a: U256, b: U256, denominator: U256, round_up: bool) -> Option<U256> {
+	if denominator.is_zero() {
+		return None;
+	}
+	let (quotient, remainder) = (U512::from(a) * U512::from(b)).div_mod(U512::from(denominator));
+	let quotient = if round_up && !remainder.is_zero() {
+		quotient.checked_add(U512::one())?
+	} else {
+		quotient
+	};
+	U256::try_from(quotient).ok()
+}
+
+/// Calculate the sqrt price of `tick` in Q64.64, rounded up.
+pub fn tick_to_sqrt_price(tick: i32) -> Option<u128> {
+	if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+		return None;
+	}
+
+	let abs_tick = tick.unsigned_abs();
+	let mut ratio: U256 = if abs_tick & 1 != 0 {
+		TICK_RATIOS[0].into()
+	} else {
+		U256::one() << 128
+	};
+	for (i, factor) in TICK_RATIOS.iter().enumerate().skip(1) {
+		if abs_tick & (1 << i) != 0 {
+			ratio = (ratio * U256::from(*factor)) >> 128;
+		}
+	}
+	if tick > 0 {
+		ratio = U256::MAX / ratio;
+	}
+
+	// convert from Q128.128 to Q64.64
+	let round: U256 = if (ratio & U256::from(u64::MAX)).is_zero() {
+		U256::zero()
+	} else {
+		U256::one()
+	};
+	u128::try_from((ratio >> 64) + round).ok()
+}
+
+/// Get the greatest tick whose sqrt price is not greater than `sqrt_price`.
+pub fn sqrt_price_to_tick(sqrt_price: u128) -> Option<i32> {
+	if !(MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price) {
+		return None;
+	}
+
+	let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+	while low < high {
+		let mid = low + (high - low + 1) / 2;
+		if tick_to_sqrt_price(mid)? <= sqrt_price {
+			low = mid;
+		} else {
+			high = mid - 1;
+		}
+	}
+	Some(low)
+}
+
+/// Get the amount of currency_0 between two sqrt prices with `liquidity`:
+/// `liquidity * (sqrt_upper - sqrt_lower) / (sqrt_upper * sqrt_lower)`
+pub fn get_amount_0_delta(sqrt_price_a: u128, sqrt_price_b: u128, liquidity: u128, round_up: bool) -> Option<Balance> {
+	let (sqrt_lower, sqrt_upper) = if sqrt_price_a < sqrt_price_b {
+		(sqrt_price_a, sqrt_price_b)
+	} else {
+		(sqrt_price_b, sqrt_price_a)
+	};
+	if sqrt_lower == 0 {
+		return None;
+	}
+
+	let amount = mul_div(
+		U256::from(liquidity) << 64,
+		U256::from(sqrt_upper - sqrt_lower),
+		U256::from(sqrt_upper),
+		round_up,
+	)?;
+	let (amount, remainder) = amount.div_mod(U256::from(sqrt_lower));
+	let amount = if round_up && !remainder.is_zero() {
+		amount + U256::one()
+	} else {
+		amount
+	};
+	Balance::try_from(amount).ok()
+}
+
+/// Get the amount of currency_1 between two sqrt prices with `liquidity`:
+/// `liquidity * (sqrt_upper - sqrt_lower)`
+pub fn get_amount_1_delta(sqrt_price_a: u128, sqrt_price_b: u128, liquidity: u128, round_up: bool) -> Option<Balance> {
+	let sqrt_diff = sqrt_price_a.max(sqrt_price_b) - sqrt_price_a.min(sqrt_price_b);
+	let amount = mul_div(
+		U256::from(liquidity),
+		U256::from(sqrt_diff),
+		U256::one() << 64,
+		round_up,
+	)?;
+	Balance::try_from(amount).ok()
+}
+
+/// Get the next sqrt price after adding or removing `amount` of currency_0,
+/// rounded up so the price moves less than the exact value.
+fn get_next_sqrt_price_from_amount_0(sqrt_price: u128, liquidity: u128, amount: Balance, add: bool) -> Option<u128> {
+	if amount == 0 {
+		return Some(sqrt_price);
+	}
+
+	let numerator = U256::from(liquidity) << 64;
+	let product = U256::from(amount) * U256::from(sqrt_price);
+	let denominator = if add {
+		numerator.checked_add(product)?
+	} else {
+		numerator.checked_sub(product)?
+	};
+	u128::try_from(mul_div(numerator, U256::from(sqrt_price), denominator, true)?).ok()
+}
+
+/// Get the next sqrt price after adding or removing `amount` of currency_1,
+/// rounded down so the price moves less than the exact value.
+fn get_next_sqrt_price_from_amount_1(sqrt_price: u128, liquidity: u128, amount: Balance, add: bool) -> Option<u128> {
+	if liquidity == 0 {
+		return None;
+	}
+
+	let quotient = u128::try_from(mul_div(
+		U256::from(amount),
+		U256::one() << 64,
+		U256::from(liquidity),
+		!add,
+	)?)
+	.ok()?;
+	if add {
+		sqrt_price.checked_add(quotient)
+	} else {
+		sqrt_price.checked_sub(quotient).filter(|price| *price > 0)
+	}
+}
+
+/// Get the next sqrt price after supplying `amount_in` to the pool.
+pub fn get_next_sqrt_price_from_input(
+	sqrt_price: u128,
+	liquidity: u128,
+	amount_in: Balance,
+	zero_for_one: bool,
+) -> Option<u128> {
+	if zero_for_one {
+		get_next_sqrt_price_from_amount_0(sqrt_price, liquidity, amount_in, true)
+	} else {
+		get_next_sqrt_price_from_amount_1(sqrt_price, liquidity, amount_in, true)
+	}
+}
+
+/// Get the next sqrt price after taking `amount_out` from the pool.
+pub fn get_next_sqrt_price_from_output(
+	sqrt_price: u128,
+	liquidity: u128,
+	amount_out: Balance,
+	zero_for_one: bool,
+) -> Option<u128> {
+	if zero_for_one {
+		get_next_sqrt_price_from_amount_1(sqrt_price, liquidity, amount_out, false)
+	} else {
+		get_next_sqrt_price_from_amount_0(sqrt_price, liquidity, amount_out, false)
+	}
+}
+
+/// Get the max liquidity that `amount_0` and `amount_1` can provide for the
+/// range `[sqrt_price_lower, sqrt_price_upper]` at `sqrt_price`.
+pub fn get_liquidity_for_amounts(
+	sqrt_price: u128,
+	sqrt_price_lower: u128,
+	sqrt_price_upper: u128,
+	amount_0: Balance,
+	amount_1: Balance,
+) -> Option<u128> {
+	// liquidity = amount_0 * sqrt_a * sqrt_b / (sqrt_b - sqrt_a)
+	let liquidity_0 = |sqrt_a: u128, sqrt_b: u128| -> Option<u128> {
+		let intermediate = mul_div(sqrt_a.into(), sqrt_b.into(), U256::one() << 64, false)?;
+		u128::try_from(mul_div(amount_0.into(), intermediate, (sqrt_b - sqrt_a).into(), false)?).ok()
+	};
+	// liquidity = amount_1 / (sqrt_b - sqrt_a)
+	let liquidity_1 = |sqrt_a: u128, sqrt_b: u128| -> Option<u128> {
+		u128::try_from(mul_div(
+			amount_1.into(),
+			U256::one() << 64,
+			(sqrt_b - sqrt_a).into(),
+			false,
+		)?)
+		.ok()
+	};
+
+	if sqrt_price_lower >= sqrt_price_upper {
+		None
+	} else if sqrt_price <= sqrt_price_lower {
+		liquidity_0(sqrt_price_lower, sqrt_price_upper)
+	} else if sqrt_price < sqrt_price_upper {
+		Some(liquidity_0(sqrt_price, sqrt_price_upper)?.min(liquidity_1(sqrt_price_lower, sqrt_price)?))
+	} else {
+		liquidity_1(sqrt_price_lower, sqrt_price_upper)
+	}
+}
+
+/// Get the amounts of currency_0 and currency_1 represented by `liquidity` in
+/// the range `[sqrt_price_lower, sqrt_price_upper]` at `sqrt_price`.
+pub fn get_amounts_for_liquidity(
+	sqrt_price: u128,
+	sqrt_price_lower: u128,
+	sqrt_price_upper: u128,
+	liquidity: u128,
+	round_up: bool,
+) -> Option<(Balance, Balance)> {
+	if sqrt_price <= sqrt_price_lower {
+		Some((
+			get_amount_0_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?,
+			0,
+		))
+	} else if sqrt_price < sqrt_price_upper {
+		Some((
+			get_amount_0_delta(sqrt_price, sqrt_price_upper, liquidity, round_up)?,
+			get_amount_1_delta(sqrt_price_lower, sqrt_price, liquidity, round_up)?,
+		))
+	} else {
+		Some((
+			0,
+			get_amount_1_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?,
+		))
+	}
+}
+
+/// Swap within a single tick range, moving the price from `sqrt_price_current`
+/// towards `sqrt_price_target` until `amount_remaining` is consumed.
+///
+/// `amount_remaining` is the supply amount (fee included) for exact supply
+/// swaps, or the target amount for exact target swaps.
+pub fn compute_swap_step(
+	sqrt_price_current: u128,
+	sqrt_price_target: u128,
+	liquidity: u128,
+	amount_remaining: Balance,
+	exact_supply: bool,
+	fee_rate: (u32, u32),
+) -> Option<SwapStep> {
+	let zero_for_one = sqrt_price_current >= sqrt_price_target;
+	let (fee_numerator, fee_denominator) = fee_rate;
+	let fee_complement = fee_denominator.checked_sub(fee_numerator).filter(|x| *x > 0)?;
+
+	let sqrt_price_next = if exact_supply {
+		let amount_remaining_less_fee = u128::try_from(mul_div(
+			amount_remaining.into(),
+			fee_complement.into(),
+			fee_denominator.into(),
+			false,
+		)?)
+		.ok()?;
+		let amount_in_max = if zero_for_one {
+			get_amount_0_delta(sqrt_price_target, sqrt_price_current, liquidity, true)?
+		} else {
+			get_amount_1_delta(sqrt_price_current, sqrt_price_target, liquidity, true)?
+		};
+		if amount_remaining_less_fee >= amount_in_max {
+			sqrt_price_target
+		} else {
+			get_next_sqrt_price_from_input(sqrt_price_current, liquidity, amount_remaining_less_fee, zero_for_one)?
+		}
+	} else {
+		let amount_out_max = if zero_for_one {
+			get_amount_1_delta(sqrt_price_target, sqrt_price_current, liquidity, false)?
+		} else {
+			get_amount_0_delta(sqrt_price_current, sqrt_price_target, liquidity, false)?
+		};
+		if amount_remaining >= amount_out_max {
+			sqrt_price_target
+		} else {
+			get_next_sqrt_price_from_output(sqrt_price_current, liquidity, amount_remaining, zero_for_one)?
+		}
+	};
+
+	let (amount_in, mut amount_out) = if zero_for_one {
+		(
+			get_amount_0_delta(sqrt_price_next, sqrt_price_current, liquidity, true)?,
+			get_amount_1_delta(sqrt_price_next, sqrt_price_current, liquidity, false)?,
+		)
+	} else {
+		(
+			get_amount_1_delta(sqrt_price_current, sqrt_price_next, liquidity, true)?,
+			get_amount_0_delta(sqrt_price_current, sqrt_price_next, liquidity, false)?,
+		)
+	};
+
+	if !exact_supply && amount_out > amount_remaining {
+		amount_out = amount_remaining;
+	}
+
+	let fee_amount = if exact_supply && sqrt_price_next != sqrt_price_target {
+		// the remaining supply amount is taken as fee
+		amount_remaining.saturating_sub(amount_in)
+	} else {
+		u128::try_from(mul_div(
+			amount_in.into(),
+			fee_numerator.into(),
+			fee_complement.into(),
+			true,
+		)?)
+		.ok()?
+	};
+
+	Some(SwapStep {
+		sqrt_price_next,
+		amount_in,
+		amount_out,
+		fee_amount,
+	})
+}
@@ -0,0 +1,321 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the concentrated dex module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_ok, construct_runtime, ord_parameter_types, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
+};
+use frame_system::EnsureSignedBy;
+use module_nft::{ClassData, TokenData};
+use orml_traits::parameter_type_with_key;
+use primitives::{
+	nft::{ClassProperty, Properties},
+	Amount, ReserveIdentifier, TokenSymbol,
+};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+pub type BlockNumber = u64;
+pub type AccountId = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CAROL: AccountId = 3;
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const CLASS_ID: ClassIdOf<Runtime> = 0;
+pub const CREATE_TOKEN_DEPOSIT: Balance = 100;
+
+parameter_types! {
+	pub static AUSDDOTPair: TradingPair = TradingPair::from_currency_ids(AUSD, DOT).unwrap();
+	pub static DOTBTCPair: TradingPair = TradingPair::from_currency_ids(DOT, BTC).unwrap();
+	pub static MaxTickCrossings: u32 = 3;
+	pub static MockDEXSwapAmount: Option<(Balance, Balance)> = None;
+}
+
+mod concentrated_dex {
+	pub use super::super::*;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = ReserveIdentifier;
+	type WeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type OnNewTokenAccount = ();
+	type OnKilledTokenAccount = ();
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ();
+	type ProxyDepositBase = ConstU128<1>;
+	type ProxyDepositFactor = ConstU128<1>;
+	type MaxProxies = ConstU32<4>;
+	type WeightInfo = ();
+	type CallHasher = ::sp_runtime::traits::BlakeTwo256;
+	type MaxPending = ConstU32<2>;
+	type AnnouncementDepositBase = ConstU128<1>;
+	type AnnouncementDepositFactor = ConstU128<1>;
+}
+
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ClassData<Balance>;
+	type TokenData = TokenData<Balance>;
+	type MaxClassMetadata = ConstU32<1024>;
+	type MaxTokenMetadata = ConstU32<1024>;
+}
+
+parameter_types! {
+	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+}
+
+impl module_nft::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CreateClassDeposit = ConstU128<200>;
+	type CreateTokenDeposit = ConstU128<CREATE_TOKEN_DEPOSIT>;
+	type DataDepositPerByte = ConstU128<10>;
+	type PalletId = NftPalletId;
+	type MaxAttributesBytes = ConstU32<10>;
+	type WeightInfo = ();
+}
+
+ord_parameter_types! {
+	pub const ListingOrigin: AccountId = 3;
+}
+
+parameter_types! {
+	pub const ConcentratedDEXPalletId: PalletId = PalletId(*b"aca/cdex");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type TradingPathLimit = ConstU32<3>;
+	type MaxInitializedTicks = ConstU32<4>;
+	type MaxTickCrossings = MaxTickCrossings;
+	type MinimumLiquidity = ConstU128<1_000_000>;
+	type PalletId = ConcentratedDEXPalletId;
+	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type WeightInfo = ();
+}
+
+/// Constant product pools quoting `MockDEXSwapAmount` for any path, swaps
+/// don't transfer anything.
+pub struct MockDEX;
+impl DEXManager<AccountId, Balance, CurrencyId> for MockDEX {
+	fn get_liquidity_pool(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> (Balance, Balance) {
+		(1_000, 1_000)
+	}
+
+	fn get_liquidity_token_address(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> Option<H160> {
+		Some(H160::repeat_byte(1))
+	}
+
+	fn get_swap_amount(_path: &[CurrencyId], _limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+		MockDEXSwapAmount::get()
+	}
+
+	fn get_best_price_swap_path(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		_limit: SwapLimit<Balance>,
+		_alternative_path_joint_list: Vec<Vec<CurrencyId>>,
+	) -> Option<(Vec<CurrencyId>, Balance, Balance)> {
+		MockDEXSwapAmount::get().map(|(supply_amount, target_amount)| {
+			(
+				vec![supply_currency_id, target_currency_id],
+				supply_amount,
+				target_amount,
+			)
+		})
+	}
+
+	fn swap_with_specific_path(
+		_who: &AccountId,
+		_path: &[CurrencyId],
+		_limit: SwapLimit<Balance>,
+	) -> Result<(Balance, Balance), DispatchError> {
+		MockDEXSwapAmount::get().ok_or_else(|| Error::<Runtime>::InsufficientLiquidity.into())
+	}
+
+	fn add_liquidity(
+		_who: &AccountId,
+		_currency_id_a: CurrencyId,
+		_currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		_min_share_increment: Balance,
+		_stake_increment_share: bool,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		Ok((max_amount_a, max_amount_b, max_amount_a))
+	}
+
+	fn remove_liquidity(
+		_who: &AccountId,
+		_currency_id_a: CurrencyId,
+		_currency_id_b: CurrencyId,
+		remove_share: Balance,
+		_min_withdrawn_a: Balance,
+		_min_withdrawn_b: Balance,
+		_by_unstake: bool,
+	) -> Result<(Balance, Balance), DispatchError> {
+		Ok((remove_share, remove_share))
+	}
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		ConcentratedDexModule: concentrated_dex::{Pallet, Storage, Call, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
+		NFTModule: module_nft::{Pallet, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![
+				(ALICE, AUSD, 1_000_000_000_000_000_000u128),
+				(BOB, AUSD, 1_000_000_000_000_000_000u128),
+				(ALICE, BTC, 1_000_000_000_000_000_000u128),
+				(BOB, BTC, 1_000_000_000_000_000_000u128),
+				(ALICE, DOT, 1_000_000_000_000_000_000u128),
+				(BOB, DOT, 1_000_000_000_000_000_000u128),
+			],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100_000), (BOB, 100_000), (CAROL, 100_000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			// the NFT class of positions
+			assert_ok!(NFTModule::create_class(
+				Origin::signed(CAROL),
+				vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+				Default::default(),
+			));
+			// a burnable NFT class
+			assert_ok!(NFTModule::create_class(
+				Origin::signed(CAROL),
+				vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+				Default::default(),
+			));
+		});
+		ext
+	}
+}
@@ -0,0 +1,1263 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the concentrated dex module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, ReservableCurrency},
+};
+use math::SwapStep;
+use mock::{
+	AUSDDOTPair, Balances, ConcentratedDexModule, Event, ExtBuilder, ListingOrigin, MaxTickCrossings, MockDEX,
+	MockDEXSwapAmount, Origin, Runtime, System, Tokens, ALICE, AUSD, BOB, BTC, CLASS_ID, CREATE_TOKEN_DEPOSIT, DOT,
+};
+use sp_runtime::traits::{BadOrigin, One};
+
+const Q64: u128 = 1 << 64;
+const FEE_RATE: (u32, u32) = (3, 1000);
+const TICK_SPACING: u32 = 10;
+const POSITION_A_LIQUIDITY: u128 = 20_505_166_268_106;
+const POSITION_B_LIQUIDITY: u128 = 20_051_041_647_900;
+
+fn module_balance(currency_id: CurrencyId) -> Balance {
+	Tokens::free_balance(currency_id, &ConcentratedDexModule::account_id())
+}
+
+fn initialize_ausd_dot_pool() {
+	assert_ok!(ConcentratedDexModule::set_position_class(
+		Origin::signed(ListingOrigin::get()),
+		CLASS_ID
+	));
+	assert_ok!(ConcentratedDexModule::create_pool(
+		Origin::signed(ListingOrigin::get()),
+		AUSD,
+		DOT,
+		Price::one(),
+		FEE_RATE,
+		TICK_SPACING,
+	));
+}
+
+/// Position 0 over [-1000, 1000) and position 1 over [-100, 100), both
+/// owned by ALICE.
+fn initialize_ausd_dot_positions() {
+	initialize_ausd_dot_pool();
+	assert_ok!(ConcentratedDexModule::mint_position(
+		Origin::signed(ALICE),
+		AUSD,
+		DOT,
+		-1000,
+		1000,
+		1_000_000_000_000u128,
+		1_000_000_000_000u128,
+		0,
+	));
+	assert_ok!(ConcentratedDexModule::mint_position(
+		Origin::signed(ALICE),
+		AUSD,
+		DOT,
+		-100,
+		100,
+		100_000_000_000u128,
+		100_000_000_000u128,
+		0,
+	));
+}
+
+#[test]
+fn tick_math_work() {
+	assert_eq!(math::tick_to_sqrt_price(0), Some(Q64));
+	assert_eq!(math::tick_to_sqrt_price(1), Some(18_447_666_387_855_959_851));
+	assert_eq!(math::tick_to_sqrt_price(-1), Some(18_445_821_805_675_392_312));
+	assert_eq!(math::tick_to_sqrt_price(1000), Some(19_392_480_388_906_836_278));
+	assert_eq!(math::tick_to_sqrt_price(-1000), Some(17_547_129_613_991_598_782));
+	assert_eq!(math::tick_to_sqrt_price(MIN_TICK), Some(MIN_SQRT_PRICE));
+	assert_eq!(math::tick_to_sqrt_price(MAX_TICK), Some(MAX_SQRT_PRICE));
+	assert_eq!(math::tick_to_sqrt_price(MIN_TICK - 1), None);
+	assert_eq!(math::tick_to_sqrt_price(MAX_TICK + 1), None);
+
+	assert_eq!(math::sqrt_price_to_tick(Q64), Some(0));
+	assert_eq!(math::sqrt_price_to_tick(Q64 - 1), Some(-1));
+	assert_eq!(math::sqrt_price_to_tick(19_392_480_388_906_836_278), Some(1000));
+	assert_eq!(math::sqrt_price_to_tick(19_392_480_388_906_836_277), Some(999));
+	assert_eq!(math::sqrt_price_to_tick(MIN_SQRT_PRICE), Some(MIN_TICK));
+	assert_eq!(math::sqrt_price_to_tick(MAX_SQRT_PRICE), Some(MAX_TICK));
+	assert_eq!(math::sqrt_price_to_tick(MIN_SQRT_PRICE - 1), None);
+	assert_eq!(math::sqrt_price_to_tick(MAX_SQRT_PRICE + 1), None);
+}
+
+#[test]
+fn liquidity_math_work() {
+	let sqrt_price_lower = math::tick_to_sqrt_price(-1000).unwrap();
+	let sqrt_price_upper = math::tick_to_sqrt_price(1000).unwrap();
+
+	assert_eq!(
+		math::get_amount_0_delta(sqrt_price_lower, Q64, 1_000_000_000_000_000_000, true),
+		Some(51_268_468_376_766_591)
+	);
+	assert_eq!(
+		math::get_amount_1_delta(sqrt_price_lower, Q64, 1_000_000_000_000_000_000, false),
+		Some(48_768_197_581_278_888)
+	);
+
+	assert_eq!(
+		math::get_liquidity_for_amounts(
+			Q64,
+			sqrt_price_lower,
+			sqrt_price_upper,
+			1_000_000_000_000,
+			1_000_000_000_000
+		),
+		Some(POSITION_A_LIQUIDITY)
+	);
+	assert_eq!(
+		math::get_amounts_for_liquidity(Q64, sqrt_price_lower, sqrt_price_upper, POSITION_A_LIQUIDITY, true),
+		Some((1_000_000_000_000, 1_000_000_000_000))
+	);
+	// only the first currency is needed when the price is below the range
+	assert_eq!(
+		math::get_amounts_for_liquidity(
+			sqrt_price_lower - 1,
+			sqrt_price_lower,
+			sqrt_price_upper,
+			1_000_000,
+			true
+		),
+		Some((
+			math::get_amount_0_delta(sqrt_price_lower, sqrt_price_upper, 1_000_000, true).unwrap(),
+			0
+		))
+	);
+}
+
+#[test]
+fn compute_swap_step_work() {
+	let sqrt_price_target = math::tick_to_sqrt_price(-100).unwrap();
+
+	// the supply amount is used up before reaching the target price
+	assert_eq!(
+		math::compute_swap_step(
+			Q64,
+			sqrt_price_target,
+			1_000_000_000_000_000,
+			1_000_000_000,
+			true,
+			FEE_RATE
+		),
+		Some(SwapStep {
+			sqrt_price_next: 18_446_725_682_324_046_339,
+			amount_in: 997_000_000,
+			amount_out: 996_999_005,
+			fee_amount: 3_000_000,
+		})
+	);
+	assert_eq!(
+		math::compute_swap_step(
+			Q64,
+			sqrt_price_target,
+			1_000_000_000_000_000,
+			1_000_000_000,
+			false,
+			FEE_RATE
+		),
+		Some(SwapStep {
+			sqrt_price_next: 18_446_725_626_965_477_906,
+			amount_in: 1_000_001_001,
+			amount_out: 1_000_000_000,
+			fee_amount: 3_009_031,
+		})
+	);
+
+	// reached the target price
+	assert_eq!(
+		math::compute_swap_step(
+			Q64,
+			sqrt_price_target,
+			1_000_000_000_000,
+			1_000_000_000_000,
+			true,
+			FEE_RATE
+		),
+		Some(SwapStep {
+			sqrt_price_next: sqrt_price_target,
+			amount_in: 5_012_269_624,
+			amount_out: 4_987_272_070,
+			fee_amount: 15_082_056,
+		})
+	);
+
+	assert_eq!(
+		math::compute_swap_step(Q64, sqrt_price_target, 1_000_000, 1_000, true, (1, 1)),
+		None
+	);
+}
+
+#[test]
+fn set_position_class_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ConcentratedDexModule::set_position_class(Origin::signed(ALICE), CLASS_ID),
+			BadOrigin
+		);
+		assert_noop!(
+			ConcentratedDexModule::set_position_class(Origin::signed(ListingOrigin::get()), CLASS_ID + 1),
+			Error::<Runtime>::BurnablePositionClass
+		);
+		assert_noop!(
+			ConcentratedDexModule::set_position_class(Origin::signed(ListingOrigin::get()), CLASS_ID + 2),
+			module_nft::Error::<Runtime>::ClassIdNotFound
+		);
+
+		assert_eq!(ConcentratedDexModule::position_class_id(), None);
+		assert_ok!(ConcentratedDexModule::set_position_class(
+			Origin::signed(ListingOrigin::get()),
+			CLASS_ID
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::PositionClassSet {
+			class_id: CLASS_ID,
+		}));
+		assert_eq!(ConcentratedDexModule::position_class_id(), Some(CLASS_ID));
+
+		assert_noop!(
+			ConcentratedDexModule::set_position_class(Origin::signed(ListingOrigin::get()), CLASS_ID),
+			Error::<Runtime>::PositionClassAlreadySet
+		);
+	});
+}
+
+#[test]
+fn create_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ConcentratedDexModule::create_pool(Origin::signed(ALICE), AUSD, DOT, Price::one(), FEE_RATE, TICK_SPACING),
+			BadOrigin
+		);
+		assert_noop!(
+			ConcentratedDexModule::create_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				AUSD,
+				Price::one(),
+				FEE_RATE,
+				TICK_SPACING
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			ConcentratedDexModule::create_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::one(),
+				(1000, 1000),
+				TICK_SPACING
+			),
+			Error::<Runtime>::InvalidFeeRate
+		);
+		assert_noop!(
+			ConcentratedDexModule::create_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::one(),
+				FEE_RATE,
+				0
+			),
+			Error::<Runtime>::InvalidTickSpacing
+		);
+		assert_noop!(
+			ConcentratedDexModule::create_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::zero(),
+				FEE_RATE,
+				TICK_SPACING
+			),
+			Error::<Runtime>::InvalidPrice
+		);
+
+		// the initial price is the price of DOT in AUSD, the pool records the price
+		// of AUSD in DOT.
+		assert_ok!(ConcentratedDexModule::create_pool(
+			Origin::signed(ListingOrigin::get()),
+			DOT,
+			AUSD,
+			Price::saturating_from_integer(2),
+			FEE_RATE,
+			TICK_SPACING
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::PoolCreated {
+			trading_pair: AUSDDOTPair::get(),
+			sqrt_price: 13_043_817_825_332_782_212,
+			tick: -6932,
+			fee_rate: FEE_RATE,
+			tick_spacing: TICK_SPACING,
+		}));
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()),
+			Some(PoolInfo {
+				sqrt_price: 13_043_817_825_332_782_212,
+				tick: -6932,
+				fee_rate: FEE_RATE,
+				tick_spacing: TICK_SPACING,
+				liquidity: 0,
+				fee_growth_global_0: U256::zero(),
+				fee_growth_global_1: U256::zero(),
+			})
+		);
+
+		assert_noop!(
+			ConcentratedDexModule::create_pool(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::one(),
+				FEE_RATE,
+				TICK_SPACING
+			),
+			Error::<Runtime>::PoolAlreadyExists
+		);
+	});
+}
+
+#[test]
+fn mint_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ConcentratedDexModule::create_pool(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			Price::one(),
+			FEE_RATE,
+			TICK_SPACING,
+		));
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				-1000,
+				1000,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::PositionClassNotSet
+		);
+		assert_ok!(ConcentratedDexModule::set_position_class(
+			Origin::signed(ListingOrigin::get()),
+			CLASS_ID
+		));
+
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(ALICE),
+				AUSD,
+				BTC,
+				-1000,
+				1000,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::PoolNotFound
+		);
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				1000,
+				-1000,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::InvalidTickRange
+		);
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				-1005,
+				1000,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::InvalidTickRange
+		);
+		assert_noop!(
+			ConcentratedDexModule::mint_position(Origin::signed(ALICE), AUSD, DOT, -1000, 1000, 0, 0, 0),
+			Error::<Runtime>::InvalidLiquidity
+		);
+		assert_noop!(
+			ConcentratedDexModule::mint_position(Origin::signed(ALICE), AUSD, DOT, -1000, 1000, 100, 100, 0),
+			Error::<Runtime>::BelowMinimumLiquidity
+		);
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				-1000,
+				1000,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				POSITION_A_LIQUIDITY + 1,
+			),
+			Error::<Runtime>::UnacceptableLiquidityIncrement
+		);
+
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			-1000,
+			1000,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			POSITION_A_LIQUIDITY,
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::PositionMinted {
+			owner: ALICE,
+			position_id: 0,
+			trading_pair: AUSDDOTPair::get(),
+			tick_lower: -1000,
+			tick_upper: 1000,
+			liquidity: POSITION_A_LIQUIDITY,
+			amount_0: 1_000_000_000_000,
+			amount_1: 1_000_000_000_000,
+		}));
+		assert_eq!(
+			ConcentratedDexModule::positions(0),
+			Some(Position {
+				trading_pair: AUSDDOTPair::get(),
+				tick_lower: -1000,
+				tick_upper: 1000,
+				liquidity: POSITION_A_LIQUIDITY,
+				fee_growth_inside_0_last: U256::zero(),
+				fee_growth_inside_1_last: U256::zero(),
+			})
+		);
+		assert_eq!(
+			<module_nft::Pallet<Runtime> as Inspect<_>>::owner(&CLASS_ID, &0),
+			Some(ALICE)
+		);
+		assert_eq!(Balances::free_balance(&ALICE), 100_000 - CREATE_TOKEN_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(&ALICE), CREATE_TOKEN_DEPOSIT);
+		assert_eq!(
+			Tokens::free_balance(AUSD, &ALICE),
+			1_000_000_000_000_000_000 - 1_000_000_000_000
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &ALICE),
+			1_000_000_000_000_000_000 - 1_000_000_000_000
+		);
+		assert_eq!(module_balance(AUSD), 1_000_000_000_000);
+		assert_eq!(module_balance(DOT), 1_000_000_000_000);
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), -1000),
+			TickInfo {
+				liquidity_gross: POSITION_A_LIQUIDITY,
+				liquidity_net: POSITION_A_LIQUIDITY as i128,
+				fee_growth_outside_0: U256::zero(),
+				fee_growth_outside_1: U256::zero(),
+			}
+		);
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), 1000),
+			TickInfo {
+				liquidity_gross: POSITION_A_LIQUIDITY,
+				liquidity_net: -(POSITION_A_LIQUIDITY as i128),
+				fee_growth_outside_0: U256::zero(),
+				fee_growth_outside_1: U256::zero(),
+			}
+		);
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().liquidity,
+			POSITION_A_LIQUIDITY
+		);
+
+		// the currencies can be passed in any order
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			-100,
+			100,
+			100_000_000_000u128,
+			100_000_000_000u128,
+			0,
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::PositionMinted {
+			owner: ALICE,
+			position_id: 1,
+			trading_pair: AUSDDOTPair::get(),
+			tick_lower: -100,
+			tick_upper: 100,
+			liquidity: POSITION_B_LIQUIDITY,
+			amount_0: 100_000_000_000,
+			amount_1: 100_000_000_000,
+		}));
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().liquidity,
+			POSITION_A_LIQUIDITY + POSITION_B_LIQUIDITY
+		);
+		assert_eq!(
+			ConcentratedDexModule::initialized_ticks(AUSDDOTPair::get()).into_inner(),
+			vec![-1000, -100, 100, 1000]
+		);
+
+		// a position out of the current price range doesn't change the pool liquidity
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			-1000,
+			-100,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			0,
+		));
+		assert_eq!(module_balance(AUSD), 1_100_000_000_000);
+		assert!(module_balance(DOT) > 1_100_000_000_000);
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().liquidity,
+			POSITION_A_LIQUIDITY + POSITION_B_LIQUIDITY
+		);
+
+		assert_noop!(
+			ConcentratedDexModule::mint_position(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				-200,
+				200,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::TooManyTicks
+		);
+	});
+}
+
+#[test]
+fn swap_with_exact_supply_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(Origin::signed(BOB), vec![AUSD], 10_000_000_000u128, 0),
+			Error::<Runtime>::InvalidTradingPathLength
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![AUSD, DOT, AUSD],
+				10_000_000_000u128,
+				0
+			),
+			Error::<Runtime>::InvalidTradingPath
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(Origin::signed(BOB), vec![AUSD, BTC], 10_000_000_000u128, 0),
+			Error::<Runtime>::PoolNotFound
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![AUSD, DOT],
+				10_000_000_000u128,
+				9_967_549_661u128
+			),
+			Error::<Runtime>::InsufficientTargetAmount
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![AUSD, DOT],
+				1_000_000_000_000_000u128,
+				0
+			),
+			Error::<Runtime>::InsufficientLiquidity
+		);
+
+		// swap within the range of both positions
+		assert_ok!(ConcentratedDexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			10_000_000_000u128,
+			9_967_549_660u128
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::Swap {
+			trader: BOB,
+			path: vec![AUSD, DOT],
+			liquidity_changes: vec![10_000_000_000, 9_967_549_660],
+		}));
+		let pool = ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap();
+		assert_eq!(pool.sqrt_price, 18_442_210_394_475_691_180);
+		assert_eq!(pool.tick, -5);
+		assert_eq!(pool.liquidity, POSITION_A_LIQUIDITY + POSITION_B_LIQUIDITY);
+		assert_eq!(
+			Tokens::free_balance(AUSD, &BOB),
+			1_000_000_000_000_000_000 - 10_000_000_000
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &BOB),
+			1_000_000_000_000_000_000 + 9_967_549_660
+		);
+		assert_eq!(module_balance(AUSD), 1_110_000_000_000);
+		assert_eq!(module_balance(DOT), 1_100_000_000_000 - 9_967_549_660);
+
+		// cross the lower tick of position 1
+		assert_ok!(ConcentratedDexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			200_000_000_000u128,
+			0
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::Swap {
+			trader: BOB,
+			path: vec![AUSD, DOT],
+			liquidity_changes: vec![200_000_000_000, 198_326_255_411],
+		}));
+		let pool = ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap();
+		assert_eq!(pool.sqrt_price, 18_349_321_400_768_027_138);
+		assert_eq!(pool.tick, -106);
+		assert_eq!(pool.liquidity, POSITION_A_LIQUIDITY);
+		assert_eq!(
+			pool.fee_growth_global_0,
+			U256::from_dec_str("5436327047158215620251280000576238").unwrap()
+		);
+		assert_eq!(pool.fee_growth_global_1, U256::zero());
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), -100).fee_growth_outside_0,
+			U256::from_dec_str("5132157387018852985782813044435870").unwrap()
+		);
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), 100).fee_growth_outside_0,
+			U256::zero()
+		);
+	});
+}
+
+#[test]
+fn swap_with_exact_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+		assert_ok!(ConcentratedDexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			210_000_000_000u128,
+			0
+		));
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().liquidity,
+			POSITION_A_LIQUIDITY
+		);
+
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_target(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				0,
+				1_000_000_000_000u128
+			),
+			Error::<Runtime>::ZeroSupplyAmount
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_target(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000u128,
+				99_516_505_013u128
+			),
+			Error::<Runtime>::ExcessiveSupplyAmount
+		);
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_target(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				10_000_000_000_000u128,
+				u128::MAX
+			),
+			Error::<Runtime>::InsufficientLiquidity
+		);
+
+		// cross the lower tick of position 1 back
+		let ausd_balance = Tokens::free_balance(AUSD, &BOB);
+		let dot_balance = Tokens::free_balance(DOT, &BOB);
+		assert_ok!(ConcentratedDexModule::swap_with_exact_target(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			100_000_000_000u128,
+			99_516_505_014u128
+		));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::Swap {
+			trader: BOB,
+			path: vec![DOT, AUSD],
+			liquidity_changes: vec![99_516_505_014, 100_000_000_000],
+		}));
+		let pool = ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap();
+		assert_eq!(pool.sqrt_price, 18_397_131_588_096_692_226);
+		assert_eq!(pool.tick, -54);
+		assert_eq!(pool.liquidity, POSITION_A_LIQUIDITY + POSITION_B_LIQUIDITY);
+		assert_eq!(
+			pool.fee_growth_global_1,
+			U256::from_dec_str("2653788249929110090813754383705736").unwrap()
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), ausd_balance + 100_000_000_000);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), dot_balance - 99_516_505_014);
+	});
+}
+
+#[test]
+fn swap_tick_crossings_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+		MaxTickCrossings::set(1);
+
+		assert_noop!(
+			ConcentratedDexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![AUSD, DOT],
+				1_000_000_000_000_000u128,
+				0
+			),
+			Error::<Runtime>::TooManyTickCrossings
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+				&[AUSD, DOT],
+				SwapLimit::ExactSupply(1_000_000_000_000_000, 0)
+			),
+			None
+		);
+
+		// the actual weight counts the crossed ticks
+		let post_info =
+			ConcentratedDexModule::swap_with_exact_supply(Origin::signed(BOB), vec![AUSD, DOT], 10_000_000_000u128, 0)
+				.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::swap_with_exact_supply(2, 0))
+		);
+		let post_info =
+			ConcentratedDexModule::swap_with_exact_supply(Origin::signed(BOB), vec![AUSD, DOT], 200_000_000_000u128, 0)
+				.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::swap_with_exact_supply(2, 1))
+		);
+		assert_eq!(ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().tick, -106);
+
+		let post_info = ConcentratedDexModule::swap_with_exact_target(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			100_000_000_000u128,
+			u128::MAX,
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::swap_with_exact_target(2, 1))
+		);
+	});
+}
+
+#[test]
+fn decrease_liquidity_and_collect_fees_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+		assert_ok!(ConcentratedDexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			10_000_000_000u128,
+			0
+		));
+		assert_ok!(ConcentratedDexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			200_000_000_000u128,
+			0
+		));
+		assert_ok!(ConcentratedDexModule::swap_with_exact_target(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			100_000_000_000u128,
+			u128::MAX
+		));
+
+		assert_noop!(
+			ConcentratedDexModule::collect_fees(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ConcentratedDexModule::collect_fees(Origin::signed(ALICE), 2),
+			Error::<Runtime>::PositionNotFound
+		);
+
+		let ausd_balance = Tokens::free_balance(AUSD, &ALICE);
+		let dot_balance = Tokens::free_balance(DOT, &ALICE);
+		assert_ok!(ConcentratedDexModule::collect_fees(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::FeesCollected {
+			owner: ALICE,
+			position_id: 0,
+			amount_0: 327_589_087,
+			amount_1: 159_915_336,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), ausd_balance + 327_589_087);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), dot_balance + 159_915_336);
+
+		// the fees are settled
+		System::reset_events();
+		assert_ok!(ConcentratedDexModule::collect_fees(Origin::signed(ALICE), 0));
+		assert!(System::events().is_empty());
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), ausd_balance + 327_589_087);
+
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(Origin::signed(BOB), 1, POSITION_B_LIQUIDITY, 0, 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(Origin::signed(ALICE), 1, 0, 0, 0),
+			Error::<Runtime>::InvalidLiquidity
+		);
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(Origin::signed(ALICE), 1, POSITION_B_LIQUIDITY + 1, 0, 0),
+			Error::<Runtime>::InvalidLiquidity
+		);
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(Origin::signed(ALICE), 1, POSITION_B_LIQUIDITY - 1, 0, 0),
+			Error::<Runtime>::BelowMinimumLiquidity
+		);
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(
+				Origin::signed(ALICE),
+				1,
+				POSITION_B_LIQUIDITY,
+				154_072_669_455,
+				0
+			),
+			Error::<Runtime>::UnacceptableLiquidityWithdrawn
+		);
+
+		// withdraw the liquidity and the fees of position 1
+		let ausd_balance = Tokens::free_balance(AUSD, &ALICE);
+		let dot_balance = Tokens::free_balance(DOT, &ALICE);
+		assert_ok!(ConcentratedDexModule::decrease_liquidity(
+			Origin::signed(ALICE),
+			1,
+			POSITION_B_LIQUIDITY,
+			154_072_669_454,
+			46_072_758_894
+		));
+		System::assert_has_event(Event::ConcentratedDexModule(crate::Event::LiquidityDecreased {
+			owner: ALICE,
+			position_id: 1,
+			liquidity: POSITION_B_LIQUIDITY,
+			amount_0: 154_072_669_454,
+			amount_1: 46_072_758_894,
+		}));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::FeesCollected {
+			owner: ALICE,
+			position_id: 1,
+			amount_0: 302_410_913,
+			amount_1: 138_634_179,
+		}));
+		assert_eq!(
+			Tokens::free_balance(AUSD, &ALICE),
+			ausd_balance + 154_072_669_454 + 302_410_913
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &ALICE),
+			dot_balance + 46_072_758_894 + 138_634_179
+		);
+		assert_eq!(ConcentratedDexModule::positions(1).unwrap().liquidity, 0);
+		assert_eq!(
+			ConcentratedDexModule::pools(AUSDDOTPair::get()).unwrap().liquidity,
+			POSITION_A_LIQUIDITY
+		);
+
+		// the ticks only used by position 1 are cleared
+		assert_eq!(
+			ConcentratedDexModule::initialized_ticks(AUSDDOTPair::get()).into_inner(),
+			vec![-1000, 1000]
+		);
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), -100),
+			Default::default()
+		);
+		assert_eq!(
+			ConcentratedDexModule::ticks(AUSDDOTPair::get(), 100),
+			Default::default()
+		);
+	});
+}
+
+#[test]
+fn burn_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+		assert_eq!(Balances::reserved_balance(&ALICE), 2 * CREATE_TOKEN_DEPOSIT);
+
+		assert_noop!(
+			ConcentratedDexModule::burn_position(Origin::signed(BOB), 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ConcentratedDexModule::burn_position(Origin::signed(ALICE), 1),
+			Error::<Runtime>::PositionNotEmpty
+		);
+		// position NFTs can't be burned by the NFT module
+		assert_noop!(
+			module_nft::Pallet::<Runtime>::burn(Origin::signed(ALICE), (CLASS_ID, 1)),
+			module_nft::Error::<Runtime>::NonBurnable
+		);
+
+		assert_ok!(ConcentratedDexModule::decrease_liquidity(
+			Origin::signed(ALICE),
+			1,
+			POSITION_B_LIQUIDITY,
+			0,
+			0
+		));
+		assert_ok!(ConcentratedDexModule::burn_position(Origin::signed(ALICE), 1));
+		System::assert_last_event(Event::ConcentratedDexModule(crate::Event::PositionBurned {
+			owner: ALICE,
+			position_id: 1,
+		}));
+		assert_eq!(ConcentratedDexModule::positions(1), None);
+		assert_eq!(<module_nft::Pallet<Runtime> as Inspect<_>>::owner(&CLASS_ID, &1), None);
+		assert_eq!(Balances::reserved_balance(&ALICE), CREATE_TOKEN_DEPOSIT);
+
+		assert_noop!(
+			ConcentratedDexModule::burn_position(Origin::signed(ALICE), 1),
+			Error::<Runtime>::PositionNotFound
+		);
+	});
+}
+
+#[test]
+fn position_transfer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_positions();
+
+		assert_ok!(module_nft::Pallet::<Runtime>::transfer(
+			Origin::signed(ALICE),
+			BOB,
+			(CLASS_ID, 1)
+		));
+		assert_noop!(
+			ConcentratedDexModule::decrease_liquidity(Origin::signed(ALICE), 1, POSITION_B_LIQUIDITY, 0, 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		let ausd_balance = Tokens::free_balance(AUSD, &BOB);
+		assert_ok!(ConcentratedDexModule::decrease_liquidity(
+			Origin::signed(BOB),
+			1,
+			POSITION_B_LIQUIDITY,
+			0,
+			0
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), ausd_balance + 99_999_999_999);
+	});
+}
+
+#[test]
+fn dex_manager_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_pool();
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_liquidity_pool(AUSD, DOT),
+			(0, 0)
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_liquidity_pool(AUSD, BTC),
+			(0, 0)
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_liquidity_token_address(AUSD, DOT),
+			None
+		);
+
+		assert_noop!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::add_liquidity(
+				&ALICE,
+				AUSD,
+				DOT,
+				1_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				true
+			),
+			Error::<Runtime>::NotSupported
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::add_liquidity(
+				&ALICE,
+				DOT,
+				AUSD,
+				1_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false
+			),
+			Ok((1_000_000_000_000, 1_000_000_000_000, 1_000_000_000_232))
+		);
+		assert_eq!(
+			ConcentratedDexModule::positions(0),
+			Some(Position {
+				trading_pair: AUSDDOTPair::get(),
+				tick_lower: -443_630,
+				tick_upper: 443_630,
+				liquidity: 1_000_000_000_232,
+				fee_growth_inside_0_last: U256::zero(),
+				fee_growth_inside_1_last: U256::zero(),
+			})
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_liquidity_pool(DOT, AUSD),
+			(1_000_000_000_232, 1_000_000_000_232)
+		);
+		assert_noop!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::remove_liquidity(
+				&ALICE,
+				AUSD,
+				DOT,
+				1_000_000_000_232,
+				0,
+				0,
+				false
+			),
+			Error::<Runtime>::NotSupported
+		);
+	});
+
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_pool();
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			-1000,
+			1000,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			0,
+		));
+
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_liquidity_pool(AUSD, DOT),
+			(POSITION_A_LIQUIDITY, POSITION_A_LIQUIDITY)
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+				&[AUSD, DOT],
+				SwapLimit::ExactSupply(10_000_000_000, 0)
+			),
+			Some((10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+				&[AUSD, DOT],
+				SwapLimit::ExactSupply(10_000_000_000, 9_965_154_754)
+			),
+			None
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+				&[DOT, AUSD],
+				SwapLimit::ExactTarget(u128::MAX, 10_000_000_000)
+			),
+			Some((10_034_984_153, 10_000_000_000))
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+				&[DOT, AUSD],
+				SwapLimit::ExactTarget(10_034_984_152, 10_000_000_000)
+			),
+			None
+		);
+		assert_eq!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::get_best_price_swap_path(
+				AUSD,
+				DOT,
+				SwapLimit::ExactSupply(10_000_000_000, 0),
+				vec![vec![BTC]]
+			),
+			Some((vec![AUSD, DOT], 10_000_000_000, 9_965_154_753))
+		);
+
+		assert_ok!(
+			<ConcentratedDexModule as DEXManager<_, _, _>>::swap_with_specific_path(
+				&BOB,
+				&[DOT, AUSD],
+				SwapLimit::ExactTarget(u128::MAX, 10_000_000_000)
+			),
+			(10_034_984_153, 10_000_000_000)
+		);
+	});
+}
+
+#[test]
+fn swap_trait_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		initialize_ausd_dot_pool();
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			-1000,
+			1000,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			0,
+		));
+
+		assert_eq!(
+			<ConcentratedDexModule as Swap<_, _, _>>::get_swap_amount(
+				AUSD,
+				DOT,
+				SwapLimit::ExactSupply(10_000_000_000, 0)
+			),
+			Some((10_000_000_000, 9_965_154_753))
+		);
+		assert_noop!(
+			<ConcentratedDexModule as Swap<_, _, _>>::swap_by_aggregated_path(
+				&BOB,
+				&[
+					AggregatedSwapPath::Dex(vec![AUSD, DOT]),
+					AggregatedSwapPath::Taiga(0, 0, 1)
+				],
+				SwapLimit::ExactSupply(10_000_000_000, 0)
+			),
+			Error::<Runtime>::NotSupported
+		);
+		assert_ok!(
+			<ConcentratedDexModule as Swap<_, _, _>>::swap_by_aggregated_path(
+				&BOB,
+				&[AggregatedSwapPath::Dex(vec![AUSD, DOT])],
+				SwapLimit::ExactSupply(10_000_000_000, 0)
+			),
+			(10_000_000_000, 9_965_154_753)
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &BOB),
+			1_000_000_000_000_000_000 + 9_965_154_753
+		);
+	});
+}
+
+#[test]
+fn dex_with_concentrated_pools_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		type DEX = DexWithConcentratedPools<Runtime, MockDEX>;
+
+		initialize_ausd_dot_pool();
+		assert_ok!(ConcentratedDexModule::mint_position(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			-1000,
+			1000,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			0,
+		));
+
+		// the liquidity is managed by the constant product pools
+		assert_eq!(DEX::get_liquidity_pool(AUSD, DOT), (1_000, 1_000));
+		assert_eq!(DEX::get_liquidity_token_address(AUSD, DOT), Some(H160::repeat_byte(1)));
+		assert_eq!(
+			DEX::add_liquidity(&ALICE, AUSD, DOT, 1_000, 2_000, 0, false),
+			Ok((1_000, 2_000, 1_000))
+		);
+		assert_eq!(ConcentratedDexModule::positions(1), None);
+
+		// only the concentrated liquidity pool can swap
+		assert_eq!(
+			DEX::get_swap_amount(&[AUSD, DOT], SwapLimit::ExactSupply(10_000_000_000, 0)),
+			Some((10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(
+			DEX::get_best_price_swap_path(AUSD, DOT, SwapLimit::ExactSupply(10_000_000_000, 0), vec![]),
+			Some((vec![AUSD, DOT], 10_000_000_000, 9_965_154_753))
+		);
+
+		// ties go to the constant product pools
+		MockDEXSwapAmount::set(Some((10_000_000_000, 9_965_154_753)));
+		assert_eq!(
+			DEX::get_swap_amount(&[AUSD, DOT], SwapLimit::ExactSupply(10_000_000_000, 0)),
+			Some((10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(
+			DEX::swap_with_specific_path(&BOB, &[AUSD, DOT], SwapLimit::ExactSupply(10_000_000_000, 0)),
+			Ok((10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
+
+		// the concentrated liquidity pool gives more target
+		MockDEXSwapAmount::set(Some((10_000_000_000, 9_965_154_752)));
+		assert_eq!(
+			DEX::get_best_price_swap_path(AUSD, DOT, SwapLimit::ExactSupply(10_000_000_000, 0), vec![]),
+			Some((vec![AUSD, DOT], 10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(
+			DEX::swap_with_specific_path(&BOB, &[AUSD, DOT], SwapLimit::ExactSupply(10_000_000_000, 0)),
+			Ok((10_000_000_000, 9_965_154_753))
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &BOB),
+			1_000_000_000_000_000_000 + 9_965_154_753
+		);
+
+		// the constant product pools take less supply
+		let ausd_balance = Tokens::free_balance(AUSD, &BOB);
+		let quote = <ConcentratedDexModule as DEXManager<_, _, _>>::get_swap_amount(
+			&[DOT, AUSD],
+			SwapLimit::ExactTarget(u128::MAX, 10_000_000_000),
+		)
+		.unwrap();
+		MockDEXSwapAmount::set(Some((quote.0 - 1, 10_000_000_000)));
+		assert_eq!(
+			DEX::get_swap_amount(&[DOT, AUSD], SwapLimit::ExactTarget(u128::MAX, 10_000_000_000)),
+			Some((quote.0 - 1, 10_000_000_000))
+		);
+		assert_eq!(
+			DEX::swap_with_specific_path(&BOB, &[DOT, AUSD], SwapLimit::ExactTarget(u128::MAX, 10_000_000_000)),
+			Ok((quote.0 - 1, 10_000_000_000))
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), ausd_balance);
+
+		// no concentrated liquidity pool for the path
+		assert_eq!(
+			DEX::get_swap_amount(&[AUSD, BTC], SwapLimit::ExactSupply(10_000_000_000, 0)),
+			Some((quote.0 - 1, 10_000_000_000))
+		);
+	});
+}
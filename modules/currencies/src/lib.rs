@@ -28,7 +28,8 @@ use frame_support::{
 	traits::{
 		tokens::{fungible, fungibles, DepositConsequence, WithdrawConsequence},
 		BalanceStatus as Status, Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance,
-		LockableCurrency as PalletLockableCurrency, ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
+		LockableCurrency as PalletLockableCurrency, NamedReservableCurrency,
+		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
 	},
 	transactional,
 };
@@ -39,11 +40,11 @@ use orml_traits::{
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDust,
 };
-use primitives::{evm::EvmAddress, CurrencyId};
+use primitives::{evm::EvmAddress, CurrencyId, ReserveIdentifier};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	traits::{Bounded, CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchError, DispatchResult,
 };
 use sp_std::{fmt::Debug, marker, result, vec::Vec};
 use support::{evm::limits::erc20, AddressMapping, EVMBridge, InvokeContext};
@@ -63,6 +64,8 @@ type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_s
 pub mod module {
 	use super::*;
 
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::Currencies;
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -109,6 +112,17 @@ pub mod module {
 
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, CurrencyId, BalanceOf<Self>>;
+
+		/// The deposit per byte of the allowance, reserved in the native currency on the owner.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The native currency to reserve the deposit of the allowances.
+		type DepositCurrency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = BalanceOf<Self>,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 	}
 
 	#[pallet::error]
@@ -125,6 +139,8 @@ pub mod module {
 		RealOriginNotFound,
 		/// Deposit result is not expected
 		DepositFailed,
+		/// The allowance of the spender is not enough
+		InsufficientAllowance,
 	}
 
 	#[pallet::event]
@@ -155,8 +171,38 @@ pub mod module {
 			who: T::AccountId,
 			amount: BalanceOf<T>,
 		},
+		/// The allowance of the spender is set.
+		Approval {
+			currency_id: CurrencyId,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
+	/// The amount of the currency the spender is allowed to transfer on behalf of the owner by
+	/// the ERC20 `transferFrom` and the deposit reserved on the owner.
+	///
+	/// Allowances: double_map (CurrencyId, Owner), Spender => Option<(Balance, Balance)>
+	#[pallet::storage]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(CurrencyId, T::AccountId),
+		Blake2_128Concat,
+		T::AccountId,
+		(BalanceOf<T>, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The nonces of the EIP-2612 permits of the owner.
+	///
+	/// PermitNonces: double_map CurrencyId, Owner => u64
+	#[pallet::storage]
+	#[pallet::getter(fn permit_nonces)]
+	pub type PermitNonces<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -277,6 +323,104 @@ pub mod module {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The amount of `currency_id` that `spender` is allowed to transfer on behalf of `owner`.
+	pub fn allowance(key: (CurrencyId, T::AccountId), spender: T::AccountId) -> BalanceOf<T> {
+		Allowances::<T>::get(key, spender).map_or_else(Zero::zero, |(amount, _)| amount)
+	}
+
+	/// Set the allowance of `spender` over the `currency_id` of `owner`. The allowance of ERC20
+	/// is managed by the contract itself. The deposit of a new allowance is reserved on `owner`
+	/// and refunded when the allowance is removed or used up.
+	#[transactional]
+	pub fn do_approve(
+		currency_id: CurrencyId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!currency_id.is_erc20_currency_id(), Error::<T>::Erc20InvalidOperation);
+
+		if amount.is_zero() {
+			Self::remove_allowance(currency_id, owner, spender);
+		} else {
+			let deposit = match Allowances::<T>::get((currency_id, owner), spender) {
+				Some((_, deposit)) => deposit,
+				None => {
+					let deposit = T::DataDepositPerByte::get()
+						.saturating_mul(((currency_id, owner, spender).encoded_size() as u32).into());
+					T::DepositCurrency::reserve_named(&RESERVE_ID, owner, deposit)?;
+					deposit
+				}
+			};
+			Allowances::<T>::insert((currency_id, owner), spender, (amount, deposit));
+		}
+
+		Self::deposit_event(Event::Approval {
+			currency_id,
+			owner: owner.clone(),
+			spender: spender.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Consume the permit nonce of `owner` and set the allowance of `spender`. The permit
+	/// signature must be verified by the caller with the current nonce.
+	#[transactional]
+	pub fn do_permit(
+		currency_id: CurrencyId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		PermitNonces::<T>::try_mutate(currency_id, owner, |nonce| -> DispatchResult {
+			*nonce = nonce.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		Self::do_approve(currency_id, owner, spender, amount)
+	}
+
+	/// Transfer `amount` of `currency_id` from `from` to `to` by `spender`, deducting the
+	/// allowance of `spender` unless it's the max value.
+	#[transactional]
+	pub fn do_transfer_from(
+		currency_id: CurrencyId,
+		spender: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!currency_id.is_erc20_currency_id(), Error::<T>::Erc20InvalidOperation);
+
+		if spender != from {
+			match Allowances::<T>::get((currency_id, from), spender) {
+				Some((allowance, deposit)) if allowance != BalanceOf::<T>::max_value() => {
+					let remaining = allowance
+						.checked_sub(&amount)
+						.ok_or(Error::<T>::InsufficientAllowance)?;
+					if remaining.is_zero() {
+						Self::remove_allowance(currency_id, from, spender);
+					} else {
+						Allowances::<T>::insert((currency_id, from), spender, (remaining, deposit));
+					}
+				}
+				Some(_) => {}
+				None => ensure!(amount.is_zero(), Error::<T>::InsufficientAllowance),
+			}
+		}
+
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)
+	}
+
+	/// Remove the allowance and refund its deposit to `owner`.
+	fn remove_allowance(currency_id: CurrencyId, owner: &T::AccountId, spender: &T::AccountId) {
+		if let Some((_, deposit)) = Allowances::<T>::take((currency_id, owner), spender) {
+			T::DepositCurrency::unreserve_named(&RESERVE_ID, owner, deposit);
+		}
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = CurrencyId;
 	type Balance = BalanceOf<T>;
@@ -166,6 +166,10 @@ impl module_evm::Config for Runtime {
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = ConstU64<100>;
+	type CodeUpgradeDelay = ConstU64<10>;
+	type MinBaseFeePerGas = ConstU128<0>;
+	type BlockGasTarget = ConstU64<10_000_000>;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = module_evm::runner::stack::Runner<Self>;
@@ -195,6 +199,8 @@ impl Config for Runtime {
 	type GasToWeight = GasToWeight;
 	type SweepOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type OnDust = crate::TransferDust<Runtime, DustAccount>;
+	type DataDepositPerByte = ConstU128<1>;
+	type DepositCurrency = PalletBalances;
 }
 
 pub type NativeCurrency = Currency<Runtime, GetNativeCurrencyId>;
@@ -31,7 +31,7 @@ use mock::{
 use sp_core::H160;
 use sp_runtime::{
 	traits::{BadOrigin, Bounded},
-	ModuleError,
+	ArithmeticError, ModuleError,
 };
 use support::mocks::MockAddressMapping;
 use support::EVM as EVMTrait;
@@ -2545,3 +2545,111 @@ fn transfer_erc20_will_charge_gas() {
 		);
 	});
 }
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), 0);
+			assert_noop!(
+				Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 10),
+				Error::<Runtime>::InsufficientAllowance
+			);
+
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), 30));
+			System::assert_last_event(Event::Currencies(crate::Event::Approval {
+				currency_id: X_TOKEN_ID,
+				owner: alice(),
+				spender: bob(),
+				amount: 30,
+			}));
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), 30);
+			assert_eq!(Currencies::allowance((NATIVE_CURRENCY_ID, alice()), bob()), 0);
+			// (currency_id, owner, spender) is 66 bytes
+			assert_eq!(Allowances::<Runtime>::get((X_TOKEN_ID, alice()), bob()), Some((30, 66)));
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &alice()), 66);
+			assert_eq!(PalletBalances::reserved_balance_named(&RESERVE_ID, &alice()), 66);
+
+			// updating the allowance doesn't reserve more
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), 30));
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &alice()), 66);
+
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 10));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 90);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 10);
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), 20);
+
+			assert_noop!(
+				Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 21),
+				Error::<Runtime>::InsufficientAllowance
+			);
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 20));
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), 0);
+			assert!(!Allowances::<Runtime>::contains_key((X_TOKEN_ID, alice()), bob()));
+			// the deposit is refunded when the allowance is used up
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &alice()), 0);
+
+			// the max allowance is not deducted
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), u128::max_value()));
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 10));
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), u128::max_value());
+
+			// the owner doesn't need allowance
+			assert_ok!(Currencies::do_transfer_from(X_TOKEN_ID, &alice(), &alice(), &eva(), 10));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 50);
+
+			// the deposit is refunded when the allowance is removed, the other reserves are kept
+			assert_ok!(PalletBalances::reserve(&alice(), 5));
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &alice()), 71);
+			assert_ok!(Currencies::do_approve(X_TOKEN_ID, &alice(), &bob(), 0));
+			assert!(!Allowances::<Runtime>::contains_key((X_TOKEN_ID, alice()), bob()));
+			assert_eq!(PalletBalances::reserved_balance_named(&RESERVE_ID, &alice()), 0);
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &alice()), 5);
+
+			// the deposit can't be reserved
+			assert_noop!(
+				Currencies::do_approve(X_TOKEN_ID, &eva(), &bob(), 10),
+				pallet_balances::Error::<Runtime>::InsufficientBalance
+			);
+
+			// ERC20 is not supported
+			assert_noop!(
+				Currencies::do_approve(CurrencyId::Erc20(erc20_address()), &alice(), &bob(), 10),
+				Error::<Runtime>::Erc20InvalidOperation
+			);
+		});
+}
+
+#[test]
+fn permit_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(Currencies::permit_nonces(X_TOKEN_ID, &alice()), 0);
+
+			assert_ok!(Currencies::do_permit(X_TOKEN_ID, &alice(), &bob(), 10));
+			assert_eq!(Currencies::allowance((X_TOKEN_ID, alice()), bob()), 10);
+			assert_eq!(Currencies::permit_nonces(X_TOKEN_ID, &alice()), 1);
+			assert_eq!(Currencies::permit_nonces(DOT, &alice()), 0);
+
+			// the nonce is not consumed if failed
+			assert_noop!(
+				Currencies::do_permit(CurrencyId::Erc20(erc20_address()), &alice(), &bob(), 10),
+				Error::<Runtime>::Erc20InvalidOperation
+			);
+			assert_eq!(
+				Currencies::permit_nonces(CurrencyId::Erc20(erc20_address()), &alice()),
+				0
+			);
+
+			// the nonce can't overflow
+			PermitNonces::<Runtime>::insert(X_TOKEN_ID, &alice(), u64::max_value());
+			assert_noop!(
+				Currencies::do_permit(X_TOKEN_ID, &alice(), &bob(), 10),
+				ArithmeticError::Overflow
+			);
+		});
+}
@@ -103,6 +103,7 @@ pub mod module {
 		/// item is the denominator, fee_rate = numerator / denominator,
 		/// use (u32, u32) over `Rate` type to minimize internal division
 		/// operation.
+		/// It's the default fee rate for trading pairs without their own fee rate.
 		#[pallet::constant]
 		type GetExchangeFee: Get<(u32, u32)>;
 
@@ -183,6 +184,10 @@ pub mod module {
 		NotAllowedRefund,
 		/// Cannot swap
 		CannotSwap,
+		/// The exchange fee is invalid
+		InvalidExchangeFee,
+		/// The protocol fee rate is invalid
+		InvalidProtocolFee,
 	}
 
 	#[pallet::event]
@@ -247,6 +252,21 @@ pub mod module {
 			accumulated_provision_0: Balance,
 			accumulated_provision_1: Balance,
 		},
+		/// The exchange fee of trading pair updated, `None` means `GetExchangeFee` is used.
+		ExchangeFeeUpdated {
+			trading_pair: TradingPair,
+			exchange_fee: Option<(u32, u32)>,
+		},
+		/// The protocol fee updated, `None` means protocol fee is off.
+		ProtocolFeeUpdated {
+			protocol_fee: Option<(Ratio, T::AccountId)>,
+		},
+		/// Dex share minted to the receiver as protocol fee.
+		ProtocolFeeMinted {
+			trading_pair: TradingPair,
+			receiver: T::AccountId,
+			share_amount: Balance,
+		},
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -264,6 +284,36 @@ pub mod module {
 	pub type TradingPairStatuses<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, TradingPairStatus<Balance, T::BlockNumber>, ValueQuery>;
 
+	/// Exchange fee rate for TradingPair, `GetExchangeFee` is used if not set.
+	///
+	/// TradingPairExchangeFees: map TradingPair => (u32, u32)
+	#[pallet::storage]
+	#[pallet::getter(fn trading_pair_exchange_fees)]
+	pub type TradingPairExchangeFees<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (u32, u32), OptionQuery>;
+
+	/// The fraction of exchange fee goes to protocol, and the account receives it.
+	/// Protocol fee is off if not set.
+	///
+	/// ProtocolFee: (Ratio, AccountId)
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee)]
+	pub type ProtocolFee<T: Config> = StorageValue<_, (Ratio, T::AccountId), OptionQuery>;
+
+	/// sqrt(pool_0 * pool_1) of TradingPair after the latest liquidity event, only
+	/// tracked when protocol fee is on.
+	///
+	/// RootKLast: map TradingPair => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn root_k_last)]
+	pub type RootKLast<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Balance, ValueQuery>;
+
+	/// Accumulated dex share minted as protocol fee for TradingPair.
+	///
+	/// AccruedProtocolFees: map TradingPair => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_protocol_fees)]
+	pub type AccruedProtocolFees<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Balance, ValueQuery>;
+
 	/// Provision of TradingPair by AccountId.
 	///
 	/// ProvisioningPool: double_map TradingPair, AccountId => (Balance,
@@ -843,6 +893,67 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Set the exchange fee rate of a trading pair.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `exchange_fee`: (numerator, denominator) of the fee rate, `None` to use
+		///   `GetExchangeFee`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_exchange_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_exchange_fee(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			exchange_fee: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			match exchange_fee {
+				Some((fee_numerator, fee_denominator)) => {
+					ensure!(
+						!fee_denominator.is_zero() && fee_numerator < fee_denominator,
+						Error::<T>::InvalidExchangeFee
+					);
+					TradingPairExchangeFees::<T>::insert(trading_pair, (fee_numerator, fee_denominator));
+				}
+				None => TradingPairExchangeFees::<T>::remove(trading_pair),
+			}
+
+			Self::deposit_event(Event::ExchangeFeeUpdated {
+				trading_pair,
+				exchange_fee,
+			});
+			Ok(())
+		}
+
+		/// Set the protocol fee. Protocol fee is collected by minting dex share to
+		/// `receiver` when liquidity is added or removed.
+		///
+		/// - `protocol_fee`: the fraction of exchange fee goes to protocol and the receiver,
+		///   `None` to turn off protocol fee.
+		#[pallet::weight((<T as Config>::WeightInfo::set_protocol_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_protocol_fee(origin: OriginFor<T>, protocol_fee: Option<(Ratio, T::AccountId)>) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+
+			match protocol_fee.clone() {
+				Some((fee_rate, receiver)) => {
+					ensure!(
+						!fee_rate.is_zero() && fee_rate <= Ratio::one(),
+						Error::<T>::InvalidProtocolFee
+					);
+					ProtocolFee::<T>::put((fee_rate, receiver));
+				}
+				None => ProtocolFee::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::ProtocolFeeUpdated { protocol_fee });
+			Ok(())
+		}
 	}
 }
 
@@ -1017,6 +1128,7 @@ impl<T: Config> Pallet<T> {
 			&trading_pair,
 			|(pool_0, pool_1)| -> sp_std::result::Result<(Balance, Balance, Balance), DispatchError> {
 				let dex_share_currency_id = trading_pair.dex_share_currency_id();
+				let protocol_fee_on = Self::mint_protocol_fee(&trading_pair, *pool_0, *pool_1)?;
 				let total_shares = T::Currency::total_issuance(dex_share_currency_id);
 				let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.first() {
 					(max_amount_a, max_amount_b)
@@ -1088,6 +1200,7 @@ impl<T: Config> Pallet<T> {
 
 				*pool_0 = pool_0.checked_add(pool_0_increment).ok_or(ArithmeticError::Overflow)?;
 				*pool_1 = pool_1.checked_add(pool_1_increment).ok_or(ArithmeticError::Overflow)?;
+				Self::update_root_k_last(&trading_pair, protocol_fee_on, *pool_0, *pool_1);
 
 				if stake_increment_share {
 					T::DEXIncentives::do_deposit_dex_share(who, dex_share_currency_id, share_increment)?;
@@ -1136,6 +1249,7 @@ impl<T: Config> Pallet<T> {
 				} else {
 					(min_withdrawn_b, min_withdrawn_a)
 				};
+				let protocol_fee_on = Self::mint_protocol_fee(&trading_pair, *pool_0, *pool_1)?;
 				let total_shares = T::Currency::total_issuance(dex_share_currency_id);
 				let proportion =
 					Ratio::checked_from_rational(remove_share, total_shares).ok_or(ArithmeticError::Overflow)?;
@@ -1157,6 +1271,7 @@ impl<T: Config> Pallet<T> {
 
 				*pool_0 = pool_0.checked_sub(pool_0_decrement).ok_or(ArithmeticError::Underflow)?;
 				*pool_1 = pool_1.checked_sub(pool_1_decrement).ok_or(ArithmeticError::Underflow)?;
+				Self::update_root_k_last(&trading_pair, protocol_fee_on, *pool_0, *pool_1);
 
 				Self::deposit_event(Event::RemoveLiquidity {
 					who: who.clone(),
@@ -1189,12 +1304,81 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn root_k(pool_0: Balance, pool_1: Balance) -> Balance {
+		// the square root of the product of two `Balance` always fits in `Balance`
+		TryInto::<Balance>::try_into(U256::from(pool_0).saturating_mul(U256::from(pool_1)).integer_sqrt())
+			.unwrap_or(Balance::MAX)
+	}
+
+	/// Mint dex share to the protocol fee receiver for the growth of sqrt(k) since the last
+	/// liquidity event, like the `feeTo` of Uniswap V2. Returns whether protocol fee is on.
+	fn mint_protocol_fee(
+		trading_pair: &TradingPair,
+		pool_0: Balance,
+		pool_1: Balance,
+	) -> sp_std::result::Result<bool, DispatchError> {
+		let (fee_rate, receiver) = match Self::protocol_fee() {
+			Some(protocol_fee) => protocol_fee,
+			None => return Ok(false),
+		};
+
+		let root_k_last = Self::root_k_last(trading_pair);
+		let root_k = Self::root_k(pool_0, pool_1);
+		if !root_k_last.is_zero() && root_k > root_k_last {
+			// share_amount = total_shares * fee_rate * (root_k - root_k_last) /
+			// 		((1 - fee_rate) * root_k + fee_rate * root_k_last)
+			let dex_share_currency_id = trading_pair.dex_share_currency_id();
+			let total_shares = T::Currency::total_issuance(dex_share_currency_id);
+			let numerator = fee_rate.saturating_mul_int(root_k.saturating_sub(root_k_last));
+			let denominator = Ratio::one()
+				.saturating_sub(fee_rate)
+				.saturating_mul_int(root_k)
+				.saturating_add(fee_rate.saturating_mul_int(root_k_last));
+			let share_amount = Ratio::checked_from_rational(numerator, denominator)
+				.and_then(|n| n.checked_mul_int(total_shares))
+				.ok_or(ArithmeticError::Overflow)?;
+
+			if !share_amount.is_zero() {
+				T::Currency::deposit(dex_share_currency_id, &receiver, share_amount)?;
+				AccruedProtocolFees::<T>::try_mutate(trading_pair, |accrued| -> DispatchResult {
+					*accrued = accrued.checked_add(share_amount).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+				Self::deposit_event(Event::ProtocolFeeMinted {
+					trading_pair: *trading_pair,
+					receiver,
+					share_amount,
+				});
+			}
+		}
+
+		Ok(true)
+	}
+
+	fn update_root_k_last(trading_pair: &TradingPair, protocol_fee_on: bool, pool_0: Balance, pool_1: Balance) {
+		if protocol_fee_on {
+			RootKLast::<T>::insert(trading_pair, Self::root_k(pool_0, pool_1));
+		} else if !Self::root_k_last(trading_pair).is_zero() {
+			RootKLast::<T>::remove(trading_pair);
+		}
+	}
+
+	/// Get the exchange fee rate of the trading pair.
+	pub fn get_exchange_fee(trading_pair: &TradingPair) -> (u32, u32) {
+		Self::trading_pair_exchange_fees(trading_pair).unwrap_or_else(T::GetExchangeFee::get)
+	}
+
 	/// Get how much target amount will be got for specific supply amount.
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		exchange_fee: (u32, u32),
+	) -> Balance {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
 			let supply_amount_with_fee: U256 =
 				U256::from(supply_amount).saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)));
 			let numerator: U256 = supply_amount_with_fee.saturating_mul(U256::from(target_pool));
@@ -1210,11 +1394,16 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Get how much supply amount will be paid for specific target amount.
-	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> Balance {
+	fn get_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		exchange_fee: (u32, u32),
+	) -> Balance {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
 			let numerator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(target_amount))
 				.saturating_mul(U256::from(fee_denominator));
@@ -1256,7 +1445,12 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let target_amount = Self::get_target_amount(
+				supply_pool,
+				target_pool,
+				target_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+			);
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			target_amounts[i + 1] = target_amount;
@@ -1292,7 +1486,12 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let supply_amount = Self::get_supply_amount(
+				supply_pool,
+				target_pool,
+				supply_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+			);
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			supply_amounts[i - 1] = supply_amount;
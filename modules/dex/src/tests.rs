@@ -23,8 +23,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	ACAJointSwap, AUSDBTCPair, AUSDDOTPair, AUSDJointSwap, DOTBTCPair, DexModule, Event, ExtBuilder, ListingOrigin,
-	Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, CAROL, DOT,
+	ACAJointSwap, AUSDBTCPair, AUSDDOTPair, AUSDJointSwap, DOTBTCPair, DexModule, Event, ExtBuilder, GetExchangeFee,
+	ListingOrigin, Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, CAROL, DOT,
 };
 use orml_traits::MultiReservableCurrency;
 use sp_core::H160;
@@ -958,26 +958,47 @@ fn get_liquidity_work() {
 #[test]
 fn get_target_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_target_amount(10000, 0, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_target_amount(0, 20000, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 0, GetExchangeFee::get()), 0);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 1, 1000000, GetExchangeFee::get()),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 10000, GetExchangeFee::get()),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get()),
+			1801
+		);
 	});
 }
 
 #[test]
 fn get_supply_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0, GetExchangeFee::get()), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 1, 1, GetExchangeFee::get()), 0);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 9949, GetExchangeFee::get()),
+			9999
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 9999, GetExchangeFee::get()),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 1801, GetExchangeFee::get()),
+			1000
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get()),
+			1801
+		);
 	});
 }
 
@@ -1039,7 +1060,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_supply_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				1_000_000_000_000_000_000_000
+				1_000_000_000_000_000_000_000,
+				GetExchangeFee::get()
 			),
 			3_140_495_867_768_595_041_323
 		);
@@ -1047,7 +1069,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_target_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				3_140_495_867_768_595_041_323
+				3_140_495_867_768_595_041_323,
+				GetExchangeFee::get()
 			),
 			1_000_000_000_000_000_000_000
 		);
@@ -1101,6 +1124,190 @@ fn get_supply_amounts_work() {
 		});
 }
 
+#[test]
+fn set_exchange_fee_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), GetExchangeFee::get());
+
+			assert_noop!(
+				DexModule::set_exchange_fee(Origin::signed(ALICE), AUSD, DOT, Some((0, 1))),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), AUSD, AUSD, Some((0, 1))),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			assert_noop!(
+				DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((1, 0))),
+				Error::<Runtime>::InvalidExchangeFee
+			);
+			assert_noop!(
+				DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((100, 100))),
+				Error::<Runtime>::InvalidExchangeFee
+			);
+
+			assert_ok!(DexModule::set_exchange_fee(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				Some((0, 1))
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::ExchangeFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				exchange_fee: Some((0, 1)),
+			}));
+			assert_eq!(DexModule::trading_pair_exchange_fees(AUSDDOTPair::get()), Some((0, 1)));
+			assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), (0, 1));
+			assert_eq!(DexModule::get_exchange_fee(&AUSDBTCPair::get()), GetExchangeFee::get());
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 25000])
+			);
+			assert_eq!(
+				DexModule::get_supply_amounts(&[DOT, AUSD], 24874),
+				Ok(vec![9900, 24874])
+			);
+
+			assert_ok!(DexModule::set_exchange_fee(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				None
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::ExchangeFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				exchange_fee: None,
+			}));
+			assert_eq!(DexModule::trading_pair_exchange_fees(AUSDDOTPair::get()), None);
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 24874])
+			);
+		});
+}
+
+#[test]
+fn protocol_fee_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let dex_share_currency_id = AUSDDOTPair::get().dex_share_currency_id();
+
+			assert_noop!(
+				DexModule::set_protocol_fee(
+					Origin::signed(ALICE),
+					Some((Ratio::saturating_from_rational(1, 6), CAROL))
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::set_protocol_fee(Origin::signed(ListingOrigin::get()), Some((Ratio::zero(), CAROL))),
+				Error::<Runtime>::InvalidProtocolFee
+			);
+			assert_noop!(
+				DexModule::set_protocol_fee(
+					Origin::signed(ListingOrigin::get()),
+					Some((Ratio::saturating_from_integer(2), CAROL))
+				),
+				Error::<Runtime>::InvalidProtocolFee
+			);
+
+			assert_ok!(DexModule::set_protocol_fee(
+				Origin::signed(ListingOrigin::get()),
+				Some((Ratio::saturating_from_rational(1, 6), CAROL))
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeUpdated {
+				protocol_fee: Some((Ratio::saturating_from_rational(1, 6), CAROL)),
+			}));
+			assert_eq!(
+				DexModule::protocol_fee(),
+				Some((Ratio::saturating_from_rational(1, 6), CAROL))
+			);
+
+			// no protocol fee for the first liquidity
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false,
+			));
+			assert_eq!(DexModule::root_k_last(AUSDDOTPair::get()), 2_236_067_977_499);
+			assert_eq!(DexModule::accrued_protocol_fees(AUSDDOTPair::get()), 0);
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &CAROL), 0);
+
+			// swap does not mint protocol fee
+			assert_ok!(DexModule::do_swap_with_exact_supply(
+				&BOB,
+				&[DOT, AUSD],
+				100_000_000_000,
+				0
+			));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(4_549_590_536_852, 1_100_000_000_000)
+			);
+			assert_eq!(DexModule::root_k_last(AUSDDOTPair::get()), 2_236_067_977_499);
+			assert_eq!(DexModule::accrued_protocol_fees(AUSDDOTPair::get()), 0);
+
+			// protocol fee is minted before adding liquidity
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				1_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false,
+			));
+			assert!(System::events().iter().any(|record| record.event
+				== Event::DexModule(crate::Event::ProtocolFeeMinted {
+					trading_pair: AUSDDOTPair::get(),
+					receiver: CAROL,
+					share_amount: 757_805_434,
+				})));
+			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
+				who: BOB,
+				currency_0: AUSD,
+				pool_0: 1_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 241_779_999_999,
+				share_increment: 2_198_166_565_625,
+			}));
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &CAROL), 757_805_434);
+			assert_eq!(DexModule::accrued_protocol_fees(AUSDDOTPair::get()), 757_805_434);
+			assert_eq!(DexModule::root_k_last(AUSDDOTPair::get()), 2_728_796_362_965);
+			assert_eq!(Tokens::total_issuance(dex_share_currency_id), 12_198_924_371_059);
+
+			// root k is not tracked after protocol fee is off
+			assert_ok!(DexModule::set_protocol_fee(Origin::signed(ListingOrigin::get()), None));
+			System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeUpdated {
+				protocol_fee: None,
+			}));
+			assert_ok!(DexModule::remove_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				1_000_000_000_000,
+				0,
+				0,
+				false,
+			));
+			assert_eq!(DexModule::root_k_last(AUSDDOTPair::get()), 0);
+			assert_eq!(DexModule::accrued_protocol_fees(AUSDDOTPair::get()), 757_805_434);
+			assert_eq!(Tokens::free_balance(dex_share_currency_id, &CAROL), 757_805_434);
+		});
+}
+
 #[test]
 fn _swap_work() {
 	ExtBuilder::default()
@@ -61,6 +61,8 @@ pub trait WeightInfo {
 	fn swap_with_exact_target(u: u32, ) -> Weight;
 	fn refund_provision() -> Weight;
 	fn abort_provisioning() -> Weight;
+	fn set_exchange_fee() -> Weight;
+	fn set_protocol_fee() -> Weight;
 }
 
 /// Weights for module_dex using the Acala node and recommended hardware.
@@ -149,6 +151,14 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	fn set_exchange_fee() -> Weight {
+		(8_141_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee() -> Weight {
+		(7_751_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -236,4 +246,12 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	fn set_exchange_fee() -> Weight {
+		(8_141_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee() -> Weight {
+		(7_751_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
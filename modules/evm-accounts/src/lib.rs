@@ -258,7 +258,8 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
-fn recover_signer(sig: &[u8; 65], msg_hash: &[u8; 32]) -> Option<H160> {
+/// Recover the Ethereum address which signed the `msg_hash`.
+pub fn recover_signer(sig: &[u8; 65], msg_hash: &[u8; 32]) -> Option<H160> {
 	secp256k1_ecdsa_recover(sig, msg_hash)
 		.map(|pubkey| H160::from(H256::from_slice(&keccak_256(&pubkey))))
 		.ok()
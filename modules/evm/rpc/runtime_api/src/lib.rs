@@ -19,19 +19,24 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use primitives::evm::{AccessListItem, BlockLimits, CallInfo, CreateInfo, EstimateResourcesRequest};
+use primitives::evm::{
+	tracing::{TraceOutcome, TracerConfig},
+	AccessListInfo, AccessListItem, BlockLimits, BundledCall, CallInfo, CreateInfo, EstimateResourcesRequest,
+	LegacyBlockLimits, StateOverride, StorageDeposit,
+};
 use sp_core::H160;
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	#[api_version(2)]
+	#[api_version(7)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
+		#[changed_in(3)]
 		fn call(
 			from: H160,
 			to: H160,
@@ -43,6 +48,19 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError>;
 
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
+			estimate: bool,
+		) -> Result<CallInfo, sp_runtime::DispatchError>;
+
+		#[changed_in(3)]
 		fn create(
 			from: H160,
 			data: Vec<u8>,
@@ -53,8 +71,77 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
+			estimate: bool,
+		) -> Result<CreateInfo, sp_runtime::DispatchError>;
+
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
 
+		#[changed_in(7)]
+		fn block_limits() -> LegacyBlockLimits;
+
 		fn block_limits() -> BlockLimits;
+
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError>;
+
+		fn storage_deposits(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
+		) -> Result<Vec<StorageDeposit>, sp_runtime::DispatchError>;
+
+		fn call_bundle(calls: Vec<BundledCall<Balance>>) -> Vec<Result<CallInfo, sp_runtime::DispatchError>>;
+	}
+
+	pub trait EVMTraceApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		fn trace_create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		/// Trace an extrinsic. Must be called on top of the state with the preceding extrinsics
+		/// of the block applied.
+		fn trace_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
 	}
 }
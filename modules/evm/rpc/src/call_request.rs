@@ -16,10 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use primitives::evm::AccessListItem;
+use primitives::evm::{AccessListItem, ExitReason, Log, StorageDeposit};
 use serde::{Deserialize, Serialize};
-use sp_core::{Bytes, H160, U256};
+use sp_core::{Bytes, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
+use std::collections::BTreeMap;
 
 /// Call request
 #[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
@@ -42,6 +43,26 @@ pub struct CallRequest {
 	pub access_list: Option<Vec<AccessListItem>>,
 }
 
+/// Account state override
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<NumberOrHex>,
+	/// Nonce
+	pub nonce: Option<NumberOrHex>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Replace the whole account storage
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Replace the given storage slots
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// State override, applied before executing the call
+pub type StateOverride = BTreeMap<H160, AccountOverride>;
+
 /// EstimateResources response
 #[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -52,4 +73,36 @@ pub struct EstimateResourcesResponse {
 	pub storage: i32,
 	/// Adjusted weight fee
 	pub weight_fee: U256,
+	/// Storage charged and refunded per contract
+	pub storage_deposits: Vec<StorageDeposit>,
+}
+
+/// CreateAccessList response
+#[derive(Debug, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessListResponse {
+	/// Accessed addresses and storage keys
+	pub access_list: Vec<AccessListItem>,
+	/// Used gas with the access list applied
+	pub gas_used: U256,
+	/// Gas saved by applying the access list
+	pub gas_saved: U256,
+}
+
+/// CallBundle response of a call
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResponse {
+	/// Output data, or the address of the created contract
+	pub output: Bytes,
+	/// Exit reason, none if the call is not executed
+	pub exit_reason: Option<ExitReason>,
+	/// Used gas
+	pub used_gas: U256,
+	/// Used storage
+	pub used_storage: i32,
+	/// Logs
+	pub logs: Vec<Log>,
+	/// Error message if the call failed
+	pub error: Option<String>,
 }
@@ -26,45 +26,96 @@ use jsonrpsee::{
 };
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
 use rustc_hex::ToHex;
+use sc_client_api::BlockBackend;
 use sc_rpc_api::DenyUnsafe;
-use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, Decode, H160, U256};
+use sp_core::{Bytes, Decode, H160, H256, U256};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
+	traits::{self, Block as BlockT, Hash as HashT, HashFor, Header as HeaderT, MaybeDisplay, MaybeFromStr},
 	SaturatedConversion,
 };
 use std::{marker::PhantomData, sync::Arc};
 
-use call_request::{CallRequest, EstimateResourcesResponse};
+use call_request::{
+	CallBundleResponse, CallRequest, CreateAccessListResponse, EstimateResourcesResponse, StateOverride,
+};
 pub use module_evm::{ExitError, ExitReason};
-pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
-use primitives::evm::{BlockLimits, EstimateResourcesRequest};
+pub use module_evm_rpc_runtime_api::{EVMRuntimeRPCApi, EVMTraceApi};
+use primitives::evm::{
+	tracing::{TraceOutcome, TracerConfig},
+	AccessListItem, AccountOverride, BlockLimits, BundledCall, CallInfo, CreateInfo, EstimateResourcesRequest,
+};
 
 mod call_request;
 
+/// The max number of calls in a bundle.
+const MAX_BUNDLE_SIZE: usize = 64;
+
 /// EVM rpc interface.
 #[rpc(client, server)]
 pub trait EVMApi<BlockHash> {
-	/// Call contract, returning the output data.
+	/// Call contract, returning the output data. The `state_override` is applied to the state
+	/// before the call.
 	#[method(name = "evm_call")]
-	fn call(&self, call_request: CallRequest, at: Option<BlockHash>) -> RpcResult<Bytes>;
+	fn call(
+		&self,
+		call_request: CallRequest,
+		at: Option<BlockHash>,
+		state_override: Option<StateOverride>,
+	) -> RpcResult<Bytes>;
 
-	/// Estimate resources needed for execution of given contract.
+	/// Estimate resources needed for execution of given contract. The `state_override` is
+	/// applied to the state before the estimation.
 	#[method(name = "evm_estimateResources")]
 	fn estimate_resources(
 		&self,
 		from: H160,
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
+		state_override: Option<StateOverride>,
 	) -> RpcResult<EstimateResourcesResponse>;
 
-	/// Get max gas and storage limits per transaction
+	/// Get max gas and storage limits per transaction, and the base fee per gas of the block
 	#[method(name = "evm_blockLimits")]
 	fn block_limits(&self, at: Option<BlockHash>) -> RpcResult<BlockLimits>;
+
+	/// Create the access list of the addresses and storage keys accessed by the call.
+	#[method(name = "evm_createAccessList")]
+	fn create_access_list(
+		&self,
+		call_request: CallRequest,
+		at: Option<BlockHash>,
+	) -> RpcResult<CreateAccessListResponse>;
+
+	/// Execute the calls sequentially, each call is executed on top of the state changed by the
+	/// previous ones. Returns the result of each call.
+	#[method(name = "evm_callBundle")]
+	fn call_bundle(&self, call_requests: Vec<CallRequest>, at: Option<BlockHash>)
+		-> RpcResult<Vec<CallBundleResponse>>;
+
+	/// Execute the call and return the trace produced by `tracer`.
+	#[method(name = "evm_traceCall")]
+	fn trace_call(
+		&self,
+		call_request: CallRequest,
+		tracer: TracerConfig,
+		at: Option<BlockHash>,
+	) -> RpcResult<TraceOutcome>;
+
+	/// Replay the extrinsic with hash `transaction_hash` included in block `block_hash` and
+	/// return the trace produced by `tracer`.
+	#[method(name = "evm_traceTransaction")]
+	fn trace_transaction(
+		&self,
+		block_hash: BlockHash,
+		transaction_hash: H256,
+		tracer: TracerConfig,
+	) -> RpcResult<TraceOutcome>;
 }
 
 fn internal_err<T: ToString>(message: T) -> JsonRpseeError {
@@ -140,15 +191,15 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 
 pub struct EVM<B, C, Balance> {
 	client: Arc<C>,
-	_deny_unsafe: DenyUnsafe,
+	deny_unsafe: DenyUnsafe,
 	_marker: PhantomData<(B, Balance)>,
 }
 
 impl<B, C, Balance> EVM<B, C, Balance> {
-	pub fn new(client: Arc<C>, _deny_unsafe: DenyUnsafe) -> Self {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
 		Self {
 			client,
-			_deny_unsafe,
+			deny_unsafe,
 			_marker: Default::default(),
 		}
 	}
@@ -158,18 +209,159 @@ fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
+fn to_runtime_state_override(state_override: StateOverride) -> RpcResult<primitives::evm::StateOverride> {
+	state_override
+		.into_iter()
+		.map(|(address, account)| {
+			let nonce = account
+				.nonce
+				.map(|nonce| {
+					nonce
+						.into_u256()
+						.try_into()
+						.map_err(|_| invalid_params(format!("Invalid nonce override: {:?}", nonce)))
+				})
+				.transpose()?;
+			Ok((
+				address,
+				AccountOverride {
+					balance: account.balance.map(NumberOrHex::into_u256),
+					nonce,
+					code: account.code.map(|code| code.0),
+					state: account.state.map(|state| state.into_iter().collect()),
+					state_diff: account.state_diff.map(|state_diff| state_diff.into_iter().collect()),
+				},
+			))
+		})
+		.collect()
+}
+
+impl<B, C, Balance> EVM<B, C, Balance>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn runtime_api_version(&self, block_id: &BlockId<B>) -> RpcResult<u32> {
+		self.client
+			.runtime_api()
+			.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(block_id)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.ok_or_else(|| {
+				internal_err(format!(
+					"Could not find `EVMRuntimeRPCApi` api for block `{:?}`.",
+					block_id
+				))
+			})
+	}
+
+	#[allow(clippy::too_many_arguments, deprecated)]
+	fn call_at(
+		&self,
+		block_id: &BlockId<B>,
+		from: H160,
+		to: H160,
+		data: Vec<u8>,
+		value: Balance,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Option<Vec<AccessListItem>>,
+		state_override: Option<primitives::evm::StateOverride>,
+	) -> RpcResult<CallInfo> {
+		let api = self.client.runtime_api();
+
+		let result = if self.runtime_api_version(block_id)? >= 3 {
+			api.call(
+				block_id,
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				state_override,
+				true,
+			)
+		} else {
+			if state_override.is_some() {
+				return Err(invalid_params("State override is not supported by the runtime"));
+			}
+			api.call_before_version_3(
+				block_id,
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				true,
+			)
+		};
+
+		result
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+
+	#[allow(clippy::too_many_arguments, deprecated)]
+	fn create_at(
+		&self,
+		block_id: &BlockId<B>,
+		from: H160,
+		data: Vec<u8>,
+		value: Balance,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Option<Vec<AccessListItem>>,
+		state_override: Option<primitives::evm::StateOverride>,
+	) -> RpcResult<CreateInfo> {
+		let api = self.client.runtime_api();
+
+		let result = if self.runtime_api_version(block_id)? >= 3 {
+			api.create(
+				block_id,
+				from,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				state_override,
+				true,
+			)
+		} else {
+			if state_override.is_some() {
+				return Err(invalid_params("State override is not supported by the runtime"));
+			}
+			api.create_before_version_3(block_id, from, data, value, gas_limit, storage_limit, access_list, true)
+		};
+
+		result
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+}
+
 #[async_trait]
 impl<B, C, Balance> EVMApiServer<<B as BlockT>::Hash> for EVM<B, C, Balance>
 where
 	B: BlockT,
-	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: EVMTraceApi<B, Balance>,
+	C::Api: BlockBuilder<B>,
 	C::Api: TransactionPaymentApi<B, Balance>,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
-	fn call(&self, request: CallRequest, at: Option<<B as BlockT>::Hash>) -> RpcResult<Bytes> {
-		let api = self.client.runtime_api();
-
+	fn call(
+		&self,
+		request: CallRequest,
+		at: Option<<B as BlockT>::Hash>,
+		state_override: Option<StateOverride>,
+	) -> RpcResult<Bytes> {
 		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
 		let block_id = BlockId::Hash(hash);
@@ -228,22 +420,21 @@ where
 		let balance_value =
 			balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
 
+		let state_override = state_override.map(to_runtime_state_override).transpose()?;
+
 		match to {
 			Some(to) => {
-				let info = api
-					.call(
-						&block_id,
-						from.unwrap_or_default(),
-						to,
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						access_list,
-						true,
-					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				let info = self.call_at(
+					&block_id,
+					from.unwrap_or_default(),
+					to,
+					data,
+					balance_value,
+					gas_limit,
+					storage_limit,
+					access_list,
+					state_override,
+				)?;
 
 				log::debug!(
 					target: "evm",
@@ -255,19 +446,16 @@ where
 				Ok(Bytes(info.value))
 			}
 			None => {
-				let info = api
-					.create(
-						&block_id,
-						from.unwrap_or_default(),
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						access_list,
-						true,
-					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				let info = self.create_at(
+					&block_id,
+					from.unwrap_or_default(),
+					data,
+					balance_value,
+					gas_limit,
+					storage_limit,
+					access_list,
+					state_override,
+				)?;
 
 				log::debug!(
 					target: "evm",
@@ -286,6 +474,7 @@ where
 		from: H160,
 		unsigned_extrinsic: Bytes,
 		at: Option<<B as BlockT>::Hash>,
+		state_override: Option<StateOverride>,
 	) -> RpcResult<EstimateResourcesResponse> {
 		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
@@ -305,6 +494,8 @@ where
 
 		let block_limits = self.block_limits(at)?;
 
+		let state_override = state_override.map(to_runtime_state_override).transpose()?;
+
 		let request: EstimateResourcesRequest = self
 			.client
 			.runtime_api()
@@ -348,6 +539,8 @@ where
 			used_storage: i32,
 		}
 
+		let state_override = &state_override;
+
 		// Create a helper to check if a gas allowance results in an executable transaction
 		let executable = move |request: CallRequest, gas: u64| -> RpcResult<ExecutableResult> {
 			let CallRequest {
@@ -379,41 +572,31 @@ where
 
 			let (exit_reason, data, used_gas, used_storage) = match to {
 				Some(to) => {
-					let info = self
-						.client
-						.runtime_api()
-						.call(
-							&block_id,
-							from.unwrap_or_default(),
-							to,
-							data,
-							balance_value,
-							gas_limit,
-							storage_limit,
-							access_list,
-							true,
-						)
-						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+					let info = self.call_at(
+						&block_id,
+						from.unwrap_or_default(),
+						to,
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						state_override.clone(),
+					)?;
 
 					(info.exit_reason, info.value, info.used_gas.as_u64(), info.used_storage)
 				}
 				None => {
-					let info = self
-						.client
-						.runtime_api()
-						.create(
-							&block_id,
-							from.unwrap_or_default(),
-							data,
-							balance_value,
-							gas_limit,
-							storage_limit,
-							access_list,
-							true,
-						)
-						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+					let info = self.create_at(
+						&block_id,
+						from.unwrap_or_default(),
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						access_list,
+						state_override.clone(),
+					)?;
 
 					(info.exit_reason, Vec::new(), info.used_gas.as_u64(), info.used_storage)
 				}
@@ -497,6 +680,42 @@ where
 			}
 		}
 
+		let storage_deposits = if self.runtime_api_version(&block_id)? >= 6 {
+			let CallRequest {
+				from,
+				to,
+				storage_limit,
+				value,
+				data,
+				access_list,
+				..
+			} = request;
+
+			let balance_value = value
+				.map_or(Ok(Default::default()), |value| {
+					to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+				})
+				.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
+
+			self.client
+				.runtime_api()
+				.storage_deposits(
+					&block_id,
+					from.unwrap_or_default(),
+					to,
+					data.map(|d| d.0).unwrap_or_default(),
+					balance_value,
+					highest,
+					storage_limit.expect("Cannot be none, value set when request is constructed above; qed"),
+					access_list,
+					state_override.clone(),
+				)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+				.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?
+		} else {
+			Vec::new()
+		};
+
 		let uxt: <B as traits::Block>::Extrinsic = Decode::decode(&mut &*unsigned_extrinsic)
 			.map_err(|e| internal_err(format!("execution error: Unable to dry run extrinsic {:?}", e)))?;
 
@@ -514,40 +733,385 @@ where
 			gas: highest,
 			storage: used_storage,
 			weight_fee: adjusted_weight_fee.into(),
+			storage_deposits,
 		})
 	}
 
+	#[allow(deprecated)]
 	fn block_limits(&self, at: Option<<B as BlockT>::Hash>) -> RpcResult<BlockLimits> {
 		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
 		let block_id = BlockId::Hash(hash);
 
-		let version = self
-			.client
-			.runtime_api()
-			.api_version::<dyn EVMRuntimeRPCApi<B, Balance>>(&block_id)
-			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-			.ok_or_else(|| {
-				internal_err(format!(
-					"Could not find `EVMRuntimeRPCApi` api for block `{:?}`.",
-					&block_id
-				))
-			})?;
+		let version = self.runtime_api_version(&block_id)?;
 
-		let block_limits = if version > 1 {
+		let block_limits = if version >= 7 {
 			self.client
 				.runtime_api()
 				.block_limits(&block_id)
 				.map_err(|e| internal_err(format!("runtime error: Unable to query block limits {:?}", e)))?
+		} else if version > 1 {
+			let block_limits = self
+				.client
+				.runtime_api()
+				.block_limits_before_version_7(&block_id)
+				.map_err(|e| internal_err(format!("runtime error: Unable to query block limits {:?}", e)))?;
+			BlockLimits {
+				max_gas_limit: block_limits.max_gas_limit,
+				max_storage_limit: block_limits.max_storage_limit,
+				base_fee_per_gas: 0,
+			}
 		} else {
 			BlockLimits {
 				max_gas_limit: 20_000_000,    // 20M
 				max_storage_limit: 4_194_304, // 4Mb
+				base_fee_per_gas: 0,
 			}
 		};
 
 		Ok(block_limits)
 	}
+
+	fn create_access_list(
+		&self,
+		request: CallRequest,
+		at: Option<<B as BlockT>::Hash>,
+	) -> RpcResult<CreateAccessListResponse> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let block_id = BlockId::Hash(hash);
+
+		if self.runtime_api_version(&block_id)? < 4 {
+			return Err(internal_err(format!(
+				"`evm_createAccessList` is not supported for block `{:?}`.",
+				&block_id
+			)));
+		}
+
+		log::debug!(target: "evm", "rpc create access list, request: {:?}", request);
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			access_list,
+		} = request;
+
+		let block_limits = self.block_limits(at)?;
+
+		let gas_limit = gas_limit.unwrap_or(block_limits.max_gas_limit);
+		if gas_limit > block_limits.max_gas_limit {
+			return Err(invalid_params(format!(
+				"GasLimit exceeds allowance: {}",
+				block_limits.max_gas_limit
+			)));
+		}
+		let storage_limit = storage_limit.unwrap_or(block_limits.max_storage_limit);
+		if storage_limit > block_limits.max_storage_limit {
+			return Err(invalid_params(format!(
+				"StorageLimit exceeds allowance: {}",
+				block_limits.max_storage_limit
+			)));
+		}
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value =
+			balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
+
+		let info = self
+			.client
+			.runtime_api()
+			.create_access_list(
+				&block_id,
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		log::debug!(
+			target: "evm",
+			"rpc create access list, info.exit_reason: {:?}, info.access_list: {:?}",
+			info.exit_reason, info.access_list,
+		);
+		error_on_execution_failure(&info.exit_reason, &[])?;
+
+		Ok(CreateAccessListResponse {
+			access_list: info.access_list,
+			gas_used: info.used_gas,
+			gas_saved: info.gas_saved,
+		})
+	}
+
+	fn call_bundle(
+		&self,
+		requests: Vec<CallRequest>,
+		at: Option<<B as BlockT>::Hash>,
+	) -> RpcResult<Vec<CallBundleResponse>> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let block_id = BlockId::Hash(hash);
+
+		if self.runtime_api_version(&block_id)? < 5 {
+			return Err(internal_err(format!(
+				"`evm_callBundle` is not supported for block `{:?}`.",
+				&block_id
+			)));
+		}
+
+		if requests.len() > MAX_BUNDLE_SIZE {
+			return Err(invalid_params(format!(
+				"Bundle size exceeds allowance: {}",
+				MAX_BUNDLE_SIZE
+			)));
+		}
+
+		log::debug!(target: "evm", "rpc call bundle, requests: {:?}", requests);
+
+		let block_limits = self.block_limits(at)?;
+
+		let calls = requests
+			.into_iter()
+			.map(|request| {
+				let CallRequest {
+					from,
+					to,
+					gas_limit,
+					storage_limit,
+					value,
+					data,
+					access_list,
+				} = request;
+
+				let gas_limit = gas_limit.unwrap_or(block_limits.max_gas_limit);
+				if gas_limit > block_limits.max_gas_limit {
+					return Err(invalid_params(format!(
+						"GasLimit exceeds allowance: {}",
+						block_limits.max_gas_limit
+					)));
+				}
+				let storage_limit = storage_limit.unwrap_or(block_limits.max_storage_limit);
+				if storage_limit > block_limits.max_storage_limit {
+					return Err(invalid_params(format!(
+						"StorageLimit exceeds allowance: {}",
+						block_limits.max_storage_limit
+					)));
+				}
+
+				let balance_value = if let Some(value) = value {
+					to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+				} else {
+					Ok(Default::default())
+				};
+
+				let balance_value =
+					balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
+
+				Ok(BundledCall {
+					from: from.unwrap_or_default(),
+					to,
+					data: data.map(|d| d.0).unwrap_or_default(),
+					value: balance_value,
+					gas_limit,
+					storage_limit,
+					access_list,
+				})
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		let results = self
+			.client
+			.runtime_api()
+			.call_bundle(&block_id, calls)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(results
+			.into_iter()
+			.map(|result| match result {
+				Ok(info) => CallBundleResponse {
+					error: error_on_execution_failure(&info.exit_reason, &info.value)
+						.err()
+						.map(|err| err.to_string()),
+					output: Bytes(info.value),
+					exit_reason: Some(info.exit_reason),
+					used_gas: info.used_gas,
+					used_storage: info.used_storage,
+					logs: info.logs,
+				},
+				Err(err) => CallBundleResponse {
+					output: Bytes(vec![]),
+					exit_reason: None,
+					used_gas: U256::zero(),
+					used_storage: 0,
+					logs: vec![],
+					error: Some(format!("execution fatal: {:?}", err)),
+				},
+			})
+			.collect())
+	}
+
+	fn trace_call(
+		&self,
+		request: CallRequest,
+		tracer: TracerConfig,
+		at: Option<<B as BlockT>::Hash>,
+	) -> RpcResult<TraceOutcome> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let block_id = BlockId::Hash(hash);
+
+		if !api.has_api::<dyn EVMTraceApi<B, Balance>>(&block_id).unwrap_or(false) {
+			return Err(internal_err(format!(
+				"Could not find `EVMTraceApi` api for block `{:?}`.",
+				&block_id
+			)));
+		}
+
+		log::debug!(target: "evm", "rpc trace call, request: {:?}", request);
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			access_list,
+		} = request;
+
+		let block_limits = self.block_limits(at)?;
+
+		let gas_limit = gas_limit.unwrap_or(block_limits.max_gas_limit);
+		if gas_limit > block_limits.max_gas_limit {
+			return Err(invalid_params(format!(
+				"GasLimit exceeds allowance: {}",
+				block_limits.max_gas_limit
+			)));
+		}
+		let storage_limit = storage_limit.unwrap_or(block_limits.max_storage_limit);
+		if storage_limit > block_limits.max_storage_limit {
+			return Err(invalid_params(format!(
+				"StorageLimit exceeds allowance: {}",
+				block_limits.max_storage_limit
+			)));
+		}
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value =
+			balance_value.map_err(|_| invalid_params(format!("Invalid parameter value: {:?}", value)))?;
+
+		let result = match to {
+			Some(to) => api.trace_call(
+				&block_id,
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				tracer,
+			),
+			None => api.trace_create(
+				&block_id,
+				from.unwrap_or_default(),
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				tracer,
+			),
+		};
+
+		result
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+
+	fn trace_transaction(
+		&self,
+		block_hash: <B as BlockT>::Hash,
+		transaction_hash: H256,
+		tracer: TracerConfig,
+	) -> RpcResult<TraceOutcome> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let block_id = BlockId::Hash(block_hash);
+
+		let header = self
+			.client
+			.header(block_id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| invalid_params(format!("Unknown block `{:?}`", block_hash)))?;
+		let extrinsics = self
+			.client
+			.block_body(&block_id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| invalid_params(format!("Unknown block body `{:?}`", block_hash)))?;
+
+		let index = extrinsics
+			.iter()
+			.position(|xt| HashFor::<B>::hash_of(xt).as_ref() == transaction_hash.as_bytes())
+			.ok_or_else(|| {
+				invalid_params(format!(
+					"Transaction `{:?}` not found in block `{:?}`",
+					transaction_hash, block_hash
+				))
+			})?;
+
+		// the trace is executed on top of the parent state
+		let parent_id = BlockId::Hash(*header.parent_hash());
+
+		let api = self.client.runtime_api();
+
+		if !api.has_api::<dyn EVMTraceApi<B, Balance>>(&parent_id).unwrap_or(false) {
+			return Err(internal_err(format!(
+				"Could not find `EVMTraceApi` api for block `{:?}`.",
+				&parent_id
+			)));
+		}
+
+		api.initialize_block(&parent_id, &header)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		let mut extrinsics = extrinsics.into_iter();
+		for xt in extrinsics.by_ref().take(index) {
+			// failed extrinsics have been included as well, only runtime errors matter
+			let _ = api
+				.apply_extrinsic(&parent_id, xt)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		}
+		let extrinsic = extrinsics.next().expect("index is the position of the extrinsic; qed");
+
+		api.trace_extrinsic(&parent_id, extrinsic, tracer)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
 }
 
 #[test]
@@ -202,6 +202,10 @@ impl Config for Runtime {
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = ConstU32<100>;
+	type CodeUpgradeDelay = ConstU32<10>;
+	type MinBaseFeePerGas = ConstU128<0>;
+	type BlockGasTarget = ConstU64<10_000_000>;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = crate::runner::stack::Runner<Self>;
@@ -37,8 +37,8 @@ use frame_support::{
 	pallet_prelude::*,
 	parameter_types,
 	traits::{
-		BalanceStatus, Currency, EitherOfDiverse, EnsureOrigin, ExistenceRequirement, FindAuthor, Get,
-		NamedReservableCurrency, OnKilledAccount,
+		BalanceStatus, Currency, EitherOfDiverse, EnsureOrigin, ExistenceRequirement, FindAuthor, Get, Imbalance,
+		NamedReservableCurrency, OnKilledAccount, OnUnbalanced,
 	},
 	transactional,
 	weights::{Pays, PostDispatchInfo, Weight},
@@ -59,8 +59,8 @@ pub use orml_traits::{currency::TransferAll, MultiCurrency};
 use primitive_types::{H160, H256, U256};
 pub use primitives::{
 	evm::{
-		convert_decimals_from_evm, convert_decimals_to_evm, CallInfo, CreateInfo, EvmAddress, ExecutionInfo, Vicinity,
-		MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
+		convert_decimals_from_evm, convert_decimals_from_evm_rounded, convert_decimals_to_evm, CallInfo, CreateInfo,
+		EvmAddress, ExecutionInfo, StateOverride, Vicinity, MIRRORED_NFT_ADDRESS_START, MIRRORED_TOKENS_ADDRESS_START,
 	},
 	task::TaskResult,
 	Balance, CurrencyId, ReserveIdentifier,
@@ -144,6 +144,33 @@ fn create_nft_contract<T: Config>(gas: u64) -> Weight {
 		.saturating_add(T::GasToWeight::convert(gas.saturating_sub(BASE_CREATE_GAS)))
 }
 
+/// The max change of the base fee per block is 1/8 of it, same as EIP-1559.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u128 = 8;
+
+/// Calculate the base fee of the next block from the gas used in the current block.
+///
+/// The base fee increases when the gas used is above the target and decreases when below, by
+/// at most 1/8 per block. It never goes below `min_base_fee`.
+pub fn next_base_fee_per_gas(base_fee: Balance, gas_used: u64, gas_target: u64, min_base_fee: Balance) -> Balance {
+	if gas_target.is_zero() {
+		return base_fee.max(min_base_fee);
+	}
+
+	let next_base_fee = if gas_used > gas_target {
+		let delta = base_fee.saturating_mul(gas_used.saturating_sub(gas_target).into())
+			/ u128::from(gas_target)
+			/ BASE_FEE_MAX_CHANGE_DENOMINATOR;
+		base_fee.saturating_add(delta)
+	} else {
+		let delta = base_fee.saturating_mul(gas_target.saturating_sub(gas_used).into())
+			/ u128::from(gas_target)
+			/ BASE_FEE_MAX_CHANGE_DENOMINATOR;
+		base_fee.saturating_sub(delta)
+	};
+
+	next_base_fee.max(min_base_fee)
+}
+
 /// Helper method to calculate `call` weight.
 fn call_weight<T: Config>(gas: u64) -> Weight {
 	<T as Config>::WeightInfo::call()
@@ -159,6 +186,8 @@ pub mod module {
 	parameter_types! {
 		// Contract max code size.
 		pub const MaxCodeSize: u32 = 60 * 1024;
+		// Code upgrade migration max input size.
+		pub const MaxMigrationSize: u32 = 4 * 1024;
 	}
 
 	/// EVM module trait
@@ -227,6 +256,23 @@ pub mod module {
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
+		/// The number of blocks a contract must stay unused before it is reclaimed, the abandoned
+		/// contracts are scanned by the idle scheduler once every period.
+		#[pallet::constant]
+		type AbandonedContractPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks between proposing and enacting a contract code upgrade.
+		#[pallet::constant]
+		type CodeUpgradeDelay: Get<Self::BlockNumber>;
+
+		/// The minimum base fee per gas, it's also the base fee before any adjustment.
+		#[pallet::constant]
+		type MinBaseFeePerGas: Get<BalanceOf<Self>>;
+
+		/// The gas used per block targeted by the base fee adjustment.
+		#[pallet::constant]
+		type BlockGasTarget: Get<u64>;
+
 		type FreePublicationOrigin: EnsureOrigin<Self::Origin>;
 
 		/// EVM execution runner.
@@ -270,6 +316,43 @@ pub mod module {
 		pub ref_count: u32,
 	}
 
+	#[derive(Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+	pub struct CodeDepositInfo {
+		/// Storage bytes of the code deposit, paid by the referencing contracts or parked
+		pub total: u32,
+		/// Storage bytes of the code deposit left by the removed contracts, reserved on the
+		/// treasury account until refunded to the depositors
+		pub parked: u32,
+		/// Number of the contracts paying the code deposit
+		pub refs: u32,
+		/// Whether a `RebalanceCodeDeposit` task is scheduled
+		pub rebalancing: bool,
+	}
+
+	impl CodeDepositInfo {
+		/// Storage bytes the deposit must cover, the code size while contracts are paying it.
+		pub fn required(&self, code_size: u32) -> u32 {
+			if self.refs.is_zero() {
+				0
+			} else {
+				code_size
+			}
+		}
+
+		/// Storage bytes of the deposit over the required, to be refunded.
+		pub fn surplus(&self, code_size: u32) -> u32 {
+			self.total.saturating_sub(self.required(code_size))
+		}
+	}
+
+	#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+	pub struct CodeUpgrade<BlockNumber> {
+		pub code_hash: H256,
+		pub enact_at: BlockNumber,
+		/// The input of the call made to the contract right after the code is upgraded.
+		pub migration: BoundedVec<u8, MaxMigrationSize>,
+	}
+
 	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	/// Account definition used for genesis block construction.
@@ -332,6 +415,29 @@ pub mod module {
 	#[pallet::getter(fn code_infos)]
 	pub type CodeInfos<T: Config> = StorageMap<_, Identity, H256, CodeInfo, OptionQuery>;
 
+	/// The storage deposit of the code shared by the contracts.
+	///
+	/// CodeDeposits: H256 => Option<CodeDepositInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn code_deposits)]
+	pub type CodeDeposits<T: Config> = StorageMap<_, Identity, H256, CodeDepositInfo, OptionQuery>;
+
+	/// The storage bytes of the code deposit paid by the contract.
+	///
+	/// ContractCodeDeposits: double_map H256, EvmAddress => Option<u32>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_code_deposits)]
+	pub type ContractCodeDeposits<T: Config> =
+		StorageDoubleMap<_, Identity, H256, Twox64Concat, EvmAddress, u32, OptionQuery>;
+
+	/// The storage bytes of the code deposit parked for the depositor.
+	///
+	/// ParkedCodeDeposits: double_map H256, EvmAddress => u32
+	#[pallet::storage]
+	#[pallet::getter(fn parked_code_deposits)]
+	pub type ParkedCodeDeposits<T: Config> =
+		StorageDoubleMap<_, Identity, H256, Twox64Concat, EvmAddress, u32, ValueQuery>;
+
 	/// Next available system contract address.
 	///
 	/// NetworkContractIndex: u64
@@ -346,6 +452,49 @@ pub mod module {
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// The block number at which the contract was created or last executed.
+	/// Contracts created before the tracking was introduced have no entry until they are used.
+	///
+	/// ContractLastUsedAt: map EvmAddress => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_last_used_at)]
+	pub type ContractLastUsedAt<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The pending code upgrades of the contracts.
+	///
+	/// PendingCodeUpgrades: map EvmAddress => Option<CodeUpgrade<BlockNumber>>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_code_upgrades)]
+	pub type PendingCodeUpgrades<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, CodeUpgrade<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultBaseFeePerGas<T: Config>() -> BalanceOf<T> {
+		T::MinBaseFeePerGas::get()
+	}
+
+	/// The base fee per gas of the current block, burned out of the transaction fee.
+	///
+	/// BaseFeePerGas: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn base_fee_per_gas)]
+	pub type BaseFeePerGas<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery, DefaultBaseFeePerGas<T>>;
+
+	/// The gas used by the EVM executions in the current block.
+	///
+	/// BlockGasUsed: u64
+	#[pallet::storage]
+	#[pallet::getter(fn block_gas_used)]
+	pub type BlockGasUsed<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The index of the extrinsic and the gas used by its EVM executions, the base fee of the
+	/// gas is burned out of the transaction fee of the same extrinsic by `BurnBaseFee`.
+	///
+	/// ExtrinsicGasUsed: Option<(u32, u64)>
+	#[pallet::storage]
+	#[pallet::getter(fn extrinsic_gas_used)]
+	pub type ExtrinsicGasUsed<T: Config> = StorageValue<_, (u32, u64), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub chain_id: u64,
@@ -489,6 +638,29 @@ pub mod module {
 		ContractSetCode { contract: EvmAddress },
 		/// Selfdestructed contract code.
 		ContractSelfdestructed { contract: EvmAddress },
+		/// Reclaimed abandoned contract.
+		ContractReclaimed {
+			contract: EvmAddress,
+			maintainer: EvmAddress,
+		},
+		/// Proposed contract code upgrade.
+		CodeUpgradeProposed {
+			contract: EvmAddress,
+			code_hash: H256,
+			enact_at: T::BlockNumber,
+		},
+		/// Cancelled contract code upgrade.
+		CodeUpgradeCancelled { contract: EvmAddress, code_hash: H256 },
+		/// Enacted contract code upgrade.
+		CodeUpgradeEnacted { contract: EvmAddress, code_hash: H256 },
+		/// Refunded parked code deposit.
+		CodeDepositRefunded {
+			code_hash: H256,
+			depositor: EvmAddress,
+			amount: BalanceOf<T>,
+		},
+		/// Rebalanced code deposit of contract.
+		CodeDepositRebalanced { contract: EvmAddress, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -525,6 +697,22 @@ pub mod module {
 		InvalidDecimals,
 		/// Strict call failed
 		StrictCallFailed,
+		/// Contract is not abandoned
+		ContractNotAbandoned,
+		/// Code not found
+		CodeNotFound,
+		/// Contract already has a pending code upgrade
+		CodeUpgradeAlreadyPending,
+		/// Contract has no pending code upgrade
+		CodeUpgradeNotFound,
+		/// Code upgrade can not be enacted yet
+		CodeUpgradeNotReady,
+		/// Migration call of the code upgrade failed
+		CodeUpgradeMigrationFailed,
+		/// Migration input exceeds max migration size
+		MigrationExceedsMaxSize,
+		/// The gas price is below the base fee per gas of the block
+		GasPriceTooLow,
 	}
 
 	#[pallet::pallet]
@@ -533,6 +721,39 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// reads and writes of `on_finalize`
+			let mut weight = T::DbWeight::get().reads_writes(2, 3);
+
+			// start a scan for the abandoned contracts once every `AbandonedContractPeriod`
+			let period = T::AbandonedContractPeriod::get();
+			if !period.is_zero() && (now % period).is_zero() {
+				if let Err(e) = T::IdleScheduler::schedule(EvmTask::ReclaimAbandoned { start_key: None }.into()) {
+					log::warn!(
+						target: "evm",
+						"on_initialize: failed to schedule the abandoned contracts scan: {:?}",
+						e
+					);
+				}
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+			}
+
+			weight
+		}
+
+		fn on_finalize(_: T::BlockNumber) {
+			ExtrinsicGasUsed::<T>::kill();
+			let gas_used = BlockGasUsed::<T>::take();
+			BaseFeePerGas::<T>::mutate(|base_fee| {
+				*base_fee = next_base_fee_per_gas(
+					*base_fee,
+					gas_used,
+					T::BlockGasTarget::get(),
+					T::MinBaseFeePerGas::get(),
+				);
+			});
+		}
+
 		fn integrity_test() {
 			assert!(convert_decimals_from_evm(T::StorageDepositPerByte::get()).is_some());
 		}
@@ -555,6 +776,11 @@ pub mod module {
 			access_list: Vec<AccessListItem>,
 			#[pallet::compact] _valid_until: T::BlockNumber, // checked by tx validation logic
 		) -> DispatchResultWithPostInfo {
+			ensure!(
+				Self::is_base_fee_covered(T::TxFeePerGas::get()),
+				Error::<T>::GasPriceTooLow
+			);
+
 			match action {
 				TransactionAction::Call(target) => {
 					Self::call(origin, target, input, value, gas_limit, storage_limit, access_list)
@@ -1249,6 +1475,69 @@ pub mod module {
 				}
 			}
 		}
+
+		/// Propose to upgrade the code of a contract to an existing code. The upgrade can be
+		/// enacted after `CodeUpgradeDelay` blocks, the maintainer can cancel it before that.
+		///
+		/// - `contract`: The contract to upgrade, the caller must be root or the contract's
+		///   maintainer
+		/// - `code_hash`: The hash of the new code, the code must be already deployed
+		/// - `migration`: The input of the call made to the contract by the maintainer right after
+		///   the code is upgraded, no call is made if it's empty
+		#[pallet::weight(<T as Config>::WeightInfo::propose_code_upgrade(migration.len() as u32))]
+		#[transactional]
+		pub fn propose_code_upgrade(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			code_hash: H256,
+			migration: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let root_or_signed = Self::ensure_root_or_signed(origin)?;
+			let enact_at = Self::do_propose_code_upgrade(root_or_signed, contract, code_hash, migration)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeProposed {
+				contract,
+				code_hash,
+				enact_at,
+			});
+			Ok(().into())
+		}
+
+		/// Cancel the pending code upgrade of a contract.
+		///
+		/// - `contract`: The contract to cancel the upgrade, the caller must be root or the
+		///   contract's maintainer
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_code_upgrade())]
+		#[transactional]
+		pub fn cancel_code_upgrade(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let root_or_signed = Self::ensure_root_or_signed(origin)?;
+			let code_hash = Self::do_cancel_code_upgrade(root_or_signed, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeCancelled { contract, code_hash });
+			Ok(().into())
+		}
+
+		/// Enact the pending code upgrade of a contract after the delay. The storage deposit
+		/// change of the code is paid or refunded by the maintainer, the storage of the migration
+		/// call is paid by the caller.
+		///
+		/// - `contract`: The contract to upgrade
+		/// - `gas_limit`: the maximum gas the migration call can use
+		/// - `storage_limit`: the total bytes the migration call can increase the storage by
+		#[pallet::weight(<T as Config>::WeightInfo::set_code(0).saturating_add(call_weight::<T>(*gas_limit)))]
+		#[transactional]
+		pub fn enact_code_upgrade(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let code_hash = Self::do_enact_code_upgrade(who, contract, gas_limit, storage_limit)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::CodeUpgradeEnacted { contract, code_hash });
+			Ok(().into())
+		}
 	}
 }
 
@@ -1292,6 +1581,11 @@ impl<T: Config> Pallet<T> {
 			let account_info = account_info.as_mut().ok_or(Error::<T>::ContractNotFound)?;
 			let contract_info = account_info.contract_info.take().ok_or(Error::<T>::ContractNotFound)?;
 
+			if let Some(code_info) = Self::code_infos(&contract_info.code_hash) {
+				// the refundable deposit is left on the contract and refunded with the storage
+				Self::remove_code_deposit(contract, caller, &contract_info.code_hash, code_info.code_size)?;
+			}
+
 			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
 				if let Some(code_info) = maybe_code_info.as_mut() {
 					code_info.ref_count = code_info.ref_count.saturating_sub(1);
@@ -1306,6 +1600,10 @@ impl<T: Config> Pallet<T> {
 			});
 
 			ContractStorageSizes::<T>::take(contract);
+			ContractLastUsedAt::<T>::remove(contract);
+			if let Some(upgrade) = PendingCodeUpgrades::<T>::take(contract) {
+				Self::release_code(&upgrade.code_hash);
+			}
 
 			T::IdleScheduler::schedule(
 				EvmTask::Remove {
@@ -1364,12 +1662,14 @@ impl<T: Config> Pallet<T> {
 	/// - Update codes info.
 	/// - Update maintainer of the contract.
 	/// - Save `code` if not saved yet.
-	pub fn create_contract(source: H160, address: H160, publish: bool, code: Vec<u8>) {
+	///
+	/// Returns the code hash of the contract, `None` if the code is empty.
+	pub fn create_contract(source: H160, address: H160, publish: bool, code: Vec<u8>) -> Option<H256> {
 		let bounded_code: BoundedVec<u8, MaxCodeSize> = code
 			.try_into()
 			.expect("checked by create_contract_limit in ACALA_CONFIG; qed");
 		if bounded_code.is_empty() {
-			return;
+			return None;
 		}
 
 		// if source is account, the maintainer of the new contract is source.
@@ -1410,7 +1710,11 @@ impl<T: Config> Pallet<T> {
 			}
 		});
 
+		ContractLastUsedAt::<T>::insert(address, frame_system::Pallet::<T>::block_number());
+
 		frame_system::Pallet::<T>::inc_providers(&T::AddressMapping::get_account_id(&address));
+
+		Some(code_hash)
 	}
 
 	/// Get the account basic in EVM format.
@@ -1428,6 +1732,99 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Apply the state override to the storage. Only used to simulate calls, the changes must not
+	/// be committed.
+	pub fn apply_state_override(state_override: StateOverride) -> DispatchResult {
+		for (address, account) in state_override {
+			ensure!(
+				account.state.is_none() || account.state_diff.is_none(),
+				DispatchError::Other("Both state and stateDiff are overridden")
+			);
+
+			if let Some(balance) = account.balance {
+				let balance =
+					convert_decimals_from_evm_rounded(UniqueSaturatedInto::<u128>::unique_saturated_into(balance));
+				T::Currency::make_free_balance_be(
+					&T::AddressMapping::get_account_id(&address),
+					balance.unique_saturated_into(),
+				);
+			}
+
+			if let Some(nonce) = account.nonce {
+				Accounts::<T>::mutate(address, |maybe_account_info| {
+					if let Some(account_info) = maybe_account_info.as_mut() {
+						account_info.nonce = nonce.into();
+					} else {
+						*maybe_account_info = Some(AccountInfo::<T::Index>::new(nonce.into(), None));
+					}
+				});
+			}
+
+			if let Some(code) = account.code {
+				let bounded_code: BoundedVec<u8, MaxCodeSize> = code
+					.try_into()
+					.map_err(|_| DispatchError::Other("Code override exceeds max code size"))?;
+				let code_hash = code_hash(bounded_code.as_slice());
+
+				if !CodeInfos::<T>::contains_key(&code_hash) {
+					CodeInfos::<T>::insert(
+						&code_hash,
+						CodeInfo {
+							code_size: bounded_code.len() as u32,
+							ref_count: 0,
+						},
+					);
+					Codes::<T>::insert(&code_hash, bounded_code);
+				}
+				CodeInfos::<T>::mutate(&code_hash, |maybe_code_info| {
+					if let Some(code_info) = maybe_code_info.as_mut() {
+						code_info.ref_count = code_info.ref_count.saturating_add(1);
+					}
+				});
+
+				let old_code_hash = Self::code_hash_at_address(&address);
+				let was_contract = Self::is_contract(&address);
+				Accounts::<T>::mutate(address, |maybe_account_info| {
+					let account_info = maybe_account_info
+						.get_or_insert_with(|| AccountInfo::<T::Index>::new(Default::default(), None));
+					let maintainer = account_info
+						.contract_info
+						.as_ref()
+						.map_or_else(Default::default, |contract_info| contract_info.maintainer);
+					account_info.contract_info = Some(ContractInfo {
+						code_hash,
+						maintainer,
+						published: true,
+					});
+				});
+				if was_contract {
+					Self::release_code(&old_code_hash);
+				} else {
+					frame_system::Pallet::<T>::inc_providers(&T::AddressMapping::get_account_id(&address));
+				}
+			}
+
+			if let Some(state) = account.state {
+				let _ = AccountStorages::<T>::clear_prefix(address, u32::MAX, None);
+				for (index, value) in state.into_iter().filter(|(_, value)| !value.is_zero()) {
+					AccountStorages::<T>::insert(address, index, value);
+				}
+			}
+
+			if let Some(state_diff) = account.state_diff {
+				for (index, value) in state_diff {
+					if value.is_zero() {
+						AccountStorages::<T>::remove(address, index);
+					} else {
+						AccountStorages::<T>::insert(address, index, value);
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Get the author using the FindAuthor trait.
 	pub fn find_author() -> H160 {
 		let digest = <frame_system::Pallet<T>>::digest();
@@ -1595,14 +1992,8 @@ impl<T: Config> Pallet<T> {
 				return Ok(());
 			}
 
-			let storage_size_changed: i32 =
-				code_size.saturating_add(T::NewContractExtraBytes::get()) as i32 - old_code_info.code_size as i32;
-
-			if storage_size_changed.is_positive() {
-				Self::reserve_storage(&source, storage_size_changed as u32)?;
-			}
-			Self::charge_storage(&source, &contract, storage_size_changed)?;
-			Self::update_contract_storage_size(&contract, storage_size_changed);
+			let refunded =
+				Self::remove_code_deposit(&contract, &source, &contract_info.code_hash, old_code_info.code_size)?;
 
 			// try remove old codes
 			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| -> DispatchResult {
@@ -1628,6 +2019,17 @@ impl<T: Config> Pallet<T> {
 					Codes::<T>::insert(&code_hash, bounded_code);
 				}
 			});
+			Self::add_code_deposit(&contract, &code_hash, code_size);
+
+			let storage_size_changed: i32 =
+				code_size.saturating_add(T::NewContractExtraBytes::get()) as i32 - refunded as i32;
+
+			if storage_size_changed.is_positive() {
+				Self::reserve_storage(&source, storage_size_changed as u32)?;
+			}
+			Self::charge_storage(&source, &contract, storage_size_changed)?;
+			Self::update_contract_storage_size(&contract, storage_size_changed);
+
 			// update code_hash
 			contract_info.code_hash = code_hash;
 
@@ -1649,6 +2051,376 @@ impl<T: Config> Pallet<T> {
 		Self::remove_contract(caller, contract)
 	}
 
+	/// Reclaim a contract which is not used for `AbandonedContractPeriod`.
+	///
+	/// The storage deposit and the remaining balance of the contract go to the maintainer.
+	/// Published contracts are never reclaimed. Returns the maintainer of the reclaimed contract.
+	#[transactional]
+	fn do_reclaim_abandoned_contract(contract: &EvmAddress) -> Result<EvmAddress, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		ensure!(!contract_info.published, Error::<T>::ContractAlreadyPublished);
+		let maintainer = contract_info.maintainer;
+
+		// untracked contracts are never reclaimable
+		let last_used_at = Self::contract_last_used_at(contract).ok_or(Error::<T>::ContractNotAbandoned)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= last_used_at.saturating_add(T::AbandonedContractPeriod::get()),
+			Error::<T>::ContractNotAbandoned
+		);
+
+		Self::remove_contract(&maintainer, contract)?;
+		Ok(maintainer)
+	}
+
+	/// Ensures the signer is the maintainer of the contract if not root, returns the contract
+	/// info.
+	fn ensure_maintainer_or_root(
+		root_or_signed: Either<(), T::AccountId>,
+		contract: &EvmAddress,
+	) -> Result<ContractInfo, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+
+		if let Either::Right(signer) = root_or_signed {
+			let maintainer = T::AddressMapping::get_evm_address(&signer).ok_or(Error::<T>::AddressNotMapped)?;
+			ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+		}
+
+		Ok(contract_info)
+	}
+
+	/// Propose a code upgrade for the contract, returns the block number it can be enacted at.
+	///
+	/// The new code is referenced by the proposal so it's kept until the upgrade is enacted
+	/// or cancelled.
+	fn do_propose_code_upgrade(
+		root_or_signed: Either<(), T::AccountId>,
+		contract: EvmAddress,
+		code_hash: H256,
+		migration: Vec<u8>,
+	) -> Result<T::BlockNumber, DispatchError> {
+		Self::ensure_maintainer_or_root(root_or_signed, &contract)?;
+		ensure!(
+			!PendingCodeUpgrades::<T>::contains_key(&contract),
+			Error::<T>::CodeUpgradeAlreadyPending
+		);
+		let migration: BoundedVec<u8, MaxMigrationSize> =
+			migration.try_into().map_err(|_| Error::<T>::MigrationExceedsMaxSize)?;
+
+		CodeInfos::<T>::try_mutate(&code_hash, |maybe_code_info| -> DispatchResult {
+			let code_info = maybe_code_info.as_mut().ok_or(Error::<T>::CodeNotFound)?;
+			code_info.ref_count = code_info.ref_count.saturating_add(1);
+			Ok(())
+		})?;
+
+		let enact_at = frame_system::Pallet::<T>::block_number().saturating_add(T::CodeUpgradeDelay::get());
+		PendingCodeUpgrades::<T>::insert(
+			&contract,
+			CodeUpgrade {
+				code_hash,
+				enact_at,
+				migration,
+			},
+		);
+
+		Ok(enact_at)
+	}
+
+	/// Cancel the pending code upgrade of the contract, returns the code hash of the upgrade.
+	fn do_cancel_code_upgrade(
+		root_or_signed: Either<(), T::AccountId>,
+		contract: EvmAddress,
+	) -> Result<H256, DispatchError> {
+		Self::ensure_maintainer_or_root(root_or_signed, &contract)?;
+		let upgrade = PendingCodeUpgrades::<T>::take(&contract).ok_or(Error::<T>::CodeUpgradeNotFound)?;
+		Self::release_code(&upgrade.code_hash);
+		Ok(upgrade.code_hash)
+	}
+
+	/// Enact the pending code upgrade of the contract, returns the code hash of the upgrade.
+	///
+	/// - Update the code hash and the storage deposit of the contract.
+	/// - Call the contract with the migration input as the maintainer.
+	fn do_enact_code_upgrade(
+		who: T::AccountId,
+		contract: EvmAddress,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<H256, DispatchError> {
+		let upgrade = PendingCodeUpgrades::<T>::take(&contract).ok_or(Error::<T>::CodeUpgradeNotFound)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() >= upgrade.enact_at,
+			Error::<T>::CodeUpgradeNotReady
+		);
+
+		let maintainer =
+			Accounts::<T>::try_mutate(&contract, |maybe_account_info| -> Result<EvmAddress, DispatchError> {
+				let contract_info = maybe_account_info
+					.as_mut()
+					.and_then(|account_info| account_info.contract_info.as_mut())
+					.ok_or(Error::<T>::ContractNotFound)?;
+				let maintainer = contract_info.maintainer;
+
+				let old_code_info = Self::code_infos(&contract_info.code_hash).ok_or(Error::<T>::ContractNotFound)?;
+				let new_code_info = Self::code_infos(&upgrade.code_hash).ok_or(Error::<T>::CodeNotFound)?;
+				let refunded = Self::remove_code_deposit(
+					&contract,
+					&maintainer,
+					&contract_info.code_hash,
+					old_code_info.code_size,
+				)?;
+				// the reference of the new code is taken when the upgrade is proposed
+				Self::add_code_deposit(&contract, &upgrade.code_hash, new_code_info.code_size);
+				let storage_size_changed: i32 = new_code_info.code_size as i32 - refunded as i32;
+
+				if storage_size_changed.is_positive() {
+					Self::reserve_storage(&maintainer, storage_size_changed as u32)?;
+				}
+				Self::charge_storage(&maintainer, &contract, storage_size_changed)?;
+				if storage_size_changed.is_negative() {
+					Self::unreserve_storage(&maintainer, 0, 0, storage_size_changed.unsigned_abs())?;
+				}
+				Self::update_contract_storage_size(&contract, storage_size_changed);
+
+				Self::release_code(&contract_info.code_hash);
+				contract_info.code_hash = upgrade.code_hash;
+
+				Ok(maintainer)
+			})?;
+
+		if !upgrade.migration.is_empty() {
+			let origin = T::AddressMapping::get_or_create_evm_address(&who);
+			let info = T::Runner::call(
+				maintainer,
+				origin,
+				contract,
+				upgrade.migration.into_inner(),
+				Zero::zero(),
+				gas_limit,
+				storage_limit,
+				vec![],
+				T::config(),
+			)?;
+			ensure!(info.exit_reason.is_succeed(), Error::<T>::CodeUpgradeMigrationFailed);
+		}
+
+		Ok(upgrade.code_hash)
+	}
+
+	/// Decrease the reference count of the code, the code is removed when it's not referenced.
+	/// The parked deposit of a removed code is kept until refunded to the depositors.
+	fn release_code(code_hash: &H256) {
+		CodeInfos::<T>::mutate_exists(code_hash, |maybe_code_info| {
+			if let Some(code_info) = maybe_code_info.as_mut() {
+				code_info.ref_count = code_info.ref_count.saturating_sub(1);
+				if code_info.ref_count == 0 {
+					Codes::<T>::remove(code_hash);
+					*maybe_code_info = None;
+				}
+			}
+		});
+	}
+
+	/// Add a reference of the contract to the code deposit, the contract pays the code in full.
+	///
+	/// The code is stored once, so the deposit over the code size is refunded by a
+	/// `RebalanceCodeDeposit` task until every contract pays its even share `code_size / refs`.
+	pub(crate) fn add_code_deposit(contract: &EvmAddress, code_hash: &H256, code_size: u32) {
+		let mut code_deposit = Self::code_deposits(code_hash).unwrap_or_default();
+		code_deposit.total = code_deposit.total.saturating_add(code_size);
+		code_deposit.refs = code_deposit.refs.saturating_add(1);
+		ContractCodeDeposits::<T>::insert(code_hash, contract, code_size);
+
+		Self::update_code_deposit(code_hash, code_deposit, code_size);
+	}
+
+	/// Remove the reference of the contract from the code deposit, returns the storage bytes of
+	/// the code refundable to the contract.
+	///
+	/// While the code is still paid by other contracts, the deposit must cover the code size so
+	/// the part of the contract's deposit which can't be refunded is parked in the treasury for
+	/// the `depositor`, and refunded by a `RebalanceCodeDeposit` task once it's covered by the
+	/// new references or the code is removed.
+	fn remove_code_deposit(
+		contract: &EvmAddress,
+		depositor: &EvmAddress,
+		code_hash: &H256,
+		code_size: u32,
+	) -> Result<u32, DispatchError> {
+		// the contracts created before the code deposit is shared paid the code in full
+		let deposit = match ContractCodeDeposits::<T>::take(code_hash, contract) {
+			Some(deposit) => deposit,
+			None => return Ok(code_size),
+		};
+		let mut code_deposit = Self::code_deposits(code_hash).unwrap_or_default();
+		code_deposit.refs = code_deposit.refs.saturating_sub(1);
+
+		let refundable = deposit.min(code_deposit.surplus(code_size));
+		let parked = deposit.saturating_sub(refundable);
+		if !parked.is_zero() {
+			let amount = Self::get_storage_deposit_per_byte().saturating_mul(parked.into());
+			let val = T::Currency::repatriate_reserved_named(
+				&RESERVE_ID_STORAGE_DEPOSIT,
+				&T::AddressMapping::get_account_id(contract),
+				&T::TreasuryAccount::get(),
+				amount,
+				BalanceStatus::Reserved,
+			)?;
+			debug_assert!(val.is_zero());
+			Self::update_contract_storage_size(contract, -(parked as i32));
+			ParkedCodeDeposits::<T>::mutate(code_hash, depositor, |depositor_parked| {
+				*depositor_parked = depositor_parked.saturating_add(parked)
+			});
+		}
+		code_deposit.total = code_deposit.total.saturating_sub(refundable);
+		code_deposit.parked = code_deposit.parked.saturating_add(parked);
+
+		Self::update_code_deposit(code_hash, code_deposit, code_size);
+
+		Ok(refundable)
+	}
+
+	/// Store the code deposit, schedules a `RebalanceCodeDeposit` task if it has surplus to refund.
+	fn update_code_deposit(code_hash: &H256, mut code_deposit: CodeDepositInfo, code_size: u32) {
+		if !code_deposit.rebalancing && !code_deposit.surplus(code_size).is_zero() {
+			match T::IdleScheduler::schedule(
+				EvmTask::RebalanceCodeDeposit {
+					code_hash: *code_hash,
+					start_key: None,
+				}
+				.into(),
+			) {
+				Ok(()) => code_deposit.rebalancing = true,
+				Err(e) => {
+					log::warn!(
+						target: "evm",
+						"update_code_deposit: failed to schedule the rebalance of code {:?}: {:?}",
+						code_hash, e
+					);
+				}
+			}
+		}
+
+		if code_deposit.total.is_zero() {
+			CodeDeposits::<T>::remove(code_hash);
+		} else {
+			CodeDeposits::<T>::insert(code_hash, code_deposit);
+		}
+	}
+
+	/// Refund the parked code deposit to the depositor from the treasury.
+	fn refund_parked_code_deposit(
+		code_hash: &H256,
+		depositor: &EvmAddress,
+		depositor_parked: u32,
+		refundable: u32,
+	) -> DispatchResult {
+		let amount = Self::get_storage_deposit_per_byte().saturating_mul(refundable.into());
+		let val = T::Currency::repatriate_reserved_named(
+			&RESERVE_ID_STORAGE_DEPOSIT,
+			&T::TreasuryAccount::get(),
+			&T::AddressMapping::get_account_id(depositor),
+			amount,
+			BalanceStatus::Free,
+		)?;
+		debug_assert!(val.is_zero());
+
+		if depositor_parked == refundable {
+			ParkedCodeDeposits::<T>::remove(code_hash, depositor);
+		} else {
+			ParkedCodeDeposits::<T>::insert(code_hash, depositor, depositor_parked - refundable);
+		}
+
+		Pallet::<T>::deposit_event(Event::<T>::CodeDepositRefunded {
+			code_hash: *code_hash,
+			depositor: *depositor,
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Refund the code deposit the contract paid over its share to the maintainer.
+	fn refund_contract_code_deposit(
+		code_hash: &H256,
+		contract: &EvmAddress,
+		deposit: u32,
+		refundable: u32,
+	) -> DispatchResult {
+		let maintainer = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.map(|contract_info| contract_info.maintainer)
+			.ok_or(Error::<T>::ContractNotFound)?;
+
+		let amount = Self::get_storage_deposit_per_byte().saturating_mul(refundable.into());
+		let val = T::Currency::repatriate_reserved_named(
+			&RESERVE_ID_STORAGE_DEPOSIT,
+			&T::AddressMapping::get_account_id(contract),
+			&T::AddressMapping::get_account_id(&maintainer),
+			amount,
+			BalanceStatus::Free,
+		)?;
+		debug_assert!(val.is_zero());
+		Self::update_contract_storage_size(contract, -(refundable as i32));
+		ContractCodeDeposits::<T>::insert(code_hash, contract, deposit - refundable);
+
+		Pallet::<T>::deposit_event(Event::<T>::CodeDepositRebalanced {
+			contract: *contract,
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Record the gas used in the block and in the current extrinsic.
+	pub(crate) fn record_gas_used(used_gas: u64) {
+		BlockGasUsed::<T>::mutate(|block_gas_used| *block_gas_used = block_gas_used.saturating_add(used_gas));
+
+		// executions without an extrinsic origin don't pay the transaction fee
+		if Self::extrinsic_origin().is_none() {
+			return;
+		}
+		if let Some(index) = frame_system::Pallet::<T>::extrinsic_index() {
+			ExtrinsicGasUsed::<T>::mutate(|maybe_gas_used| {
+				let gas_used = match maybe_gas_used {
+					Some((extrinsic_index, gas_used)) if *extrinsic_index == index => gas_used.saturating_add(used_gas),
+					_ => used_gas,
+				};
+				*maybe_gas_used = Some((index, gas_used));
+			});
+		}
+	}
+
+	/// Take the base fee of the gas used by the current extrinsic.
+	pub fn take_extrinsic_base_fee() -> BalanceOf<T> {
+		match ExtrinsicGasUsed::<T>::take() {
+			Some((index, gas_used)) if frame_system::Pallet::<T>::extrinsic_index() == Some(index) => {
+				Self::base_fee_per_gas().saturating_mul(gas_used.into())
+			}
+			_ => Zero::zero(),
+		}
+	}
+
+	/// Whether the gas price of an Ethereum transaction covers the base fee per gas of the block.
+	pub fn is_base_fee_covered(gas_price: BalanceOf<T>) -> bool {
+		gas_price >= convert_decimals_to_evm(Self::base_fee_per_gas())
+	}
+
+	/// Record the contract as used at the current block.
+	///
+	/// The write is registered to the block weight instead of being charged as gas, so the used
+	/// gas of an execution doesn't depend on the earlier executions in the block.
+	pub(crate) fn mark_contract_used(address: &EvmAddress) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut weight = T::DbWeight::get().reads(2);
+		if Self::is_contract(address) && Self::contract_last_used_at(address) != Some(now) {
+			ContractLastUsedAt::<T>::insert(address, now);
+			weight = weight.saturating_add(T::DbWeight::get().writes(1));
+		}
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+	}
+
 	fn ensure_root_or_signed(o: T::Origin) -> Result<Either<(), T::AccountId>, BadOrigin> {
 		EitherOfDiverse::<EnsureRoot<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(o)
 			.map_or(Err(BadOrigin), Ok)
@@ -2007,6 +2779,31 @@ impl<T: Config + Send + Sync> SignedExtension for SetEvmOrigin<T> {
 	}
 }
 
+/// Burn the base fee of the EVM executions out of the transaction fee and pass the rest of the
+/// fee and the tip to `OnFee`.
+///
+/// The base fee is computed from the gas used by the EVM executions of the dispatched extrinsic,
+/// taken from the fee already paid by the extrinsic origin and capped at it, so the origin never
+/// pays more than the max fee of the transaction.
+pub struct BurnBaseFee<T, OnFee>(PhantomData<(T, OnFee)>);
+impl<T: Config, OnFee: OnUnbalanced<NegativeImbalanceOf<T>>> OnUnbalanced<NegativeImbalanceOf<T>>
+	for BurnBaseFee<T, OnFee>
+{
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>) {
+		if let Some(fees) = fees_then_tips.next() {
+			// drop the imbalance to burn the base fee
+			let (_base_fee, fees) = fees.split(Pallet::<T>::take_extrinsic_base_fee());
+			OnFee::on_unbalanceds(Some(fees).into_iter().chain(fees_then_tips));
+		}
+	}
+
+	fn on_nonzero_unbalanced(fees: NegativeImbalanceOf<T>) {
+		// drop the imbalance to burn the base fee
+		let (_base_fee, fees) = fees.split(Pallet::<T>::take_extrinsic_base_fee());
+		OnFee::on_unbalanced(fees);
+	}
+}
+
 #[derive(Clone, RuntimeDebug, PartialEq, Encode, Decode, TypeInfo)]
 pub enum EvmTask<T: Config> {
 	// TODO: update
@@ -2023,6 +2820,15 @@ pub enum EvmTask<T: Config> {
 		contract: EvmAddress,
 		maintainer: EvmAddress,
 	},
+	/// Reclaim the contracts not used for `AbandonedContractPeriod`, scanning `ContractLastUsedAt`
+	/// from `start_key` in bounded chunks.
+	ReclaimAbandoned { start_key: Option<Vec<u8>> },
+	/// Refund the surplus of the code deposit, the parked deposits first and then the contracts
+	/// paying over their even share, scanning `ContractCodeDeposits` from `start_key`.
+	RebalanceCodeDeposit {
+		code_hash: H256,
+		start_key: Option<Vec<u8>>,
+	},
 }
 
 impl<T: Config> DispatchableTask for EvmTask<T> {
@@ -2081,6 +2887,209 @@ impl<T: Config> DispatchableTask for EvmTask<T> {
 					}
 				}
 			}
+			EvmTask::ReclaimAbandoned { start_key } => {
+				// every checked contract is accounted as a reclaimed one, default limit 100
+				let limit = cmp::min(
+					weight
+						.checked_div(<T as Config>::WeightInfo::reclaim_abandoned_contract())
+						.unwrap_or(100),
+					100,
+				) as usize;
+				if limit.is_zero() {
+					return TaskResult {
+						result: Ok(()),
+						used_weight: 0,
+						finished: false,
+					};
+				}
+
+				let now = frame_system::Pallet::<T>::block_number();
+				let mut iter = match start_key {
+					Some(key) => ContractLastUsedAt::<T>::iter_from(key),
+					None => ContractLastUsedAt::<T>::iter(),
+				};
+				let mut count: u64 = 0;
+				let mut abandoned = vec![];
+				for (contract, last_used_at) in iter.by_ref().take(limit) {
+					count = count.saturating_add(1);
+					if now >= last_used_at.saturating_add(T::AbandonedContractPeriod::get()) {
+						abandoned.push(contract);
+					}
+				}
+				let next_key = (count == limit as u64).then(|| iter.last_raw_key().to_vec());
+
+				let mut used_weight = <T as frame_system::Config>::DbWeight::get().reads(count);
+				for contract in abandoned {
+					match Pallet::<T>::do_reclaim_abandoned_contract(&contract) {
+						Ok(maintainer) => {
+							Pallet::<T>::deposit_event(Event::<T>::ContractReclaimed { contract, maintainer });
+						}
+						Err(e) => {
+							log::debug!(
+								target: "evm",
+								"EvmTask::ReclaimAbandoned: skip contract {:?}: {:?}",
+								contract, e
+							);
+						}
+					}
+					used_weight = used_weight.saturating_add(<T as Config>::WeightInfo::reclaim_abandoned_contract());
+				}
+
+				// continue the scan in a new task
+				let result = match next_key {
+					Some(start_key) => {
+						used_weight =
+							used_weight.saturating_add(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 2));
+						T::IdleScheduler::schedule(
+							EvmTask::ReclaimAbandoned {
+								start_key: Some(start_key),
+							}
+							.into(),
+						)
+					}
+					None => Ok(()),
+				};
+
+				TaskResult {
+					result,
+					used_weight,
+					finished: true,
+				}
+			}
+			EvmTask::RebalanceCodeDeposit { code_hash, start_key } => {
+				// every checked deposit is accounted as a refunded one, default limit 100
+				let entry_weight = cmp::max(
+					<T as Config>::WeightInfo::refund_code_deposit(),
+					<T as Config>::WeightInfo::rebalance_code_deposit(),
+				);
+				let limit = cmp::min(weight.checked_div(entry_weight).unwrap_or(100), 100) as usize;
+				if limit.is_zero() {
+					return TaskResult {
+						result: Ok(()),
+						used_weight: 0,
+						finished: false,
+					};
+				}
+
+				let mut used_weight = <T as frame_system::Config>::DbWeight::get().reads_writes(2, 1);
+				let mut code_deposit = match Pallet::<T>::code_deposits(&code_hash) {
+					Some(code_deposit) => code_deposit,
+					None => {
+						return TaskResult {
+							result: Ok(()),
+							used_weight,
+							finished: true,
+						}
+					}
+				};
+				// the parked deposits of a removed code are all refundable
+				let code_size = Pallet::<T>::code_infos(&code_hash).map_or(0, |code_info| code_info.code_size);
+				let mut surplus = code_deposit.surplus(code_size);
+				let mut count: usize = 0;
+				// `Some(start_key)` to continue in a new task
+				let mut next_key = None;
+
+				// refund the deposits parked for the removed contracts first
+				if start_key.is_none() && !code_deposit.parked.is_zero() {
+					let parked = ParkedCodeDeposits::<T>::iter_prefix(&code_hash)
+						.take(limit)
+						.collect::<Vec<_>>();
+					for (depositor, depositor_parked) in parked {
+						if surplus.is_zero() {
+							break;
+						}
+						count = count.saturating_add(1);
+						let refundable = depositor_parked.min(surplus);
+						match Pallet::<T>::refund_parked_code_deposit(
+							&code_hash,
+							&depositor,
+							depositor_parked,
+							refundable,
+						) {
+							Ok(()) => {
+								surplus = surplus.saturating_sub(refundable);
+								code_deposit.total = code_deposit.total.saturating_sub(refundable);
+								code_deposit.parked = code_deposit.parked.saturating_sub(refundable);
+							}
+							Err(e) => {
+								log::debug!(
+									target: "evm",
+									"EvmTask::RebalanceCodeDeposit: skip depositor {:?}: {:?}",
+									depositor, e
+								);
+							}
+						}
+					}
+					used_weight = used_weight
+						.saturating_add(<T as Config>::WeightInfo::refund_code_deposit().saturating_mul(count as u64));
+					if !surplus.is_zero() && count == limit {
+						next_key = Some(None);
+					}
+				}
+
+				// then refund the contracts paying over their even share, once nothing is parked
+				if code_deposit.parked.is_zero() && !surplus.is_zero() && count < limit {
+					let share = code_size / code_deposit.refs.max(1);
+					let mut iter = match start_key {
+						Some(key) => ContractCodeDeposits::<T>::iter_prefix_from(&code_hash, key),
+						None => ContractCodeDeposits::<T>::iter_prefix(&code_hash),
+					};
+					let deposits = iter.by_ref().take(limit - count).collect::<Vec<_>>();
+					if deposits.len() == limit - count {
+						next_key = Some(Some(iter.last_raw_key().to_vec()));
+					}
+					used_weight = used_weight.saturating_add(
+						<T as Config>::WeightInfo::rebalance_code_deposit().saturating_mul(deposits.len() as u64),
+					);
+
+					for (contract, deposit) in deposits {
+						let refundable = deposit.saturating_sub(share).min(surplus);
+						if refundable.is_zero() {
+							continue;
+						}
+						match Pallet::<T>::refund_contract_code_deposit(&code_hash, &contract, deposit, refundable) {
+							Ok(()) => {
+								surplus = surplus.saturating_sub(refundable);
+								code_deposit.total = code_deposit.total.saturating_sub(refundable);
+							}
+							Err(e) => {
+								log::debug!(
+									target: "evm",
+									"EvmTask::RebalanceCodeDeposit: skip contract {:?}: {:?}",
+									contract, e
+								);
+							}
+						}
+					}
+					if surplus.is_zero() {
+						next_key = None;
+					}
+				}
+
+				// continue in a new task, otherwise the surplus left is refunded by the task
+				// scheduled on the next update of the code deposit
+				code_deposit.rebalancing = next_key.is_some();
+				let result = match next_key {
+					Some(start_key) => {
+						used_weight =
+							used_weight.saturating_add(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 2));
+						T::IdleScheduler::schedule(EvmTask::RebalanceCodeDeposit { code_hash, start_key }.into())
+					}
+					None => Ok(()),
+				};
+				code_deposit.rebalancing &= result.is_ok();
+				if code_deposit.total.is_zero() {
+					CodeDeposits::<T>::remove(&code_hash);
+				} else {
+					CodeDeposits::<T>::insert(&code_hash, code_deposit);
+				}
+
+				TaskResult {
+					result,
+					used_weight,
+					finished: true,
+				}
+			}
 		}
 	}
 }
@@ -209,6 +209,10 @@ impl Config for Runtime {
 	type DeveloperDeposit = ConstU128<DEVELOPER_DEPOSIT>;
 	type PublicationFee = ConstU128<PUBLICATION_FEE>;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = ConstU64<100>;
+	type CodeUpgradeDelay = ConstU64<10>;
+	type MinBaseFeePerGas = ConstU128<0>;
+	type BlockGasTarget = ConstU64<10_000_000>;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
 
 	type Runner = crate::runner::stack::Runner<Self>;
@@ -19,11 +19,12 @@
 pub mod stack;
 pub mod state;
 pub mod storage_meter;
+pub mod tracing;
 
 use crate::{BalanceOf, CallInfo, Config, CreateInfo};
 use frame_support::dispatch::DispatchError;
 use module_evm_utility::evm;
-pub use primitives::evm::{EvmAddress, Vicinity};
+pub use primitives::evm::{AccessListInfo, BundledCall, EvmAddress, StorageDeposit, Vicinity};
 use sp_core::{H160, H256};
 use sp_std::vec::Vec;
 
@@ -95,4 +96,31 @@ pub trait RunnerExtended<T: Config>: Runner<T> {
 		access_list: Vec<(H160, Vec<H256>)>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError>;
+
+	fn rpc_create_access_list(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<AccessListInfo, DispatchError>;
+
+	fn rpc_storage_deposits(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<Vec<StorageDeposit>, DispatchError>;
+
+	fn rpc_call_bundle(
+		calls: Vec<BundledCall<BalanceOf<T>>>,
+		config: &evm::Config,
+	) -> Vec<Result<CallInfo, DispatchError>>;
 }
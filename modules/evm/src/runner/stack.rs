@@ -21,7 +21,7 @@
 
 use crate::{
 	runner::{
-		state::{Accessed, StackExecutor, StackState as StackStateT, StackSubstateMetadata},
+		state::{Accessed, PrecompileSet, StackExecutor, StackState as StackStateT, StackSubstateMetadata},
 		Runner as RunnerT, RunnerExtended,
 	},
 	AccountInfo, AccountStorages, Accounts, BalanceOf, CallInfo, Config, CreateInfo, Error, ExecutionInfo, One, Pallet,
@@ -39,12 +39,18 @@ use module_evm_utility::{
 };
 use module_support::AddressMapping;
 pub use primitives::{
-	evm::{convert_decimals_from_evm, EvmAddress, Vicinity, MIRRORED_NFT_ADDRESS_START},
+	evm::{
+		convert_decimals_from_evm, convert_decimals_to_evm, AccessListInfo, AccessListItem, EvmAddress, Vicinity,
+		MIRRORED_NFT_ADDRESS_START,
+	},
 	ReserveIdentifier,
 };
 use sha3::{Digest, Keccak256};
 use sp_core::{H160, H256, U256};
-use sp_runtime::traits::{UniqueSaturatedInto, Zero};
+use sp_runtime::{
+	traits::{UniqueSaturatedInto, Zero},
+	TransactionOutcome,
+};
 use sp_std::{
 	boxed::Box,
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -53,6 +59,9 @@ use sp_std::{
 	vec::Vec,
 };
 
+/// The max number of executions to find a stable access list.
+const MAX_ACCESS_LIST_ITERATIONS: usize = 10;
+
 #[derive(Default)]
 pub struct Runner<T: Config> {
 	_marker: PhantomData<T>,
@@ -77,9 +86,13 @@ impl<T: Config> Runner<T> {
 		) -> (ExitReason, R),
 	{
 		let gas_price = U256::one();
+		let base_fee_per_gas = Pallet::<T>::base_fee_per_gas();
 		let vicinity = Vicinity {
 			gas_price,
 			origin,
+			// keep the default base fee of one when the base fee is disabled
+			block_base_fee_per_gas: (!base_fee_per_gas.is_zero())
+				.then(|| U256::from(convert_decimals_to_evm(base_fee_per_gas))),
 			..Default::default()
 		};
 
@@ -124,6 +137,10 @@ impl<T: Config> Runner<T> {
 
 		let state = executor.into_state();
 
+		for address in &state.used_contracts {
+			Pallet::<T>::mark_contract_used(address);
+		}
+
 		// charge storage
 		let actual_storage = state
 			.metadata()
@@ -186,6 +203,10 @@ impl<T: Config> Runner<T> {
 			})?;
 		}
 
+		if !skip_storage_rent {
+			Pallet::<T>::record_gas_used(used_gas.low_u64());
+		}
+
 		for address in state.substate.deletes {
 			log::debug!(
 				target: "evm",
@@ -362,6 +383,104 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 	}
 }
 
+impl<T: Config> Runner<T> {
+	/// Execute the call or create, without charging for storage rent, and inspect the executor
+	/// with `f` after the execution. All changes are reverted.
+	#[allow(clippy::too_many_arguments)]
+	fn execute_inspect<R>(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+		f: impl FnOnce(&StackExecutor<'_, '_, SubstrateStackState<'_, '_, T>, T::PrecompilesType>, H160) -> R,
+	) -> Result<ExecutionInfo<R>, DispatchError> {
+		let precompiles = T::PrecompilesValue::get();
+		frame_support::storage::with_transaction(|| {
+			let result = Self::execute(
+				source,
+				source,
+				value,
+				gas_limit,
+				storage_limit,
+				config,
+				true,
+				&precompiles,
+				|executor| {
+					let (reason, receiver) = match target {
+						Some(target) => (
+							executor
+								.transact_call(source, target, value, input, gas_limit, access_list)
+								.0,
+							target,
+						),
+						None => {
+							let address = executor
+								.create_address(evm::CreateScheme::Legacy { caller: source })
+								.unwrap_or_default(); // transact_create will check the address
+							(
+								executor.transact_create(source, value, input, gas_limit, access_list),
+								address,
+							)
+						}
+					};
+
+					(reason, f(executor, receiver))
+				},
+			);
+			TransactionOutcome::Rollback(result)
+		})
+	}
+
+	/// Execute the call or create and return the accessed addresses and storage keys.
+	#[allow(clippy::too_many_arguments)]
+	fn execute_accessed(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<ExecutionInfo<Vec<(H160, Vec<H256>)>>, DispatchError> {
+		Self::execute_inspect(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			config,
+			|executor, receiver| {
+				// the sender, the receiver and the precompiles are always warm
+				let mut accessed = BTreeMap::<H160, Vec<H256>>::new();
+				if let Some(Accessed {
+					accessed_addresses,
+					accessed_storage,
+				}) = executor.state().metadata().accessed()
+				{
+					for address in accessed_addresses {
+						if *address != source && *address != receiver && !executor.precompiles().is_precompile(*address)
+						{
+							accessed.entry(*address).or_default();
+						}
+					}
+					for (address, key) in accessed_storage {
+						accessed.entry(*address).or_default().push(*key);
+					}
+				}
+
+				accessed.into_iter().collect()
+			},
+		)
+	}
+}
+
 impl<T: Config> RunnerExtended<T> for Runner<T> {
 	/// Special method for rpc call which won't charge for storage rent
 	/// Same as call but with skip_storage_rent: true
@@ -424,6 +543,175 @@ impl<T: Config> RunnerExtended<T> for Runner<T> {
 			},
 		)
 	}
+
+	/// Special method for rpc which finds the access list of the call or create. The execution is
+	/// repeated with the found access list until it is stable.
+	fn rpc_create_access_list(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<AccessListInfo, DispatchError> {
+		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
+
+		let used_gas_without_access_list = Self::execute_accessed(
+			source,
+			target,
+			input.clone(),
+			value,
+			gas_limit,
+			storage_limit,
+			vec![],
+			config,
+		)?
+		.used_gas;
+
+		let mut access_list = access_list;
+		for _ in 0..MAX_ACCESS_LIST_ITERATIONS {
+			let info = Self::execute_accessed(
+				source,
+				target,
+				input.clone(),
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.clone(),
+				config,
+			)?;
+
+			if info.value == access_list {
+				return Ok(AccessListInfo {
+					exit_reason: info.exit_reason,
+					access_list: access_list
+						.into_iter()
+						.map(|(address, storage_keys)| AccessListItem { address, storage_keys })
+						.collect(),
+					used_gas: info.used_gas,
+					gas_saved: used_gas_without_access_list.saturating_sub(info.used_gas),
+				});
+			}
+
+			// the warm slots may change the execution path
+			access_list = info.value;
+		}
+
+		Err(DispatchError::Other("Failed to find a stable access list"))
+	}
+
+	/// Special method for rpc which returns the storage charged and refunded per contract by the
+	/// call or create.
+	fn rpc_storage_deposits(
+		source: H160,
+		target: Option<H160>,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<(H160, Vec<H256>)>,
+		config: &evm::Config,
+	) -> Result<Vec<StorageDeposit>, DispatchError> {
+		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
+
+		let info = Self::execute_inspect(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			config,
+			|executor, _| {
+				let storage_logs = &executor.state().substate.storage_logs;
+				storage_logs
+					.iter()
+					.filter(|(_, storage)| !storage.is_zero())
+					.fold(BTreeMap::<H160, (u32, u32)>::new(), |mut bmap, (contract, storage)| {
+						let (charged, refunded) = bmap.entry(*contract).or_default();
+						if storage.is_positive() {
+							*charged = charged.saturating_add(storage.unsigned_abs());
+						} else {
+							*refunded = refunded.saturating_add(storage.unsigned_abs());
+						}
+						bmap
+					})
+					.into_iter()
+					// query the maintainer before the changes are reverted
+					.map(|(contract, (charged, refunded))| StorageDeposit {
+						contract,
+						maintainer: Pallet::<T>::accounts(contract)
+							.and_then(|account_info| account_info.contract_info)
+							.map(|contract_info| contract_info.maintainer),
+						charged,
+						refunded,
+					})
+					.collect()
+			},
+		)?;
+
+		Ok(info.value)
+	}
+
+	/// Special method for rpc which executes the calls sequentially, each call is executed on top
+	/// of the state changed by the previous ones. A failed call doesn't change the state.
+	fn rpc_call_bundle(
+		calls: Vec<BundledCall<BalanceOf<T>>>,
+		config: &evm::Config,
+	) -> Vec<Result<CallInfo, DispatchError>> {
+		calls
+			.into_iter()
+			.map(|call| {
+				let access_list = call
+					.access_list
+					.unwrap_or_default()
+					.into_iter()
+					.map(|item| (item.address, item.storage_keys))
+					.collect();
+
+				frame_support::storage::with_transaction(|| {
+					let result = match call.to {
+						Some(to) => Self::rpc_call(
+							call.from,
+							call.from,
+							to,
+							call.data,
+							call.value,
+							call.gas_limit,
+							call.storage_limit,
+							access_list,
+							config,
+						),
+						None => Self::rpc_create(
+							call.from,
+							call.data,
+							call.value,
+							call.gas_limit,
+							call.storage_limit,
+							access_list,
+							config,
+						)
+						.map(|info| CallInfo {
+							exit_reason: info.exit_reason,
+							value: info.value.as_bytes().to_vec(),
+							used_gas: info.used_gas,
+							used_storage: info.used_storage,
+							logs: info.logs,
+						}),
+					};
+
+					if result.is_ok() {
+						TransactionOutcome::Commit(result)
+					} else {
+						TransactionOutcome::Rollback(result)
+					}
+				})
+			})
+			.collect()
+	}
 }
 
 struct SubstrateStackSubstate<'config> {
@@ -577,6 +865,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 pub struct SubstrateStackState<'vicinity, 'config, T> {
 	vicinity: &'vicinity Vicinity,
 	substate: SubstrateStackSubstate<'config>,
+	/// The contracts called in the transaction, including the reverted frames.
+	used_contracts: BTreeSet<H160>,
 	_marker: PhantomData<T>,
 }
 
@@ -593,6 +883,7 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 				parent: None,
 				known_original_storage: BTreeMap::new(),
 			},
+			used_contracts: BTreeSet::new(),
 			_marker: PhantomData,
 		}
 	}
@@ -843,7 +1134,10 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config> for SubstrateStackState
 		);
 
 		let code_size = code.len() as u32;
-		Pallet::<T>::create_contract(*caller, address, is_published, code);
+		if let Some(code_hash) = Pallet::<T>::create_contract(*caller, address, is_published, code) {
+			// only the deployed contracts pay the code deposit, the genesis ones don't
+			Pallet::<T>::add_code_deposit(&address, &code_hash, code_size);
+		}
 
 		let used_storage = code_size.saturating_add(T::NewContractExtraBytes::get());
 		Pallet::<T>::update_contract_storage_size(&address, used_storage as i32);
@@ -911,6 +1205,10 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config> for SubstrateStackState
 		self.substate.mark_account_dirty(_address);
 	}
 
+	fn mark_contract_used(&mut self, address: H160) {
+		self.used_contracts.insert(address);
+	}
+
 	fn is_cold(&self, address: H160) -> bool {
 		self.substate
 			.recursive_is_cold(&|a| a.accessed_addresses.contains(&address))
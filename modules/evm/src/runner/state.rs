@@ -48,7 +48,12 @@ use sp_std::{
 };
 
 macro_rules! event {
-	($x:expr) => {};
+	($x:expr) => {
+		crate::runner::tracing::with(|listener| {
+			use crate::runner::tracing::Event::*;
+			listener.event($x)
+		})
+	};
 }
 
 #[cfg(feature = "tracing")]
@@ -85,7 +90,7 @@ macro_rules! emit_exit {
 		let reason = $reason;
 		event!(Exit {
 			reason: &reason,
-			return_value: &Vec::new(),
+			return_value: &[],
 		});
 		reason
 	}};
@@ -333,6 +338,8 @@ pub trait StackState<'config>: Backend {
 	fn transfer(&mut self, transfer: Transfer) -> Result<(), ExitError>;
 	fn reset_balance(&mut self, address: H160);
 	fn touch(&mut self, address: H160);
+	/// Record the contract at `address` as used, it's kept even if the frame is reverted.
+	fn mark_contract_used(&mut self, address: H160);
 }
 
 /// Data returned by a precompile on success.
@@ -468,6 +475,23 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 
 	/// Exit a substate. Panic if it results an empty substate stack.
 	pub fn exit_substate(&mut self, kind: StackExitKind) -> Result<(), ExitError> {
+		event!(SubstateExit {
+			gas_limit: self
+				.state
+				.metadata()
+				.gasometer
+				.gas()
+				.saturating_add(self.state.metadata().gasometer.total_used_gas()),
+			used_gas: self.state.metadata().gasometer.total_used_gas(),
+			used_storage: match kind {
+				StackExitKind::Succeeded => {
+					let storage_meter = self.state.metadata().storage_meter();
+					storage_meter.total_used() as i32 - storage_meter.total_refunded() as i32
+				}
+				StackExitKind::Reverted | StackExitKind::Failed => 0,
+			},
+		});
+
 		match kind {
 			StackExitKind::Succeeded => self.state.exit_commit(),
 			StackExitKind::Reverted => self.state.exit_revert(),
@@ -512,7 +536,7 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			value,
 			init_code: &init_code,
 			gas_limit,
-			address: self.create_address(CreateScheme::Legacy { caller }),
+			address: self.create_address(CreateScheme::Legacy { caller }).unwrap_or_default(),
 		});
 
 		if let Err(e) = self.record_create_transaction_cost(&init_code, &access_list) {
@@ -550,11 +574,13 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			init_code: &init_code,
 			salt,
 			gas_limit,
-			address: self.create_address(CreateScheme::Create2 {
-				caller,
-				code_hash,
-				salt,
-			}),
+			address: self
+				.create_address(CreateScheme::Create2 {
+					caller,
+					code_hash,
+					salt,
+				})
+				.unwrap_or_default(),
 		});
 
 		if let Err(e) = self.record_create_transaction_cost(&init_code, &access_list) {
@@ -780,7 +806,18 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			gas - gas / 64
 		}
 
-		let address = match self.create_address(scheme) {
+		let address = self.create_address(scheme);
+
+		event!(Create {
+			caller,
+			address: address.clone().unwrap_or_default(),
+			scheme,
+			value,
+			init_code: &init_code,
+			target_gas
+		});
+
+		let address = match address {
 			Err(e) => {
 				return Capture::Exit((ExitReason::Error(e), None, Vec::new()));
 			}
@@ -793,15 +830,6 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		self.state.metadata_mut().access_address(caller);
 		self.state.metadata_mut().access_address(address);
 
-		event!(Create {
-			caller,
-			address,
-			scheme,
-			value,
-			init_code: &init_code,
-			target_gas
-		});
-
 		if let Some(depth) = self.state.metadata().depth {
 			if depth >= self.config.call_stack_limit {
 				return Capture::Exit((ExitError::CallTooDeep.into(), None, Vec::new()));
@@ -999,6 +1027,11 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 			}
 		}
 
+		// the last used block is written after the execution and not metered by the gasometer,
+		// so the used gas doesn't depend on the earlier executions in the block
+		self.state.mark_contract_used(code_address);
+		self.state.mark_contract_used(context.address);
+
 		if let Some(transfer) = transfer {
 			match self.state.transfer(transfer) {
 				Ok(()) => (),
@@ -1060,10 +1093,12 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> StackExecu
 		#[cfg(not(feature = "tracing"))]
 		let reason = self.execute(&mut runtime);
 		#[cfg(feature = "tracing")]
-		//let reason = module_evm_utility::evm::tracing::using(&mut Tracer, || self.execute(&mut runtime));
-		let reason = module_evm_utility::evm_runtime::tracing::using(&mut Tracer, || self.execute(&mut runtime));
-		//let reason = module_evm_utility::evm_gasometer::tracing::using(&mut Tracer, || self.execute(&mut
-		// runtime));
+		// an installed trace listener takes over the opcode events, otherwise log them
+		let reason = if crate::runner::tracing::is_active() {
+			self.execute(&mut runtime)
+		} else {
+			module_evm_utility::evm_runtime::tracing::using(&mut Tracer, || self.execute(&mut runtime))
+		};
 
 		log::debug!(target: "evm", "Call execution using address {}: {:?}", code_address, reason);
 
@@ -1218,19 +1253,6 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 		Ok(())
 	}
 
-	#[cfg(not(feature = "tracing"))]
-	fn create(
-		&mut self,
-		caller: H160,
-		scheme: CreateScheme,
-		value: U256,
-		init_code: Vec<u8>,
-		target_gas: Option<u64>,
-	) -> Capture<(ExitReason, Option<H160>, Vec<u8>), Self::CreateInterrupt> {
-		self.create_inner(caller, scheme, value, init_code, target_gas, true)
-	}
-
-	#[cfg(feature = "tracing")]
 	fn create(
 		&mut self,
 		caller: H160,
@@ -1248,29 +1270,6 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 		capture
 	}
 
-	#[cfg(not(feature = "tracing"))]
-	fn call(
-		&mut self,
-		code_address: H160,
-		transfer: Option<Transfer>,
-		input: Vec<u8>,
-		target_gas: Option<u64>,
-		is_static: bool,
-		context: Context,
-	) -> Capture<(ExitReason, Vec<u8>), Self::CallInterrupt> {
-		self.call_inner(
-			code_address,
-			transfer,
-			input,
-			target_gas,
-			is_static,
-			true,
-			true,
-			context,
-		)
-	}
-
-	#[cfg(feature = "tracing")]
 	fn call(
 		&mut self,
 		code_address: H160,
@@ -1302,6 +1301,9 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
 		// log::trace!(target: "evm", "Running opcode: {:?}, Pre gas-left: {:?}", opcode, gasometer.gas());
 
+		#[cfg(feature = "tracing")]
+		let gas_before = self.state.metadata().gasometer.gas();
+
 		if let Some(cost) = gasometer::static_opcode_cost(opcode) {
 			self.state.metadata_mut().gasometer.record_cost(cost)?;
 		} else {
@@ -1319,6 +1321,12 @@ impl<'config, 'precompiles, S: StackState<'config>, P: PrecompileSet> Handler
 			}
 		}
 
+		#[cfg(feature = "tracing")]
+		event!(StepCost {
+			gas: gas_before,
+			cost: gas_before.saturating_sub(self.state.metadata().gasometer.gas()),
+		});
+
 		Ok(())
 	}
 }
@@ -0,0 +1,356 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM execution tracing.
+//!
+//! The executor emits an `Event` when entering and leaving every call frame. The listener
+//! installed by `using` (e.g. `CallTracer`) consumes these events. Opcode level events are only
+//! available with the `tracing` feature enabled.
+
+use frame_support::dispatch::DispatchError;
+use module_evm_utility::evm::{Context, CreateScheme, ExitReason, Opcode, Transfer};
+pub use primitives::evm::tracing::{CallTrace, CallType, Step, TraceOutcome, TracerConfig};
+use sp_core::{H160, H256, U256};
+use sp_std::vec::Vec;
+
+pub enum Event<'a> {
+	Call {
+		code_address: H160,
+		transfer: &'a Option<Transfer>,
+		input: &'a [u8],
+		target_gas: Option<u64>,
+		is_static: bool,
+		context: &'a Context,
+	},
+	Create {
+		caller: H160,
+		address: H160,
+		scheme: CreateScheme,
+		value: U256,
+		init_code: &'a [u8],
+		target_gas: Option<u64>,
+	},
+	Suicide {
+		address: H160,
+		target: H160,
+		balance: U256,
+	},
+	Exit {
+		reason: &'a ExitReason,
+		return_value: &'a [u8],
+	},
+	TransactCall {
+		caller: H160,
+		address: H160,
+		value: U256,
+		data: &'a [u8],
+		gas_limit: u64,
+	},
+	TransactCreate {
+		caller: H160,
+		value: U256,
+		init_code: &'a [u8],
+		gas_limit: u64,
+		address: H160,
+	},
+	TransactCreate2 {
+		caller: H160,
+		value: U256,
+		init_code: &'a [u8],
+		salt: H256,
+		gas_limit: u64,
+		address: H160,
+	},
+	/// The substate of the current frame is about to be exited.
+	SubstateExit {
+		gas_limit: u64,
+		used_gas: u64,
+		used_storage: i32,
+	},
+	/// An opcode is about to be executed.
+	Step {
+		opcode: Opcode,
+		pc: u64,
+		stack: &'a [H256],
+		memory: &'a [u8],
+	},
+	/// The gas of the current opcode has been charged.
+	StepCost {
+		gas: u64,
+		cost: u64,
+	},
+}
+
+pub trait EventListener {
+	fn event(&mut self, event: Event);
+}
+
+environmental::environmental!(listener: dyn EventListener + 'static);
+
+/// Run `f` with `new` receiving the tracing events.
+pub fn using<R, F: FnOnce() -> R>(new: &mut (dyn EventListener + 'static), f: F) -> R {
+	#[cfg(feature = "tracing")]
+	let f = || module_evm_utility::evm_runtime::tracing::using(&mut RuntimeListener, f);
+
+	listener::using(new, f)
+}
+
+/// Emit an event to the installed listener, if any.
+pub(crate) fn with<F: FnOnce(&mut (dyn EventListener + 'static))>(f: F) {
+	listener::with(f);
+}
+
+/// Whether a listener is installed.
+#[cfg(feature = "tracing")]
+pub(crate) fn is_active() -> bool {
+	listener::with(|_| ()).is_some()
+}
+
+/// Execute `f` with the tracer specified by `config`, returning the result of `f` and the
+/// trace.
+pub fn trace<R, F: FnOnce() -> R>(config: TracerConfig, f: F) -> Result<(R, TraceOutcome), DispatchError> {
+	match config {
+		TracerConfig::CallTracer => {
+			let mut tracer = CallTracer::default();
+			let result = using(&mut tracer, f);
+			Ok((result, TraceOutcome::Calls(tracer.finalize())))
+		}
+		#[cfg(feature = "tracing")]
+		TracerConfig::OpcodeTracer {
+			disable_stack,
+			enable_memory,
+		} => {
+			let mut tracer = OpcodeTracer::new(disable_stack, enable_memory);
+			let result = using(&mut tracer, f);
+			Ok((result, TraceOutcome::Steps(tracer.finalize())))
+		}
+		#[cfg(not(feature = "tracing"))]
+		TracerConfig::OpcodeTracer { .. } => Err(DispatchError::Other(
+			"Opcode tracer is not available, the runtime is built without tracing feature",
+		)),
+	}
+}
+
+/// Records the call frames.
+#[derive(Default)]
+pub struct CallTracer {
+	stack: Vec<CallTrace>,
+	calls: Vec<CallTrace>,
+}
+
+impl CallTracer {
+	/// Returns the recorded top level frames. Unfinished frames are included with no exit
+	/// reason.
+	pub fn finalize(mut self) -> Vec<CallTrace> {
+		while let Some(frame) = self.stack.pop() {
+			self.finish_frame(frame);
+		}
+		self.calls
+	}
+
+	fn push_frame(&mut self, call_type: CallType, from: H160, to: H160, input: Vec<u8>, value: U256) {
+		self.stack.push(CallTrace {
+			call_type,
+			from,
+			to,
+			input,
+			value,
+			gas: 0,
+			gas_used: 0,
+			used_storage: 0,
+			output: Vec::new(),
+			exit_reason: None,
+			depth: self.stack.len() as u32,
+			calls: Vec::new(),
+		});
+	}
+
+	fn finish_frame(&mut self, frame: CallTrace) {
+		match self.stack.last_mut() {
+			Some(parent) => parent.calls.push(frame),
+			None => self.calls.push(frame),
+		}
+	}
+}
+
+impl EventListener for CallTracer {
+	fn event(&mut self, event: Event) {
+		match event {
+			Event::Call {
+				code_address,
+				transfer,
+				input,
+				is_static,
+				context,
+				..
+			} => {
+				let (call_type, from) = match transfer {
+					_ if is_static => (CallType::StaticCall, context.caller),
+					None => (CallType::DelegateCall, context.address),
+					Some(_) if context.address != code_address => (CallType::CallCode, context.address),
+					Some(_) => (CallType::Call, context.caller),
+				};
+				let value = transfer.as_ref().map(|t| t.value).unwrap_or_default();
+				self.push_frame(call_type, from, code_address, input.to_vec(), value);
+			}
+			Event::Create {
+				caller,
+				address,
+				value,
+				init_code,
+				..
+			} => {
+				self.push_frame(CallType::Create, caller, address, init_code.to_vec(), value);
+			}
+			Event::Suicide {
+				address,
+				target,
+				balance,
+			} => {
+				let depth = self.stack.len() as u32;
+				self.finish_frame(CallTrace {
+					call_type: CallType::Suicide,
+					from: address,
+					to: target,
+					input: Vec::new(),
+					value: balance,
+					gas: 0,
+					gas_used: 0,
+					used_storage: 0,
+					output: Vec::new(),
+					exit_reason: None,
+					depth,
+					calls: Vec::new(),
+				});
+			}
+			Event::SubstateExit {
+				gas_limit,
+				used_gas,
+				used_storage,
+			} => {
+				if let Some(frame) = self.stack.last_mut() {
+					frame.gas = gas_limit;
+					frame.gas_used = used_gas;
+					frame.used_storage = used_storage;
+				}
+			}
+			Event::Exit { reason, return_value } => {
+				// transaction failed before entering the frame
+				if let Some(mut frame) = self.stack.pop() {
+					frame.exit_reason = Some(reason.clone());
+					frame.output = return_value.to_vec();
+					self.finish_frame(frame);
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Records the executed opcodes.
+#[cfg(feature = "tracing")]
+pub struct OpcodeTracer {
+	disable_stack: bool,
+	enable_memory: bool,
+	depth: u32,
+	steps: Vec<Step>,
+}
+
+#[cfg(feature = "tracing")]
+impl OpcodeTracer {
+	pub fn new(disable_stack: bool, enable_memory: bool) -> Self {
+		Self {
+			disable_stack,
+			enable_memory,
+			depth: 0,
+			steps: Vec::new(),
+		}
+	}
+
+	pub fn finalize(self) -> Vec<Step> {
+		self.steps
+	}
+}
+
+#[cfg(feature = "tracing")]
+impl EventListener for OpcodeTracer {
+	fn event(&mut self, event: Event) {
+		match event {
+			Event::Call { .. } | Event::Create { .. } => {
+				self.depth = self.depth.saturating_add(1);
+			}
+			Event::Exit { .. } => {
+				self.depth = self.depth.saturating_sub(1);
+			}
+			Event::Step {
+				opcode,
+				pc,
+				stack,
+				memory,
+			} => {
+				self.steps.push(Step {
+					op: opcode.as_u8(),
+					pc,
+					depth: self.depth,
+					gas: 0,
+					gas_cost: 0,
+					stack: if self.disable_stack { Vec::new() } else { stack.to_vec() },
+					memory: if self.enable_memory {
+						memory.to_vec()
+					} else {
+						Vec::new()
+					},
+				});
+			}
+			Event::StepCost { gas, cost } => {
+				if let Some(step) = self.steps.last_mut() {
+					step.gas = gas;
+					step.gas_cost = cost;
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Forwards the opcode events of `evm_runtime` to the installed listener.
+#[cfg(feature = "tracing")]
+struct RuntimeListener;
+
+#[cfg(feature = "tracing")]
+impl module_evm_utility::evm_runtime::tracing::EventListener for RuntimeListener {
+	fn event(&mut self, event: module_evm_utility::evm_runtime::tracing::Event) {
+		if let module_evm_utility::evm_runtime::tracing::Event::Step {
+			opcode,
+			position,
+			stack,
+			memory,
+			..
+		} = event
+		{
+			with(|listener| {
+				listener.event(Event::Step {
+					opcode,
+					pc: position.as_ref().map_or(0, |pc| *pc as u64),
+					stack: stack.data(),
+					memory: memory.data(),
+				})
+			});
+		}
+	}
+}
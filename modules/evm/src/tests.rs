@@ -24,9 +24,12 @@ use mock::{evm_mod, Call, Event, IdleScheduler, *};
 use crate::runner::{
 	stack::SubstrateStackState,
 	state::{StackExecutor, StackState, StackSubstateMetadata},
+	tracing::{CallType, TraceOutcome, TracerConfig},
+	RunnerExtended,
 };
 use frame_support::{assert_noop, assert_ok, dispatch::DispatchErrorWithPostInfo};
 use module_support::AddressMapping;
+use primitives::evm::{AccountOverride, BundledCall, StorageDeposit};
 use sp_core::{
 	bytes::{from_hex, to_hex},
 	H160,
@@ -190,7 +193,7 @@ fn create_reverts_with_message() {
 	new_test_ext().execute_with(|| {
 		let result = <Runtime as Config>::Runner::create(
 			alice(),
-			contract,
+			contract.clone(),
 			0,
 			12_000_000,
 			12_000_000,
@@ -1446,224 +1449,848 @@ fn should_selfdestruct() {
 }
 
 #[test]
-fn storage_limit_should_work() {
+fn reclaim_abandoned_contract_works() {
 	// pragma solidity ^0.5.0;
-
-	// contract Factory {
-	// 	Contract[] newContracts;
-
-	// 	function createContract (uint num) public payable {
-	// 		for(uint i = 0; i < num; i++) {
-	// 			Contract newContract = new Contract();
-	// 			newContracts.push(newContract);
-	// 		}
-	// 	}
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
 	// }
-
-	// contract Contract {}
-	let contract = from_hex(
-		"0x608060405234801561001057600080fd5b506101a0806100206000396000f3fe60806040526004361061001e5760003560e01c80639db8d7d514610023575b600080fd5b61004f6004803603602081101561003957600080fd5b8101908080359060200190929190505050610051565b005b60008090505b8181101561010057600060405161006d90610104565b604051809103906000f080158015610089573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff16021790555050508080600101915050610057565b5050565b605b806101118339019056fe6080604052348015600f57600080fd5b50603e80601d6000396000f3fe6080604052600080fdfea265627a7a7231582035666e9471716d6d05ed9f0c1ab13d0371f49d536270f905bff06cd98212dcb064736f6c63430005110032a265627a7a723158203b6aaf6588bc3e6a35986612a62f715255430eab09ffb24401e5f18eb58a05d564736f6c63430005110032"
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
 	).unwrap();
 
 	new_test_ext().execute_with(|| {
+		let amount = 1000u128;
+
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
+
+		// create contract
 		let result = <Runtime as Config>::Runner::create(
 			alice(),
-			contract.clone(),
-			0,
-			200_000,
-			1000,
+			contract,
+			convert_decimals_to_evm(amount),
+			1000000,
+			100000,
 			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
-		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
-		assert_eq!(result.used_storage, 516);
-		let alice_balance = INITIAL_BALANCE - 516 * EVM::get_storage_deposit_per_byte();
-		assert_eq!(balance(alice()), alice_balance);
 
-		let factory_contract_address = result.value;
-
-		#[cfg(not(feature = "with-ethereum-compatibility"))]
-		publish_free(factory_contract_address);
-
-		assert_eq!(balance(factory_contract_address), 0);
-		assert_eq!(
-			reserved_balance(factory_contract_address),
-			516 * EVM::get_storage_deposit_per_byte()
-		);
-
-		// Factory.createContract(1)
-		let amount = 1000000000;
-		let create_contract =
-			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
-		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
-		assert_eq!(
-			EVM::call(
-				Origin::signed(alice_account_id.clone()),
-				factory_contract_address,
-				create_contract,
-				amount,
-				1000000000,
+		let contract_address = result.value;
+		assert_eq!(ContractLastUsedAt::<Runtime>::get(&contract_address), Some(1));
+
+		// call contract: getValue()
+		System::set_block_number(50);
+		let call = || {
+			<Runtime as Config>::Runner::call(
+				alice(),
+				alice(),
+				contract_address,
+				from_hex("0x20965255").unwrap(),
+				0,
+				1000000,
 				0,
 				vec![],
-			),
-			Ok(PostDispatchInfo {
-				actual_weight: None,
-				pays_fee: Pays::Yes
-			})
-		);
-		System::assert_last_event(Event::EVM(crate::Event::ExecutedFailed {
-			from: alice(),
-			contract: factory_contract_address,
-			exit_reason: ExitReason::Error(ExitError::Other(
-				Into::<&str>::into(Error::<Runtime>::OutOfStorage).into(),
-			)),
-			output: vec![],
-			logs: vec![],
-			used_gas: 1000000000,
-			used_storage: 0,
-		}));
+				<Runtime as Config>::config(),
+			)
+			.unwrap()
+		};
+		let first_call = call();
+		assert_eq!(ContractLastUsedAt::<Runtime>::get(&contract_address), Some(50));
+		// the last used block is not charged as gas
+		assert_eq!(call().used_gas, first_call.used_gas);
 
-		// Factory.createContract(1)
-		let amount = 1000000000;
-		let create_contract =
-			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
+		// reverted calls also use the contract
+		System::set_block_number(60);
 		let result = <Runtime as Config>::Runner::call(
 			alice(),
 			alice(),
-			factory_contract_address,
-			create_contract,
-			amount,
-			1000000000,
-			1000000000,
+			contract_address,
+			from_hex("0x12345678").unwrap(),
+			0,
+			1000000,
+			0,
 			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
-		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(result.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(ContractLastUsedAt::<Runtime>::get(&contract_address), Some(60));
 
-		// code_size + array_update(2 items) + extra_size = 290, array_length is already set
-		let expected_used_storage = 62 + 2 * 64 + 100;
-		assert_eq!(expected_used_storage, 290);
-		assert_eq!(result.used_storage, expected_used_storage);
+		let alice_balance = balance(alice());
+		let reserved_amount = 287 * EVM::get_storage_deposit_per_byte();
+		assert_eq!(reserved_balance(contract_address), reserved_amount);
 
-		// Factory.createContract(2)
-		let amount = 1000000000;
-		let create_contract =
-			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000002").unwrap();
+		// AbandonedContractPeriod is 100, the scan starts every 100 blocks
+		System::set_block_number(99);
+		EVM::on_initialize(99);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+
+		System::set_block_number(100);
+		EVM::on_initialize(100);
 		assert_eq!(
-			EVM::call(
-				Origin::signed(alice_account_id),
-				factory_contract_address,
-				create_contract,
-				amount,
-				1000000000,
-				451,
-				vec![],
-			),
-			Ok(PostDispatchInfo {
-				actual_weight: None,
-				pays_fee: Pays::Yes
-			})
+			module_idle_scheduler::Tasks::<Runtime>::iter_values().collect::<Vec<_>>(),
+			vec![ScheduledTasks::EvmTask(EvmTask::ReclaimAbandoned { start_key: None })]
 		);
-		System::assert_last_event(Event::EVM(crate::Event::ExecutedFailed {
-			from: alice(),
-			contract: factory_contract_address,
-			exit_reason: ExitReason::Error(ExitError::Other(
-				Into::<&str>::into(Error::<Runtime>::OutOfStorage).into(),
-			)),
-			output: vec![],
-			logs: vec![],
-			used_gas: 1000000000,
-			used_storage: 0,
-		}));
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		assert_eq!(ContractLastUsedAt::<Runtime>::get(&contract_address), Some(60));
 
-		// Factory.createContract(2)
-		let amount = 1000000000;
-		let create_contract =
-			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000002").unwrap();
-		let result = <Runtime as Config>::Runner::call(
-			alice(),
-			alice(),
-			factory_contract_address,
-			create_contract,
-			amount,
-			1000000000,
-			452,
-			vec![],
-			<Runtime as Config>::config(),
-		)
-		.unwrap();
-		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		System::set_block_number(200);
+		EVM::on_initialize(200);
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		System::assert_has_event(Event::EVM(crate::Event::ContractReclaimed {
+			contract: contract_address,
+			maintainer: alice(),
+		}));
+		assert!(!ContractLastUsedAt::<Runtime>::contains_key(&contract_address));
+		assert!(!ContractStorageSizes::<Runtime>::contains_key(&contract_address));
 
-		// 2 * code_size + array_update(2 items) + extra_size = 452, array_length is already set
-		let expected_used_storage = 2 * 62 + 2 * 64 + 2 * 100;
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
 
-		assert_eq!(expected_used_storage, 452);
-		assert_eq!(result.used_storage, expected_used_storage);
+		// refund storage deposit and the balance to the maintainer
+		assert_eq!(balance(alice()), alice_balance + amount + reserved_amount);
+		assert_eq!(balance(contract_address), 0);
+		assert_eq!(reserved_balance(contract_address), 0);
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
 	});
 }
 
 #[test]
-fn evm_execute_mode_should_work() {
+fn code_upgrade_works() {
 	// pragma solidity ^0.5.0;
-
-	// contract Factory {
-	// 	Contract[] newContracts;
-
-	// 	function createContract (uint num) public payable {
-	// 		for(uint i = 0; i < num; i++) {
-	// 			Contract newContract = new Contract();
-	// 			newContracts.push(newContract);
-	// 		}
-	// 	}
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
 	// }
-
-	// contract Contract {}
-	let contract = from_hex(
-		"0x608060405234801561001057600080fd5b506101a0806100206000396000f3fe60806040526004361061001e5760003560e01c80639db8d7d514610023575b600080fd5b61004f6004803603602081101561003957600080fd5b8101908080359060200190929190505050610051565b005b60008090505b8181101561010057600060405161006d90610104565b604051809103906000f080158015610089573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff16021790555050508080600101915050610057565b5050565b605b806101118339019056fe6080604052348015600f57600080fd5b50603e80601d6000396000f3fe6080604052600080fdfea265627a7a7231582035666e9471716d6d05ed9f0c1ab13d0371f49d536270f905bff06cd98212dcb064736f6c63430005110032a265627a7a723158203b6aaf6588bc3e6a35986612a62f715255430eab09ffb24401e5f18eb58a05d564736f6c63430005110032"
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
 	).unwrap();
+	// PUSH1 0x2a PUSH1 0x00 SSTORE STOP
+	let new_code = from_hex("0x602a60005500").unwrap();
 
 	new_test_ext().execute_with(|| {
-		let mut alice_balance = INITIAL_BALANCE - 516 * EVM::get_storage_deposit_per_byte();
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Runtime as Config>::AddressMapping::get_account_id(&bob());
+
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
 
 		let result = <Runtime as Config>::Runner::create(
 			alice(),
-			contract.clone(),
+			contract,
 			0,
-			1000000000,
-			1000000000,
+			1000000,
+			100000,
 			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
-		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let contract_address = result.value;
 
-		let account = Accounts::<Runtime>::get(&result.value).unwrap();
-		let code_info = CodeInfos::<Runtime>::get(account.contract_info.unwrap().code_hash).unwrap();
+		let old_code_hash = H256::from_str("21fe816097a50d298f819bc6d40cff473c43c87d99bcd7d3c3b2b85417f66f5a").unwrap();
+		let old_code_size = 123u32;
+		let new_code_hash = code_hash(&new_code);
+		let new_code_size = new_code.len() as u32;
 
-		// code_size + extra_size = 516
-		let expected_used_storage = 416 + 100;
-		assert_eq!(code_info.code_size, 416);
-		assert_eq!(result.used_storage, expected_used_storage);
-		assert_eq!(balance(alice()), alice_balance);
-		let factory_contract_address = result.value;
+		assert_noop!(
+			EVM::propose_code_upgrade(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				new_code_hash,
+				vec![]
+			),
+			Error::<Runtime>::CodeNotFound
+		);
 
-		#[cfg(not(feature = "with-ethereum-compatibility"))]
-		publish_free(factory_contract_address);
+		// deploy the new code
+		EVM::create_contract(alice(), H160::repeat_byte(0x11), true, new_code);
+		assert_eq!(CodeInfos::<Runtime>::get(&new_code_hash).unwrap().ref_count, 1);
 
-		let context = InvokeContext {
-			contract: factory_contract_address,
-			sender: alice(),
-			origin: alice(),
-		};
+		assert_noop!(
+			EVM::propose_code_upgrade(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				new_code_hash,
+				vec![]
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			EVM::propose_code_upgrade(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				new_code_hash,
+				vec![0; MaxMigrationSize::get() as usize + 1]
+			),
+			Error::<Runtime>::MigrationExceedsMaxSize
+		);
 
-		// ExecutionMode::EstimateGas
-		// Factory.createContract(1)
-		let create_contract =
-			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
-		let result = EVM::execute(
-			context,
+		// propose and cancel
+		assert_ok!(EVM::propose_code_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			new_code_hash,
+			vec![]
+		));
+		System::assert_last_event(Event::EVM(crate::Event::CodeUpgradeProposed {
+			contract: contract_address,
+			code_hash: new_code_hash,
+			enact_at: 11,
+		}));
+		assert_eq!(CodeInfos::<Runtime>::get(&new_code_hash).unwrap().ref_count, 2);
+		assert_noop!(
+			EVM::propose_code_upgrade(Origin::root(), contract_address, new_code_hash, vec![]),
+			Error::<Runtime>::CodeUpgradeAlreadyPending
+		);
+		assert_noop!(
+			EVM::cancel_code_upgrade(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(EVM::cancel_code_upgrade(
+			Origin::signed(alice_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::CodeUpgradeCancelled {
+			contract: contract_address,
+			code_hash: new_code_hash,
+		}));
+		assert_eq!(CodeInfos::<Runtime>::get(&new_code_hash).unwrap().ref_count, 1);
+		assert_noop!(
+			EVM::enact_code_upgrade(Origin::signed(bob_account_id.clone()), contract_address, 1000000, 0),
+			Error::<Runtime>::CodeUpgradeNotFound
+		);
+
+		// propose with migration and enact
+		assert_ok!(EVM::propose_code_upgrade(
+			Origin::root(),
+			contract_address,
+			new_code_hash,
+			vec![0x01]
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			EVM::enact_code_upgrade(Origin::signed(bob_account_id.clone()), contract_address, 1000000, 0),
+			Error::<Runtime>::CodeUpgradeNotReady
+		);
+
+		let alice_balance = balance(alice());
+		let reserved_amount = reserved_balance(contract_address);
+		let storage_size = ContractStorageSizes::<Runtime>::get(&contract_address);
+
+		System::set_block_number(11);
+		assert_ok!(EVM::enact_code_upgrade(
+			Origin::signed(bob_account_id),
+			contract_address,
+			1000000,
+			0
+		));
+		System::assert_last_event(Event::EVM(crate::Event::CodeUpgradeEnacted {
+			contract: contract_address,
+			code_hash: new_code_hash,
+		}));
+
+		assert!(!PendingCodeUpgrades::<Runtime>::contains_key(&contract_address));
+		assert_eq!(
+			Accounts::<Runtime>::get(&contract_address)
+				.unwrap()
+				.contract_info
+				.unwrap()
+				.code_hash,
+			new_code_hash
+		);
+		assert!(!CodeInfos::<Runtime>::contains_key(&old_code_hash));
+		assert!(!Codes::<Runtime>::contains_key(&old_code_hash));
+		assert_eq!(CodeInfos::<Runtime>::get(&new_code_hash).unwrap().ref_count, 2);
+
+		// storage deposit of the code is refunded to the maintainer
+		let refunded = (old_code_size - new_code_size) as u128 * EVM::get_storage_deposit_per_byte();
+		assert_eq!(
+			ContractStorageSizes::<Runtime>::get(&contract_address),
+			storage_size - (old_code_size - new_code_size)
+		);
+		assert_eq!(reserved_balance(contract_address), reserved_amount - refunded);
+		assert_eq!(balance(alice()), alice_balance + refunded);
+
+		// migration is executed
+		assert_eq!(
+			AccountStorages::<Runtime>::get(&contract_address, H256::zero()),
+			H256::from_low_u64_be(0x2a)
+		);
+	});
+}
+
+#[test]
+fn shared_code_deposit_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+	let mut stored_value: Vec<u8> =
+		from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+	contract.append(&mut stored_value);
+
+	new_test_ext().execute_with(|| {
+		let per_byte = EVM::get_storage_deposit_per_byte();
+		let treasury_reserved = Balances::reserved_balance(&TreasuryAccount::get());
+		let create = |contract: Vec<u8>| {
+			<Runtime as Config>::Runner::create(
+				alice(),
+				contract,
+				0,
+				1000000,
+				100000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+			.unwrap()
+		};
+
+		let result = create(contract.clone());
+		let contract_1 = result.value;
+		let code_hash = Accounts::<Runtime>::get(&contract_1)
+			.unwrap()
+			.contract_info
+			.unwrap()
+			.code_hash;
+		let code_size = CodeInfos::<Runtime>::get(&code_hash).unwrap().code_size;
+		assert_eq!(code_size, 123);
+		assert_eq!(result.used_storage, code_size + 64 + NEW_CONTRACT_EXTRA_BYTES);
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: code_size,
+				parked: 0,
+				refs: 1,
+				rebalancing: false,
+			})
+		);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+
+		// every contract pays the code in full, the surplus is refunded later
+		let result = create(contract.clone());
+		let contract_2 = result.value;
+		assert_eq!(result.used_storage, code_size + 64 + NEW_CONTRACT_EXTRA_BYTES);
+		assert_eq!(
+			ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_2),
+			Some(code_size)
+		);
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: 2 * code_size,
+				parked: 0,
+				refs: 2,
+				rebalancing: true,
+			})
+		);
+		assert_eq!(
+			module_idle_scheduler::Tasks::<Runtime>::iter_values().collect::<Vec<_>>(),
+			vec![ScheduledTasks::EvmTask(EvmTask::RebalanceCodeDeposit {
+				code_hash,
+				start_key: None
+			})]
+		);
+
+		// the contracts are refunded down to their even share of the code
+		let alice_balance = balance(alice());
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		assert_eq!(balance(alice()), alice_balance + code_size as u128 * per_byte);
+		let mut deposits = [contract_1, contract_2]
+			.map(|contract| ContractCodeDeposits::<Runtime>::get(&code_hash, &contract).unwrap());
+		deposits.sort();
+		assert_eq!(deposits, [code_size / 2, code_size - code_size / 2]);
+		for contract in [contract_1, contract_2] {
+			let deposit = ContractCodeDeposits::<Runtime>::get(&code_hash, &contract).unwrap();
+			System::assert_has_event(Event::EVM(crate::Event::CodeDepositRebalanced {
+				contract,
+				amount: (code_size - deposit) as u128 * per_byte,
+			}));
+			assert_eq!(
+				reserved_balance(contract),
+				(deposit + 64 + NEW_CONTRACT_EXTRA_BYTES) as u128 * per_byte
+			);
+			assert_eq!(
+				ContractStorageSizes::<Runtime>::get(&contract),
+				deposit + 64 + NEW_CONTRACT_EXTRA_BYTES
+			);
+		}
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: code_size,
+				parked: 0,
+				refs: 2,
+				rebalancing: false,
+			})
+		);
+
+		// the code is still used, the deposit which can't be refunded is parked for the caller
+		let parked = ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_1).unwrap();
+		assert_ok!(EVM::remove_contract(&alice(), &contract_1));
+		assert_eq!(ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_1), None);
+		assert_eq!(ParkedCodeDeposits::<Runtime>::get(&code_hash, alice()), parked);
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: code_size,
+				parked,
+				refs: 1,
+				rebalancing: false,
+			})
+		);
+		assert_eq!(
+			reserved_balance(contract_1),
+			(64 + NEW_CONTRACT_EXTRA_BYTES) as u128 * per_byte
+		);
+		assert_eq!(
+			Balances::reserved_balance(&TreasuryAccount::get()),
+			treasury_reserved + parked as u128 * per_byte
+		);
+		// clear the storages of the removed contract
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+
+		// a new contract covers the code, the parked deposit is refunded first
+		let contract_3 = create(contract).value;
+		let alice_balance = balance(alice());
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		System::assert_has_event(Event::EVM(crate::Event::CodeDepositRefunded {
+			code_hash,
+			depositor: alice(),
+			amount: parked as u128 * per_byte,
+		}));
+		assert_eq!(balance(alice()), alice_balance + code_size as u128 * per_byte);
+		assert!(!ParkedCodeDeposits::<Runtime>::contains_key(&code_hash, alice()));
+		assert_eq!(Balances::reserved_balance(&TreasuryAccount::get()), treasury_reserved);
+		assert_eq!(
+			ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_2).unwrap()
+				+ ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_3).unwrap(),
+			code_size
+		);
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: code_size,
+				parked: 0,
+				refs: 2,
+				rebalancing: false,
+			})
+		);
+
+		// the last contract is refunded its own deposit, the parked deposit is refunded after
+		// the code is removed
+		let parked = ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_2).unwrap();
+		let deposit = ContractCodeDeposits::<Runtime>::get(&code_hash, &contract_3).unwrap();
+		assert_ok!(EVM::remove_contract(&alice(), &contract_2));
+		assert_ok!(EVM::remove_contract(&alice(), &contract_3));
+		assert!(!CodeInfos::<Runtime>::contains_key(&code_hash));
+		assert_eq!(
+			reserved_balance(contract_3),
+			(deposit + 64 + NEW_CONTRACT_EXTRA_BYTES) as u128 * per_byte
+		);
+		assert_eq!(
+			CodeDeposits::<Runtime>::get(&code_hash),
+			Some(CodeDepositInfo {
+				total: parked,
+				parked,
+				refs: 0,
+				rebalancing: true,
+			})
+		);
+
+		IdleScheduler::on_idle(0, 1_000_000_000_000);
+		System::assert_has_event(Event::EVM(crate::Event::CodeDepositRefunded {
+			code_hash,
+			depositor: alice(),
+			amount: parked as u128 * per_byte,
+		}));
+		assert!(!CodeDeposits::<Runtime>::contains_key(&code_hash));
+		assert!(!ParkedCodeDeposits::<Runtime>::contains_key(&code_hash, alice()));
+		assert_eq!(Balances::reserved_balance(&TreasuryAccount::get()), treasury_reserved);
+	});
+}
+
+#[test]
+fn next_base_fee_per_gas_works() {
+	// gas used equals to the target
+	assert_eq!(next_base_fee_per_gas(1_000_000, 100, 100, 0), 1_000_000);
+	// full block
+	assert_eq!(next_base_fee_per_gas(1_000_000, 200, 100, 0), 1_125_000);
+	// empty block
+	assert_eq!(next_base_fee_per_gas(1_000_000, 0, 100, 0), 875_000);
+	assert_eq!(next_base_fee_per_gas(1_000_000, 0, 100, 900_000), 900_000);
+	assert_eq!(next_base_fee_per_gas(1_000_000, 200, 0, 0), 1_000_000);
+	assert_eq!(next_base_fee_per_gas(0, 200, 100, 0), 0);
+}
+
+#[test]
+fn base_fee_works() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		let alice_balance = balance(alice());
+
+		BaseFeePerGas::<Runtime>::put(10);
+		ExtrinsicOrigin::<Runtime>::put(alice_account_id);
+		System::set_extrinsic_index(1);
+
+		let info = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			bob(),
+			vec![],
+			0,
+			1000000,
+			0,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert!(info.exit_reason.is_succeed());
+
+		let used_gas = info.used_gas.as_u64();
+		assert_eq!(EVM::block_gas_used(), used_gas);
+		// the base fee is not charged on top of the transaction fee
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(EVM::extrinsic_gas_used(), Some((1, used_gas)));
+
+		// the base fee is burned out of the transaction fee, the rest goes to the fee handler
+		struct DepositToBob;
+		impl OnUnbalanced<NegativeImbalanceOf<Runtime>> for DepositToBob {
+			fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Runtime>) {
+				Balances::resolve_creating(&<Runtime as Config>::AddressMapping::get_account_id(&bob()), amount);
+			}
+		}
+		let bob_balance = balance(bob());
+		let total_issuance = Balances::total_issuance();
+		let fee = 100 * used_gas as u128;
+		BurnBaseFee::<Runtime, DepositToBob>::on_unbalanced(Balances::issue(fee));
+		assert_eq!(balance(bob()), bob_balance + fee - 10 * used_gas as u128);
+		assert_eq!(Balances::total_issuance(), total_issuance + fee - 10 * used_gas as u128);
+		assert_eq!(EVM::extrinsic_gas_used(), None);
+
+		// the burned base fee is capped at the transaction fee
+		ExtrinsicGasUsed::<Runtime>::put((1, 100 * used_gas));
+		BurnBaseFee::<Runtime, DepositToBob>::on_unbalanced(Balances::issue(fee / 2));
+		assert_eq!(balance(bob()), bob_balance + fee - 10 * used_gas as u128);
+		assert_eq!(Balances::total_issuance(), total_issuance + fee - 10 * used_gas as u128);
+
+		// the gas used by another extrinsic is not burned out of the transaction fee
+		ExtrinsicGasUsed::<Runtime>::put((0, used_gas));
+		BurnBaseFee::<Runtime, DepositToBob>::on_unbalanced(Balances::issue(fee));
+		assert_eq!(balance(bob()), bob_balance + 2 * fee - 10 * used_gas as u128);
+		assert_eq!(EVM::extrinsic_gas_used(), None);
+
+		// executions without an extrinsic origin don't pay the base fee
+		ExtrinsicOrigin::<Runtime>::kill();
+		assert_ok!(<Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			bob(),
+			vec![],
+			0,
+			1000000,
+			0,
+			vec![],
+			<Runtime as Config>::config(),
+		));
+		assert_eq!(EVM::block_gas_used(), 2 * used_gas);
+		assert_eq!(EVM::extrinsic_gas_used(), None);
+
+		// the block is below the gas target
+		EVM::on_finalize(1);
+		assert_eq!(EVM::base_fee_per_gas(), 9);
+		assert_eq!(EVM::block_gas_used(), 0);
+	});
+}
+
+#[test]
+fn eth_call_requires_gas_price_above_base_fee() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+
+		// TxFeePerGas is 20_000_000 in the EVM decimals
+		BaseFeePerGas::<Runtime>::put(21);
+		assert!(!EVM::is_base_fee_covered(<Runtime as Config>::TxFeePerGas::get()));
+		assert_noop!(
+			EVM::eth_call(
+				Origin::signed(alice_account_id.clone()),
+				TransactionAction::Call(bob()),
+				vec![],
+				0,
+				1000000,
+				0,
+				vec![],
+				0
+			),
+			Error::<Runtime>::GasPriceTooLow
+		);
+
+		BaseFeePerGas::<Runtime>::put(20);
+		assert!(EVM::is_base_fee_covered(<Runtime as Config>::TxFeePerGas::get()));
+		assert_ok!(EVM::eth_call(
+			Origin::signed(alice_account_id),
+			TransactionAction::Call(bob()),
+			vec![],
+			0,
+			1000000,
+			0,
+			vec![],
+			0
+		));
+	});
+}
+
+#[test]
+fn storage_limit_should_work() {
+	// pragma solidity ^0.5.0;
+
+	// contract Factory {
+	// 	Contract[] newContracts;
+
+	// 	function createContract (uint num) public payable {
+	// 		for(uint i = 0; i < num; i++) {
+	// 			Contract newContract = new Contract();
+	// 			newContracts.push(newContract);
+	// 		}
+	// 	}
+	// }
+
+	// contract Contract {}
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b506101a0806100206000396000f3fe60806040526004361061001e5760003560e01c80639db8d7d514610023575b600080fd5b61004f6004803603602081101561003957600080fd5b8101908080359060200190929190505050610051565b005b60008090505b8181101561010057600060405161006d90610104565b604051809103906000f080158015610089573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff16021790555050508080600101915050610057565b5050565b605b806101118339019056fe6080604052348015600f57600080fd5b50603e80601d6000396000f3fe6080604052600080fdfea265627a7a7231582035666e9471716d6d05ed9f0c1ab13d0371f49d536270f905bff06cd98212dcb064736f6c63430005110032a265627a7a723158203b6aaf6588bc3e6a35986612a62f715255430eab09ffb24401e5f18eb58a05d564736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			200_000,
+			1000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(result.used_storage, 516);
+		let alice_balance = INITIAL_BALANCE - 516 * EVM::get_storage_deposit_per_byte();
+		assert_eq!(balance(alice()), alice_balance);
+
+		let factory_contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(factory_contract_address);
+
+		assert_eq!(balance(factory_contract_address), 0);
+		assert_eq!(
+			reserved_balance(factory_contract_address),
+			516 * EVM::get_storage_deposit_per_byte()
+		);
+
+		// Factory.createContract(1)
+		let amount = 1000000000;
+		let create_contract =
+			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
+		let alice_account_id = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		assert_eq!(
+			EVM::call(
+				Origin::signed(alice_account_id.clone()),
+				factory_contract_address,
+				create_contract,
+				amount,
+				1000000000,
+				0,
+				vec![],
+			),
+			Ok(PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: Pays::Yes
+			})
+		);
+		System::assert_last_event(Event::EVM(crate::Event::ExecutedFailed {
+			from: alice(),
+			contract: factory_contract_address,
+			exit_reason: ExitReason::Error(ExitError::Other(
+				Into::<&str>::into(Error::<Runtime>::OutOfStorage).into(),
+			)),
+			output: vec![],
+			logs: vec![],
+			used_gas: 1000000000,
+			used_storage: 0,
+		}));
+
+		// Factory.createContract(1)
+		let amount = 1000000000;
+		let create_contract =
+			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			factory_contract_address,
+			create_contract,
+			amount,
+			1000000000,
+			1000000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+		// code_size + array_update(2 items) + extra_size = 290, array_length is already set
+		let expected_used_storage = 62 + 2 * 64 + 100;
+		assert_eq!(expected_used_storage, 290);
+		assert_eq!(result.used_storage, expected_used_storage);
+
+		// Factory.createContract(2)
+		let amount = 1000000000;
+		let create_contract =
+			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000002").unwrap();
+		assert_eq!(
+			EVM::call(
+				Origin::signed(alice_account_id),
+				factory_contract_address,
+				create_contract,
+				amount,
+				1000000000,
+				451,
+				vec![],
+			),
+			Ok(PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: Pays::Yes
+			})
+		);
+		System::assert_last_event(Event::EVM(crate::Event::ExecutedFailed {
+			from: alice(),
+			contract: factory_contract_address,
+			exit_reason: ExitReason::Error(ExitError::Other(
+				Into::<&str>::into(Error::<Runtime>::OutOfStorage).into(),
+			)),
+			output: vec![],
+			logs: vec![],
+			used_gas: 1000000000,
+			used_storage: 0,
+		}));
+
+		// Factory.createContract(2)
+		let amount = 1000000000;
+		let create_contract =
+			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000002").unwrap();
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			factory_contract_address,
+			create_contract,
+			amount,
+			1000000000,
+			452,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+		// 2 * code_size + array_update(2 items) + extra_size = 452, array_length is already set
+		let expected_used_storage = 2 * 62 + 2 * 64 + 2 * 100;
+
+		assert_eq!(expected_used_storage, 452);
+		assert_eq!(result.used_storage, expected_used_storage);
+	});
+}
+
+#[test]
+fn evm_execute_mode_should_work() {
+	// pragma solidity ^0.5.0;
+
+	// contract Factory {
+	// 	Contract[] newContracts;
+
+	// 	function createContract (uint num) public payable {
+	// 		for(uint i = 0; i < num; i++) {
+	// 			Contract newContract = new Contract();
+	// 			newContracts.push(newContract);
+	// 		}
+	// 	}
+	// }
+
+	// contract Contract {}
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b506101a0806100206000396000f3fe60806040526004361061001e5760003560e01c80639db8d7d514610023575b600080fd5b61004f6004803603602081101561003957600080fd5b8101908080359060200190929190505050610051565b005b60008090505b8181101561010057600060405161006d90610104565b604051809103906000f080158015610089573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff16021790555050508080600101915050610057565b5050565b605b806101118339019056fe6080604052348015600f57600080fd5b50603e80601d6000396000f3fe6080604052600080fdfea265627a7a7231582035666e9471716d6d05ed9f0c1ab13d0371f49d536270f905bff06cd98212dcb064736f6c63430005110032a265627a7a723158203b6aaf6588bc3e6a35986612a62f715255430eab09ffb24401e5f18eb58a05d564736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let mut alice_balance = INITIAL_BALANCE - 516 * EVM::get_storage_deposit_per_byte();
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			1000000000,
+			1000000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+
+		let account = Accounts::<Runtime>::get(&result.value).unwrap();
+		let code_info = CodeInfos::<Runtime>::get(account.contract_info.unwrap().code_hash).unwrap();
+
+		// code_size + extra_size = 516
+		let expected_used_storage = 416 + 100;
+		assert_eq!(code_info.code_size, 416);
+		assert_eq!(result.used_storage, expected_used_storage);
+		assert_eq!(balance(alice()), alice_balance);
+		let factory_contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(factory_contract_address);
+
+		let context = InvokeContext {
+			contract: factory_contract_address,
+			sender: alice(),
+			origin: alice(),
+		};
+
+		// ExecutionMode::EstimateGas
+		// Factory.createContract(1)
+		let create_contract =
+			from_hex("0x9db8d7d50000000000000000000000000000000000000000000000000000000000000001").unwrap();
+		let result = EVM::execute(
+			context,
 			create_contract,
 			Default::default(),
 			2_100_000,
@@ -2637,3 +3264,415 @@ fn aggregated_storage_logs_works() {
 		}));
 	})
 }
+
+#[test]
+fn call_tracer_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Factory {
+	//     Contract[] newContracts;
+	//
+	//     function createContract () public payable {
+	//         Contract newContract = new Contract();
+	//         newContracts.push(newContract);
+	//     }
+	// }
+	//
+	// contract Contract {}
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5061016f806100206000396000f3fe608060405260043610610041576000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff168063412a5a6d14610046575b600080fd5b61004e610050565b005b600061005a6100e2565b604051809103906000f080158015610076573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055505050565b6040516052806100f28339019056fe6080604052348015600f57600080fd5b50603580601d6000396000f3fe6080604052600080fdfea165627a7a7230582092dc1966a8880ddf11e067f9dd56a632c11a78a4afd4a9f05924d427367958cc0029a165627a7a723058202b2cc7384e11c452cdbf39b68dada2d5e10a632cc0174a354b8b8c83237e28a40029"
+	).unwrap();
+	new_test_ext().execute_with(|| {
+		let (result, outcome) = runner::tracing::trace(TracerConfig::CallTracer, || {
+			<Runtime as Config>::Runner::create(
+				alice(),
+				contract.clone(),
+				0,
+				1000000000,
+				1000000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		let result = result.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let factory_contract_address = result.value;
+
+		let calls = match outcome {
+			TraceOutcome::Calls(calls) => calls,
+			_ => panic!("unexpected trace outcome"),
+		};
+		assert_eq!(calls.len(), 1);
+		assert_eq!(calls[0].call_type, CallType::Create);
+		assert_eq!(calls[0].from, alice());
+		assert_eq!(calls[0].to, factory_contract_address);
+		assert_eq!(calls[0].exit_reason, Some(ExitReason::Succeed(ExitSucceed::Returned)));
+		assert_eq!(calls[0].used_storage, 467);
+		assert!(calls[0].calls.is_empty());
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(factory_contract_address);
+
+		// Factory.createContract
+		let (result, outcome) = runner::tracing::trace(TracerConfig::CallTracer, || {
+			<Runtime as Config>::Runner::call(
+				alice(),
+				alice(),
+				factory_contract_address,
+				from_hex("0x412a5a6d").unwrap(),
+				0,
+				1000000000,
+				1000000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		assert_eq!(result.unwrap().exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+		let calls = match outcome {
+			TraceOutcome::Calls(calls) => calls,
+			_ => panic!("unexpected trace outcome"),
+		};
+		assert_eq!(calls.len(), 1);
+		assert_eq!(calls[0].call_type, CallType::Call);
+		assert_eq!(calls[0].from, alice());
+		assert_eq!(calls[0].to, factory_contract_address);
+		assert_eq!(calls[0].input, from_hex("0x412a5a6d").unwrap());
+		assert_eq!(calls[0].depth, 0);
+		assert_eq!(calls[0].exit_reason, Some(ExitReason::Succeed(ExitSucceed::Stopped)));
+		assert_eq!(calls[0].used_storage, 281);
+
+		assert_eq!(calls[0].calls.len(), 1);
+		let child = &calls[0].calls[0];
+		assert_eq!(child.call_type, CallType::Create);
+		assert_eq!(child.from, factory_contract_address);
+		assert_eq!(
+			child.to,
+			H160::from_str("7b8f8ca099f6e33cf1817cf67d0556429cfc54e4").unwrap()
+		);
+		assert_eq!(child.depth, 1);
+		assert_eq!(child.exit_reason, Some(ExitReason::Succeed(ExitSucceed::Returned)));
+		assert!(child.gas_used > 0 && child.gas_used < calls[0].gas_used);
+	});
+}
+
+#[test]
+fn opcode_tracer_requires_tracing_feature() {
+	new_test_ext().execute_with(|| {
+		let result = runner::tracing::trace(
+			TracerConfig::OpcodeTracer {
+				disable_stack: false,
+				enable_memory: false,
+			},
+			|| (),
+		);
+		if cfg!(feature = "tracing") {
+			assert_eq!(result, Ok(((), TraceOutcome::Steps(vec![]))));
+		} else {
+			assert!(result.is_err());
+		}
+	});
+}
+
+#[test]
+fn apply_state_override_works() {
+	// PUSH1 0x00 SLOAD PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+	let code = from_hex("0x60005460005260206000f3").unwrap();
+	let contract = H160::from_low_u64_be(0x1234);
+
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			EVM::apply_state_override(vec![(
+				contract,
+				AccountOverride {
+					state: Some(vec![]),
+					state_diff: Some(vec![]),
+					..Default::default()
+				}
+			)]),
+			DispatchError::Other("Both state and stateDiff are overridden")
+		);
+
+		assert_ok!(EVM::apply_state_override(vec![
+			(
+				bob(),
+				AccountOverride {
+					// the remainder below the native decimals is rounded down
+					balance: Some(U256::from(convert_decimals_to_evm(1_000_000_000u128) + 999_999)),
+					nonce: Some(5),
+					..Default::default()
+				}
+			),
+			(
+				contract,
+				AccountOverride {
+					code: Some(code.clone()),
+					state: Some(vec![(H256::zero(), H256::from_low_u64_be(42))]),
+					..Default::default()
+				}
+			),
+		]));
+
+		assert_eq!(balance(bob()), 1_000_000_000);
+		assert_eq!(EVM::account_basic(&bob()).nonce, U256::from(5));
+		assert_eq!(EVM::code_at_address(&contract).into_inner(), code);
+
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract,
+			vec![],
+			0,
+			1000000,
+			0,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(result.value, H256::from_low_u64_be(42).as_bytes().to_vec());
+
+		// patch the storage slot
+		assert_ok!(EVM::apply_state_override(vec![(
+			contract,
+			AccountOverride {
+				state_diff: Some(vec![(H256::zero(), H256::from_low_u64_be(7))]),
+				..Default::default()
+			}
+		)]));
+		assert_eq!(EVM::account_storages(contract, H256::zero()), H256::from_low_u64_be(7));
+
+		// replacing the code releases the old one
+		let new_code = from_hex("0x00").unwrap();
+		assert_eq!(CodeInfos::<Runtime>::get(code_hash(&code)).unwrap().ref_count, 1);
+		assert_ok!(EVM::apply_state_override(vec![(
+			contract,
+			AccountOverride {
+				code: Some(new_code.clone()),
+				..Default::default()
+			}
+		)]));
+		assert_eq!(CodeInfos::<Runtime>::get(code_hash(&code)), None);
+		assert_eq!(Codes::<Runtime>::get(code_hash(&code)), None);
+		assert_eq!(CodeInfos::<Runtime>::get(code_hash(&new_code)).unwrap().ref_count, 1);
+		assert_eq!(EVM::code_at_address(&contract).into_inner(), new_code);
+	});
+}
+
+#[test]
+fn create_access_list_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//     mapping(address => uint256) public values;
+	//
+	//     constructor() public {
+	//         values[msg.sender] = 42;
+	//     }
+	//
+	//     function set(uint val) public {
+	//      values[msg.sender] = val;
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b50602a6000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff16815260200190815260200160002081905550610154806100646000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fe9fd71461003b57806360fe47b114610093575b600080fd5b61007d6004803603602081101561005157600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff1690602001909291905050506100c1565b6040518082815260200191505060405180910390f35b6100bf600480360360208110156100a957600080fd5b81019080803590602001909291905050506100d9565b005b60006020528060005260406000206000915090505481565b806000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff168152602001908152602001600020819055505056fea265627a7a723158207ab6991e97c9c12f57d81df0c7f955435418354adeb26116b581d7f2f035ca8f64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			500000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// slot of `values[bob]`
+		let mut key = [0u8; 64];
+		key[12..32].copy_from_slice(bob().as_bytes());
+		let slot = H256::from(sp_io::hashing::keccak_256(&key));
+
+		// call method `set(123)`
+		let input = from_hex("0x60fe47b1000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		let info = <Runtime as Config>::Runner::rpc_create_access_list(
+			bob(),
+			Some(contract_address),
+			input,
+			0,
+			1000000,
+			1000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(
+			info.access_list,
+			vec![AccessListItem {
+				address: contract_address,
+				storage_keys: vec![slot],
+			}]
+		);
+
+		// the changes are reverted
+		assert_eq!(EVM::account_storages(contract_address, slot), H256::zero());
+		assert_eq!(EVM::account_basic(&bob()).nonce, U256::zero());
+	});
+}
+
+#[test]
+fn call_bundle_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//     mapping(address => uint256) public values;
+	//
+	//     constructor() public {
+	//         values[msg.sender] = 42;
+	//     }
+	//
+	//     function set(uint val) public {
+	//      values[msg.sender] = val;
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b50602a6000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff16815260200190815260200160002081905550610154806100646000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fe9fd71461003b57806360fe47b114610093575b600080fd5b61007d6004803603602081101561005157600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff1690602001909291905050506100c1565b6040518082815260200191505060405180910390f35b6100bf600480360360208110156100a957600080fd5b81019080803590602001909291905050506100d9565b005b60006020528060005260406000206000915090505481565b806000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff168152602001908152602001600020819055505056fea265627a7a723158207ab6991e97c9c12f57d81df0c7f955435418354adeb26116b581d7f2f035ca8f64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			500000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		let bundled_call = |value: Balance, data: &str| BundledCall {
+			from: bob(),
+			to: Some(contract_address),
+			data: from_hex(data).unwrap(),
+			value,
+			gas_limit: 1000000,
+			storage_limit: 1000,
+			access_list: None,
+		};
+
+		let results = <Runtime as Config>::Runner::rpc_call_bundle(
+			vec![
+				// set(123)
+				bundled_call(
+					0,
+					"0x60fe47b1000000000000000000000000000000000000000000000000000000000000007b",
+				),
+				// value with invalid decimals
+				bundled_call(
+					1,
+					"0x60fe47b1000000000000000000000000000000000000000000000000000000000000007c",
+				),
+				// values(bob)
+				bundled_call(
+					0,
+					"0x54fe9fd70000000000000000000000001000000000000000000000000000000000000002",
+				),
+			],
+			<Runtime as Config>::config(),
+		);
+
+		assert_eq!(results.len(), 3);
+		assert_eq!(
+			results[0].as_ref().unwrap().exit_reason,
+			ExitReason::Succeed(ExitSucceed::Stopped)
+		);
+		assert_eq!(results[1], Err(Error::<Runtime>::InvalidDecimals.into()));
+		let info = results[2].as_ref().unwrap();
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, H256::from_low_u64_be(123).as_bytes().to_vec());
+	});
+}
+
+#[test]
+fn storage_deposits_works() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Factory {
+	//     Contract[] newContracts;
+	//
+	//     function createContract () public payable {
+	//         Contract newContract = new Contract();
+	//         newContracts.push(newContract);
+	//     }
+	// }
+	//
+	// contract Contract {}
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5061016f806100206000396000f3fe608060405260043610610041576000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff168063412a5a6d14610046575b600080fd5b61004e610050565b005b600061005a6100e2565b604051809103906000f080158015610076573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055505050565b6040516052806100f28339019056fe6080604052348015600f57600080fd5b50603580601d6000396000f3fe6080604052600080fdfea165627a7a7230582092dc1966a8880ddf11e067f9dd56a632c11a78a4afd4a9f05924d427367958cc0029a165627a7a723058202b2cc7384e11c452cdbf39b68dada2d5e10a632cc0174a354b8b8c83237e28a40029"
+	).unwrap();
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000000,
+			1000000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let factory_contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(factory_contract_address);
+
+		// Factory.createContract
+		let deposits = <Runtime as Config>::Runner::rpc_storage_deposits(
+			alice(),
+			Some(factory_contract_address),
+			from_hex("0x412a5a6d").unwrap(),
+			0,
+			1000000000,
+			1000000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+
+		let contract_address = H160::from_str("7b8f8ca099f6e33cf1817cf67d0556429cfc54e4").unwrap();
+		let mut expected = vec![
+			StorageDeposit {
+				contract: factory_contract_address,
+				maintainer: Some(alice()),
+				charged: 128,
+				refunded: 0,
+			},
+			StorageDeposit {
+				contract: contract_address,
+				maintainer: Some(factory_contract_address),
+				charged: 153,
+				refunded: 0,
+			},
+		];
+		expected.sort_by_key(|deposit| deposit.contract);
+		assert_eq!(deposits, expected);
+
+		// the changes are reverted
+		assert!(!EVM::is_contract(&contract_address));
+	});
+}
@@ -29,7 +29,7 @@ pub use frame_system::{EnsureRoot, EnsureSignedBy, RawOrigin};
 pub use module_relaychain::RelayChainCallBuilder;
 pub use module_support::mocks::MockAddressMapping;
 pub use orml_traits::{parameter_type_with_key, XcmTransfer};
-pub use primitives::{Amount, TokenSymbol};
+pub use primitives::{Amount, ReserveIdentifier, TokenSymbol};
 pub use sp_core::{H160, H256};
 pub use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
 
@@ -237,8 +237,8 @@ impl pallet_balances::Config for Runtime {
 	type AccountStore = frame_system::Pallet<Runtime>;
 	type MaxLocks = ();
 	type WeightInfo = ();
-	type MaxReserves = ();
-	type ReserveIdentifier = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = ReserveIdentifier;
 }
 
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Runtime, PalletBalances, Amount, BlockNumber>;
@@ -260,6 +260,8 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<Root, AccountId>;
 	type OnDust = ();
+	type DataDepositPerByte = ConstU128<0>;
+	type DepositCurrency = PalletBalances;
 }
 
 parameter_types! {
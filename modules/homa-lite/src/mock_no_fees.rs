@@ -185,8 +185,8 @@ impl pallet_balances::Config for NoFeeRuntime {
 	type AccountStore = frame_system::Pallet<NoFeeRuntime>;
 	type MaxLocks = ();
 	type WeightInfo = ();
-	type MaxReserves = ();
-	type ReserveIdentifier = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = ReserveIdentifier;
 }
 
 pub type AdaptedBasicCurrency =
@@ -209,6 +209,8 @@ impl module_currencies::Config for NoFeeRuntime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<Root, AccountId>;
 	type OnDust = ();
+	type DataDepositPerByte = ConstU128<0>;
+	type DepositCurrency = PalletBalances;
 }
 
 parameter_types! {
@@ -189,10 +189,7 @@ pub mod module {
 		) -> DispatchResult {
 			let to = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			Self::check_authorization(&from, &to, currency_id)?;
-			<loans::Pallet<T>>::transfer_loan(&from, &to, currency_id)?;
-			Ok(())
+			Self::do_transfer_loan_from(&to, currency_id, &from)
 		}
 
 		/// Authorize `to` to manipulate the loan under `currency_id`
@@ -208,24 +205,7 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			if from == to {
-				return Ok(());
-			}
-
-			Authorization::<T>::try_mutate_exists(&from, (currency_id, &to), |maybe_reserved| -> DispatchResult {
-				ensure!(maybe_reserved.is_none(), Error::<T>::AlreadyAuthorized);
-
-				let reserve_amount = T::DepositPerAuthorization::get();
-				<T as Config>::Currency::reserve_named(&RESERVE_ID, &from, reserve_amount)?;
-				*maybe_reserved = Some(reserve_amount);
-				Self::deposit_event(Event::Authorization {
-					authorizer: from.clone(),
-					authorizee: to.clone(),
-					collateral_type: currency_id,
-				});
-				Ok(())
-			})?;
-			Ok(())
+			Self::do_authorize(&from, currency_id, &to)
 		}
 
 		/// Cancel the authorization for `to` under `currency_id`
@@ -241,15 +221,7 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			let reserved =
-				Authorization::<T>::take(&from, (currency_id, &to)).ok_or(Error::<T>::AuthorizationNotExists)?;
-			<T as Config>::Currency::unreserve_named(&RESERVE_ID, &from, reserved);
-			Self::deposit_event(Event::UnAuthorization {
-				authorizer: from,
-				authorizee: to,
-				collateral_type: currency_id,
-			});
-			Ok(())
+			Self::do_unauthorize(&from, currency_id, &to)
 		}
 
 		/// Cancel all authorization of caller
@@ -354,22 +326,7 @@ pub mod module {
 			debit_transfer: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let debit_amount: Amount = debit_transfer.try_into().map_err(|_| ArithmeticError::Overflow)?;
-			let negative_debit = debit_amount.checked_neg().ok_or(ArithmeticError::Overflow)?;
-			// Adds ausd to user account momentarily to adjust loan
-			<T as cdp_engine::Config>::CDPTreasury::issue_debit(&who, debit_transfer, true)?;
-
-			<cdp_engine::Pallet<T>>::adjust_position(&who, from_currency, Zero::zero(), negative_debit)?;
-			<cdp_engine::Pallet<T>>::adjust_position(&who, to_currency, Zero::zero(), debit_amount)?;
-			// Removes debit issued for debit transfer
-			<T as cdp_engine::Config>::CDPTreasury::burn_debit(&who, debit_transfer)?;
-
-			Self::deposit_event(Event::TransferDebit {
-				from_currency,
-				to_currency,
-				amount: debit_transfer,
-			});
-			Ok(())
+			Self::do_transfer_debit(&who, from_currency, to_currency, debit_transfer)
 		}
 	}
 }
@@ -407,6 +364,68 @@ impl<T: Config> Pallet<T> {
 		<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
 		Ok(())
 	}
+
+	fn do_transfer_loan_from(to: &T::AccountId, currency_id: CurrencyId, from: &T::AccountId) -> DispatchResult {
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		Self::check_authorization(from, to, currency_id)?;
+		<loans::Pallet<T>>::transfer_loan(from, to, currency_id)?;
+		Ok(())
+	}
+
+	fn do_authorize(from: &T::AccountId, currency_id: CurrencyId, to: &T::AccountId) -> DispatchResult {
+		if from == to {
+			return Ok(());
+		}
+
+		Authorization::<T>::try_mutate_exists(from, (currency_id, to), |maybe_reserved| -> DispatchResult {
+			ensure!(maybe_reserved.is_none(), Error::<T>::AlreadyAuthorized);
+
+			let reserve_amount = T::DepositPerAuthorization::get();
+			<T as Config>::Currency::reserve_named(&RESERVE_ID, from, reserve_amount)?;
+			*maybe_reserved = Some(reserve_amount);
+			Self::deposit_event(Event::Authorization {
+				authorizer: from.clone(),
+				authorizee: to.clone(),
+				collateral_type: currency_id,
+			});
+			Ok(())
+		})
+	}
+
+	fn do_unauthorize(from: &T::AccountId, currency_id: CurrencyId, to: &T::AccountId) -> DispatchResult {
+		let reserved = Authorization::<T>::take(from, (currency_id, to)).ok_or(Error::<T>::AuthorizationNotExists)?;
+		<T as Config>::Currency::unreserve_named(&RESERVE_ID, from, reserved);
+		Self::deposit_event(Event::UnAuthorization {
+			authorizer: from.clone(),
+			authorizee: to.clone(),
+			collateral_type: currency_id,
+		});
+		Ok(())
+	}
+
+	fn do_transfer_debit(
+		who: &T::AccountId,
+		from_currency: CurrencyId,
+		to_currency: CurrencyId,
+		debit_transfer: Balance,
+	) -> DispatchResult {
+		let debit_amount: Amount = debit_transfer.try_into().map_err(|_| ArithmeticError::Overflow)?;
+		let negative_debit = debit_amount.checked_neg().ok_or(ArithmeticError::Overflow)?;
+		// Adds ausd to user account momentarily to adjust loan
+		<T as cdp_engine::Config>::CDPTreasury::issue_debit(who, debit_transfer, true)?;
+
+		<cdp_engine::Pallet<T>>::adjust_position(who, from_currency, Zero::zero(), negative_debit)?;
+		<cdp_engine::Pallet<T>>::adjust_position(who, to_currency, Zero::zero(), debit_amount)?;
+		// Removes debit issued for debit transfer
+		<T as cdp_engine::Config>::CDPTreasury::burn_debit(who, debit_transfer)?;
+
+		Self::deposit_event(Event::TransferDebit {
+			from_currency,
+			to_currency,
+			amount: debit_transfer,
+		});
+		Ok(())
+	}
 }
 
 impl<T: Config> HonzonManager<T::AccountId, CurrencyId, Amount, Balance> for Pallet<T> {
@@ -423,6 +442,52 @@ impl<T: Config> HonzonManager<T::AccountId, CurrencyId, Amount, Balance> for Pal
 		Self::do_close_loan_by_dex(who, currency_id, max_collateral_amount)
 	}
 
+	fn authorize(from: &T::AccountId, currency_id: CurrencyId, to: &T::AccountId) -> DispatchResult {
+		Self::do_authorize(from, currency_id, to)
+	}
+
+	fn unauthorize(from: &T::AccountId, currency_id: CurrencyId, to: &T::AccountId) -> DispatchResult {
+		Self::do_unauthorize(from, currency_id, to)
+	}
+
+	#[transactional]
+	fn transfer_loan_from(to: &T::AccountId, currency_id: CurrencyId, from: &T::AccountId) -> DispatchResult {
+		Self::do_transfer_loan_from(to, currency_id, from)
+	}
+
+	fn expand_position_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		increase_debit_value: Balance,
+		min_increase_collateral: Balance,
+	) -> DispatchResult {
+		<cdp_engine::Pallet<T>>::expand_position_collateral(
+			who,
+			currency_id,
+			increase_debit_value,
+			min_increase_collateral,
+		)
+	}
+
+	fn shrink_position_debit(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		decrease_collateral: Balance,
+		min_decrease_debit_value: Balance,
+	) -> DispatchResult {
+		<cdp_engine::Pallet<T>>::shrink_position_debit(who, currency_id, decrease_collateral, min_decrease_debit_value)
+	}
+
+	#[transactional]
+	fn transfer_debit(
+		who: &T::AccountId,
+		from_currency: CurrencyId,
+		to_currency: CurrencyId,
+		debit_transfer: Balance,
+	) -> DispatchResult {
+		Self::do_transfer_debit(who, from_currency, to_currency, debit_transfer)
+	}
+
 	fn get_position(who: &T::AccountId, currency_id: CurrencyId) -> Position {
 		<loans::Pallet<T>>::positions(currency_id, who)
 	}
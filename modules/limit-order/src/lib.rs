@@ -0,0 +1,497 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Limit Order Module
+//!
+//! ## Overview
+//!
+//! Resting limit orders executed against the DEX pools. Placing an order
+//! escrows the supply amount in the module account and records the limit
+//! price, the minimum target amount per unit of supply amount. The orders are
+//! checked in `on_idle` with the remaining block weight, in a round-robin manner
+//! over blocks: an order is swapped through its path by `DEXManager` whenever
+//! the pools can fill it at the limit price. If only part of the order can be
+//! filled at the limit price, the largest fillable part is executed and the
+//! rest stays in the book, unless it is below the minimum order amount, then
+//! it's refunded. Orders can be cancelled by their owner, expired orders are
+//! removed and refunded when checked. A deposit is reserved from the owner
+//! for every order in the book, and unreserved when the order is removed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, traits::NamedReservableCurrency, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::{GetByKey, MultiCurrency};
+use primitives::{Balance, CurrencyId, ReserveIdentifier};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Zero},
+	ArithmeticError, FixedPointNumber, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+use support::{DEXManager, Price, SwapLimit};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type OrderId = u64;
+
+/// The number of bisection steps to search the largest fillable part of an
+/// order, the precision of partial fills is 1/256 of the remaining amount.
+const PARTIAL_FILL_SEARCH_STEPS: u32 = 8;
+
+/// A resting limit order.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct LimitOrder<AccountId, BlockNumber> {
+	/// The owner of the order.
+	pub owner: AccountId,
+	/// The swap path, from the supply currency to the target currency.
+	pub path: Vec<CurrencyId>,
+	/// The escrowed supply amount that hasn't been filled.
+	pub supply_amount: Balance,
+	/// The minimum target amount per unit of supply amount.
+	pub limit_price: Price,
+	/// The order expires at this block number.
+	pub expiry: Option<BlockNumber>,
+	/// The deposit reserved from the owner.
+	pub deposit: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::LimitOrder;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for escrowing and transfer currencies
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// Currency for order deposits
+		type NativeCurrency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = Balance,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
+
+		/// Reserved amount per order.
+		#[pallet::constant]
+		type OrderDeposit: Get<Balance>;
+
+		/// DEX to execute the orders
+		type DEX: DEXManager<Self::AccountId, Balance, CurrencyId>;
+
+		/// The minimum supply amount of orders, also the minimum amount of partial
+		/// fills.
+		type MinimumOrderAmount: GetByKey<CurrencyId, Balance>;
+
+		/// The limit for length of trading path
+		#[pallet::constant]
+		type TradingPathLimit: Get<u32>;
+
+		/// The maximum number of orders checked in a block
+		#[pallet::constant]
+		type MaxOrdersCheckedPerBlock: Get<u32>;
+
+		/// The limit order module id, keep all escrowed assets in this module.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Trading path is invalid
+		InvalidTradingPath,
+		/// The length of trading path is invalid
+		InvalidTradingPathLength,
+		/// The limit price is zero
+		InvalidLimitPrice,
+		/// The expiry has passed
+		InvalidExpiry,
+		/// The supply amount is below the minimum order amount
+		BelowMinimumOrderAmount,
+		/// The order doesn't exist
+		OrderNotFound,
+		/// The caller isn't the owner of the order
+		NoPermission,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A limit order is placed.
+		OrderPlaced {
+			order_id: OrderId,
+			owner: T::AccountId,
+			path: Vec<CurrencyId>,
+			supply_amount: Balance,
+			limit_price: Price,
+			expiry: Option<T::BlockNumber>,
+		},
+		/// A limit order is filled in full or in part.
+		OrderExecuted {
+			order_id: OrderId,
+			owner: T::AccountId,
+			supply_amount: Balance,
+			target_amount: Balance,
+			remaining_supply_amount: Balance,
+		},
+		/// A limit order is cancelled by the owner.
+		OrderCancelled {
+			order_id: OrderId,
+			owner: T::AccountId,
+			refund_amount: Balance,
+		},
+		/// A limit order is expired.
+		OrderExpired {
+			order_id: OrderId,
+			owner: T::AccountId,
+			refund_amount: Balance,
+		},
+		/// The remaining supply amount of a limit order is below the minimum order
+		/// amount and is refunded.
+		OrderRemainderRefunded {
+			order_id: OrderId,
+			owner: T::AccountId,
+			refund_amount: Balance,
+		},
+	}
+
+	/// The limit orders.
+	///
+	/// Orders: map OrderId => Option<LimitOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub type Orders<T: Config> =
+		StorageMap<_, Twox64Concat, OrderId, LimitOrder<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The order id used to index orders.
+	///
+	/// NextOrderId: OrderId
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The last order checked, the next check starts after it.
+	///
+	/// LastCheckedOrderId: Option<OrderId>
+	#[pallet::storage]
+	#[pallet::getter(fn last_checked_order_id)]
+	pub type LastCheckedOrderId<T: Config> = StorageValue<_, OrderId, OptionQuery>;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let base_weight = <T as Config>::WeightInfo::check_orders(0);
+			if remaining_weight <= base_weight {
+				return 0;
+			}
+
+			// calculate max number of orders that can be checked with the remaining weight
+			let weight_per_order = <T as Config>::WeightInfo::check_orders(1).saturating_sub(base_weight);
+			let max_orders = remaining_weight
+				.saturating_sub(base_weight)
+				.checked_div(weight_per_order)
+				.unwrap_or(Weight::MAX)
+				.min(T::MaxOrdersCheckedPerBlock::get().into());
+			if max_orders.is_zero() {
+				return 0;
+			}
+
+			let checked = Self::check_orders(now, max_orders.saturated_into());
+			<T as Config>::WeightInfo::check_orders(checked)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place a limit order to swap the supply currency for the target currency
+		/// at or above the limit price, the supply amount is escrowed until the
+		/// order is filled, cancelled or expired.
+		///
+		/// - `path`: trading path, from the supply currency to the target currency.
+		/// - `supply_amount`: the supply amount.
+		/// - `limit_price`: the minimum target amount per unit of supply amount.
+		/// - `expiry`: the order expires at this block number, never expires if None.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		#[transactional]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] supply_amount: Balance,
+			limit_price: Price,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_place_order(&who, path, supply_amount, limit_price, expiry)?;
+			Ok(())
+		}
+
+		/// Cancel the limit order and refund the remaining supply amount.
+		///
+		/// - `order_id`: the order id.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		#[transactional]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_order(&who, order_id)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	fn do_place_order(
+		who: &T::AccountId,
+		path: Vec<CurrencyId>,
+		supply_amount: Balance,
+		limit_price: Price,
+		expiry: Option<T::BlockNumber>,
+	) -> Result<OrderId, DispatchError> {
+		let path_length = path.len();
+		ensure!(
+			path_length >= 2 && path_length <= T::TradingPathLimit::get().saturated_into(),
+			Error::<T>::InvalidTradingPathLength
+		);
+		ensure!(path.get(0) != path.get(path_length - 1), Error::<T>::InvalidTradingPath);
+		ensure!(!limit_price.is_zero(), Error::<T>::InvalidLimitPrice);
+		ensure!(
+			supply_amount >= T::MinimumOrderAmount::get(&path[0]),
+			Error::<T>::BelowMinimumOrderAmount
+		);
+		if let Some(expiry) = expiry {
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+		}
+
+		let order_id = NextOrderId::<T>::try_mutate(|current| -> Result<OrderId, DispatchError> {
+			let id = *current;
+			*current = current.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+			Ok(id)
+		})?;
+
+		T::Currency::transfer(path[0], who, &Self::account_id(), supply_amount)?;
+		let deposit = T::OrderDeposit::get();
+		T::NativeCurrency::reserve_named(&RESERVE_ID, who, deposit)?;
+		Orders::<T>::insert(
+			order_id,
+			LimitOrder {
+				owner: who.clone(),
+				path: path.clone(),
+				supply_amount,
+				limit_price,
+				expiry,
+				deposit,
+			},
+		);
+
+		Self::deposit_event(Event::OrderPlaced {
+			order_id,
+			owner: who.clone(),
+			path,
+			supply_amount,
+			limit_price,
+			expiry,
+		});
+		Ok(order_id)
+	}
+
+	fn do_cancel_order(who: &T::AccountId, order_id: OrderId) -> DispatchResult {
+		let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		ensure!(order.owner == *who, Error::<T>::NoPermission);
+
+		T::Currency::transfer(order.path[0], &Self::account_id(), who, order.supply_amount)?;
+		T::NativeCurrency::unreserve_named(&RESERVE_ID, who, order.deposit);
+		Orders::<T>::remove(order_id);
+
+		Self::deposit_event(Event::OrderCancelled {
+			order_id,
+			owner: who.clone(),
+			refund_amount: order.supply_amount,
+		});
+		Ok(())
+	}
+
+	/// The minimum target amount of swapping `supply_amount` for the order.
+	fn minimum_target_amount(order: &LimitOrder<T::AccountId, T::BlockNumber>, supply_amount: Balance) -> Balance {
+		order.limit_price.saturating_mul_int(supply_amount)
+	}
+
+	/// Get the largest supply amount of the order that can be filled at the
+	/// limit price.
+	fn get_fill_amount(order: &LimitOrder<T::AccountId, T::BlockNumber>) -> Option<Balance> {
+		let is_fillable = |supply_amount: Balance| -> bool {
+			T::DEX::get_swap_amount(
+				&order.path,
+				SwapLimit::ExactSupply(supply_amount, Self::minimum_target_amount(order, supply_amount)),
+			)
+			.is_some()
+		};
+
+		if is_fillable(order.supply_amount) {
+			return Some(order.supply_amount);
+		}
+
+		let minimum_amount = T::MinimumOrderAmount::get(&order.path[0]);
+		if minimum_amount >= order.supply_amount || !is_fillable(minimum_amount) {
+			return None;
+		}
+
+		// the average price of a swap gets worse as the supply amount grows, search the
+		// largest fillable amount in (minimum_amount, supply_amount).
+		let (mut low, mut high) = (minimum_amount, order.supply_amount);
+		for _ in 0..PARTIAL_FILL_SEARCH_STEPS {
+			let mid = low.saturating_add(high.saturating_sub(low) / 2);
+			if mid == low {
+				break;
+			}
+			if is_fillable(mid) {
+				low = mid;
+			} else {
+				high = mid;
+			}
+		}
+		Some(low)
+	}
+
+	/// Remove the order if it's expired, otherwise fill the order as much as
+	/// possible at the limit price. The remaining supply amount below the
+	/// minimum order amount can't be partially filled, refund it and remove the
+	/// order.
+	#[transactional]
+	fn check_order(order_id: OrderId, now: T::BlockNumber) -> DispatchResult {
+		Orders::<T>::try_mutate_exists(order_id, |maybe_order| -> DispatchResult {
+			let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
+			let module_account_id = Self::account_id();
+
+			if order.expiry.map_or(false, |expiry| now >= expiry) {
+				T::Currency::transfer(order.path[0], &module_account_id, &order.owner, order.supply_amount)?;
+				T::NativeCurrency::unreserve_named(&RESERVE_ID, &order.owner, order.deposit);
+				Self::deposit_event(Event::OrderExpired {
+					order_id,
+					owner: order.owner.clone(),
+					refund_amount: order.supply_amount,
+				});
+				*maybe_order = None;
+				return Ok(());
+			}
+
+			if let Some(fill_amount) = Self::get_fill_amount(order) {
+				let (supply_amount, target_amount) = T::DEX::swap_with_specific_path(
+					&module_account_id,
+					&order.path,
+					SwapLimit::ExactSupply(fill_amount, Self::minimum_target_amount(order, fill_amount)),
+				)?;
+				T::Currency::transfer(
+					order.path[order.path.len() - 1],
+					&module_account_id,
+					&order.owner,
+					target_amount,
+				)?;
+				order.supply_amount = order.supply_amount.saturating_sub(supply_amount);
+
+				Self::deposit_event(Event::OrderExecuted {
+					order_id,
+					owner: order.owner.clone(),
+					supply_amount,
+					target_amount,
+					remaining_supply_amount: order.supply_amount,
+				});
+			}
+
+			if order.supply_amount.is_zero() {
+				T::NativeCurrency::unreserve_named(&RESERVE_ID, &order.owner, order.deposit);
+				*maybe_order = None;
+			} else if order.supply_amount < T::MinimumOrderAmount::get(&order.path[0]) {
+				T::Currency::transfer(order.path[0], &module_account_id, &order.owner, order.supply_amount)?;
+				T::NativeCurrency::unreserve_named(&RESERVE_ID, &order.owner, order.deposit);
+				Self::deposit_event(Event::OrderRemainderRefunded {
+					order_id,
+					owner: order.owner.clone(),
+					refund_amount: order.supply_amount,
+				});
+				*maybe_order = None;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// Check at most `max_orders` orders, starting after the last checked order
+	/// and wrapping around. Returns the number of orders checked.
+	fn check_orders(now: T::BlockNumber, max_orders: u32) -> u32 {
+		let last_checked_order_id = Self::last_checked_order_id();
+		let max_orders = max_orders as usize;
+
+		let mut order_ids: Vec<OrderId> = match last_checked_order_id {
+			Some(order_id) => Orders::<T>::iter_keys_from(Orders::<T>::hashed_key_for(order_id))
+				.take(max_orders)
+				.collect(),
+			None => Orders::<T>::iter_keys().take(max_orders).collect(),
+		};
+		if last_checked_order_id.is_some() {
+			// wrap around, up to the last checked order, which may have been removed.
+			let first_order_id = order_ids.first().copied();
+			let mut wrapped_order_ids: Vec<OrderId> = vec![];
+			for order_id in Orders::<T>::iter_keys() {
+				if order_ids.len() + wrapped_order_ids.len() >= max_orders || Some(order_id) == first_order_id {
+					break;
+				}
+				wrapped_order_ids.push(order_id);
+				if Some(order_id) == last_checked_order_id {
+					break;
+				}
+			}
+			order_ids.extend(wrapped_order_ids);
+		}
+
+		for order_id in order_ids.iter() {
+			if let Err(e) = Self::check_order(*order_id, now) {
+				log::debug!(
+					target: "limit-order",
+					"check order {:?} failed: {:?}",
+					order_id,
+					e
+				);
+			}
+		}
+
+		LastCheckedOrderId::<T>::set(order_ids.last().copied());
+		order_ids.len() as u32
+	}
+}
@@ -0,0 +1,408 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the limit order module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	Balances, BlockNumber, Dex, Event, ExtBuilder, LimitOrderModule, Origin, Runtime, System, Tokens, ALICE, AUSD, BOB,
+	BTC, CAROL, DOT, ORDER_DEPOSIT,
+};
+
+const IDLE_WEIGHT: Weight = 1_000_000_000_000;
+
+fn module_balance(currency_id: CurrencyId) -> Balance {
+	Tokens::free_balance(currency_id, &LimitOrderModule::account_id())
+}
+
+fn run_on_idle(n: BlockNumber) -> Weight {
+	System::set_block_number(n);
+	LimitOrderModule::on_idle(n, IDLE_WEIGHT)
+}
+
+#[test]
+fn place_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LimitOrderModule::place_order(Origin::signed(BOB), vec![DOT], 1_000_000_000_000, Price::one(), None),
+			Error::<Runtime>::InvalidTradingPathLength
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(BOB),
+				vec![DOT, AUSD, BTC, AUSD],
+				1_000_000_000_000,
+				Price::one(),
+				None
+			),
+			Error::<Runtime>::InvalidTradingPathLength
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(BOB),
+				vec![DOT, AUSD, DOT],
+				1_000_000_000_000,
+				Price::one(),
+				None
+			),
+			Error::<Runtime>::InvalidTradingPath
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				Price::zero(),
+				None
+			),
+			Error::<Runtime>::InvalidLimitPrice
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(Origin::signed(BOB), vec![DOT, AUSD], 999_999_999, Price::one(), None),
+			Error::<Runtime>::BelowMinimumOrderAmount
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				Price::one(),
+				Some(1)
+			),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(CAROL),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				Price::one(),
+				None
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+		assert_noop!(
+			LimitOrderModule::place_order(
+				Origin::signed(ALICE),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				Price::one(),
+				None
+			),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::saturating_from_integer(11),
+			Some(10)
+		));
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderPlaced {
+			order_id: 0,
+			owner: BOB,
+			path: vec![DOT, AUSD],
+			supply_amount: 1_000_000_000_000,
+			limit_price: Price::saturating_from_integer(11),
+			expiry: Some(10),
+		}));
+		assert_eq!(
+			LimitOrderModule::orders(0),
+			Some(LimitOrder {
+				owner: BOB,
+				path: vec![DOT, AUSD],
+				supply_amount: 1_000_000_000_000,
+				limit_price: Price::saturating_from_integer(11),
+				expiry: Some(10),
+				deposit: ORDER_DEPOSIT,
+			})
+		);
+		assert_eq!(LimitOrderModule::next_order_id(), 1);
+		assert_eq!(
+			Tokens::free_balance(DOT, &BOB),
+			1_000_000_000_000_000 - 1_000_000_000_000
+		);
+		assert_eq!(module_balance(DOT), 1_000_000_000_000);
+		assert_eq!(Balances::reserved_balance(BOB), ORDER_DEPOSIT);
+	});
+}
+
+#[test]
+fn cancel_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::saturating_from_integer(11),
+			None
+		));
+		assert_eq!(Balances::reserved_balance(BOB), ORDER_DEPOSIT);
+
+		assert_noop!(
+			LimitOrderModule::cancel_order(Origin::signed(BOB), 1),
+			Error::<Runtime>::OrderNotFound
+		);
+		assert_noop!(
+			LimitOrderModule::cancel_order(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(LimitOrderModule::cancel_order(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderCancelled {
+			order_id: 0,
+			owner: BOB,
+			refund_amount: 1_000_000_000_000,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000);
+		assert_eq!(module_balance(DOT), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn fill_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::saturating_from_rational(95, 10),
+			None
+		));
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::saturating_from_integer(11),
+			None
+		));
+
+		assert!(run_on_idle(2) > 0);
+		System::assert_has_event(Event::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			owner: BOB,
+			supply_amount: 1_000_000_000_000,
+			target_amount: 9_802_950_787_206,
+			remaining_supply_amount: 0,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(
+			LimitOrderModule::orders(1).map(|order| order.supply_amount),
+			Some(1_000_000_000_000)
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 9_802_950_787_206);
+		assert_eq!(module_balance(DOT), 1_000_000_000_000);
+		assert_eq!(module_balance(AUSD), 0);
+		assert_eq!(Balances::reserved_balance(BOB), ORDER_DEPOSIT);
+		assert_eq!(
+			Dex::get_liquidity_pool(AUSD, DOT),
+			(1_000_000_000_000_000 - 9_802_950_787_206, 101_000_000_000_000)
+		);
+	});
+}
+
+#[test]
+fn partial_fill_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000_000_000_000,
+			Price::saturating_from_rational(95, 10),
+			None
+		));
+
+		// only part of the order can be filled at the limit price
+		run_on_idle(2);
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			owner: BOB,
+			supply_amount: 4_219_328_125_000,
+			target_amount: 40_096_465_025_795,
+			remaining_supply_amount: 5_780_671_875_000,
+		}));
+		assert_eq!(
+			LimitOrderModule::orders(0).map(|order| order.supply_amount),
+			Some(5_780_671_875_000)
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 40_096_465_025_795);
+		assert_eq!(module_balance(DOT), 5_780_671_875_000);
+		assert_eq!(Balances::reserved_balance(BOB), ORDER_DEPOSIT);
+
+		// the price doesn't change, no more fills
+		System::reset_events();
+		run_on_idle(3);
+		assert!(System::events().is_empty());
+
+		// the price of DOT rises
+		assert_ok!(Dex::swap_with_exact_supply(
+			Origin::signed(CAROL),
+			vec![AUSD, DOT],
+			200_000_000_000_000,
+			0
+		));
+		run_on_idle(4);
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			owner: BOB,
+			supply_amount: 5_780_671_875_000,
+			target_amount: 72_057_231_650_253,
+			remaining_supply_amount: 0,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(
+			Tokens::free_balance(AUSD, &BOB),
+			40_096_465_025_795 + 72_057_231_650_253
+		);
+		assert_eq!(module_balance(DOT), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn partial_fill_refunds_remainder_below_minimum() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_500_000_000,
+			Price::saturating_from_rational(98_999, 10_000),
+			None
+		));
+
+		// the remainder of the partial fill is below the minimum order amount
+		run_on_idle(2);
+		System::assert_has_event(Event::LimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			owner: BOB,
+			supply_amount: 1_019_531_250,
+			target_amount: 10_093_257_500,
+			remaining_supply_amount: 480_468_750,
+		}));
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderRemainderRefunded {
+			order_id: 0,
+			owner: BOB,
+			refund_amount: 480_468_750,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000 - 1_019_531_250);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 10_093_257_500);
+		assert_eq!(module_balance(DOT), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn order_expiry_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::saturating_from_integer(11),
+			Some(5)
+		));
+
+		run_on_idle(4);
+		assert!(LimitOrderModule::orders(0).is_some());
+
+		run_on_idle(5);
+		System::assert_last_event(Event::LimitOrderModule(crate::Event::OrderExpired {
+			order_id: 0,
+			owner: BOB,
+			refund_amount: 1_000_000_000_000,
+		}));
+		assert_eq!(LimitOrderModule::orders(0), None);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000);
+		assert_eq!(module_balance(DOT), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn on_idle_checks_orders_in_turn() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(LimitOrderModule::place_order(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				Price::saturating_from_integer(11),
+				Some(5)
+			));
+		}
+
+		// not enough weight to check any order
+		assert_eq!(LimitOrderModule::on_idle(5, <() as WeightInfo>::check_orders(1) - 1), 0);
+		assert_eq!(LimitOrderModule::last_checked_order_id(), None);
+
+		// at most 2 orders are checked in a block
+		assert_eq!(run_on_idle(5), <() as WeightInfo>::check_orders(2));
+		let expired_orders = || {
+			System::events()
+				.into_iter()
+				.filter(|record| matches!(record.event, Event::LimitOrderModule(crate::Event::OrderExpired { .. })))
+				.count()
+		};
+		assert_eq!(expired_orders(), 2);
+		assert_eq!(Orders::<Runtime>::iter().count(), 1);
+		assert_eq!(Balances::reserved_balance(BOB), ORDER_DEPOSIT);
+		assert!(LimitOrderModule::last_checked_order_id().is_some());
+
+		// continue with the rest orders
+		assert_eq!(run_on_idle(6), <() as WeightInfo>::check_orders(1));
+		assert_eq!(expired_orders(), 3);
+		assert_eq!(Orders::<Runtime>::iter().count(), 0);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000);
+
+		assert_eq!(run_on_idle(7), <() as WeightInfo>::check_orders(0));
+		assert_eq!(LimitOrderModule::last_checked_order_id(), None);
+	});
+}
+
+#[test]
+fn unfillable_order_does_not_block_others() {
+	ExtBuilder::default().build().execute_with(|| {
+		// no liquidity for DOT/BTC
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, BTC],
+			1_000_000_000_000,
+			Price::one(),
+			None
+		));
+		assert_ok!(LimitOrderModule::place_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			1_000_000_000_000,
+			Price::one(),
+			None
+		));
+
+		run_on_idle(2);
+		assert!(LimitOrderModule::orders(0).is_some());
+		assert_eq!(LimitOrderModule::orders(1), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 9_802_950_787_206);
+	});
+}
@@ -49,6 +49,7 @@ use sp_runtime::{
 use sp_std::prelude::*;
 
 pub mod benchmarking;
+pub mod migration;
 mod mock;
 mod tests;
 pub mod weights;
@@ -193,9 +194,67 @@ pub mod module {
 			owner: T::AccountId,
 			class_id: ClassIdOf<T>,
 		},
+		/// Changed the approved account of NFT token.
+		ApprovedToken {
+			owner: T::AccountId,
+			approved: Option<T::AccountId>,
+			class_id: ClassIdOf<T>,
+			token_id: TokenIdOf<T>,
+		},
+		/// Changed the approval of an operator to manage all the tokens of the owner in the
+		/// class.
+		ApprovedOperator {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			class_id: ClassIdOf<T>,
+			approved: bool,
+		},
 	}
 
+	/// The account approved to transfer the token and the deposit reserved on the owner, cleared
+	/// when the token is transferred or burned.
+	///
+	/// TokenApprovals: double_map ClassId, TokenId => Option<(AccountId, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ClassIdOf<T>,
+		Twox64Concat,
+		TokenIdOf<T>,
+		(T::AccountId, BalanceOf<T>),
+		OptionQuery,
+	>;
+
+	/// The operators approved to manage all the tokens of the owner in the class and the
+	/// deposit reserved on the owner.
+	///
+	/// OperatorApprovals: double_map (Owner, ClassId), Operator => Option<Balance>
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, ClassIdOf<T>),
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	/// The number of tokens of the owner in the class.
+	///
+	/// ClassBalances: double_map AccountId, ClassId => NFTBalance
+	#[pallet::storage]
+	#[pallet::getter(fn balance_of_class)]
+	pub type ClassBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ClassIdOf<T>, NFTBalance, ValueQuery>;
+
+	/// The current storage version, `ClassBalances` is populated since v1.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -217,34 +276,7 @@ pub mod module {
 			attributes: Attributes,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let next_id = orml_nft::Pallet::<T>::next_class_id();
-			let owner: T::AccountId = T::PalletId::get().into_sub_account_truncating(next_id);
-			let class_deposit = T::CreateClassDeposit::get();
-
-			let data_deposit = Self::data_deposit(&metadata, &attributes)?;
-			let proxy_deposit = <pallet_proxy::Pallet<T>>::deposit(1u32);
-			let deposit = class_deposit.saturating_add(data_deposit);
-			let total_deposit = proxy_deposit.saturating_add(deposit);
-
-			// ensure enough token for proxy deposit + class deposit + data deposit
-			<T as module::Config>::Currency::transfer(&who, &owner, total_deposit, KeepAlive)?;
-
-			<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &owner, deposit)?;
-
-			// owner add proxy delegate to origin
-			<pallet_proxy::Pallet<T>>::add_proxy_delegate(&owner, who, Default::default(), Zero::zero())?;
-
-			let data = ClassData {
-				deposit,
-				properties,
-				attributes,
-			};
-			orml_nft::Pallet::<T>::create_class(&owner, metadata, data)?;
-
-			Self::deposit_event(Event::CreatedClass {
-				owner,
-				class_id: next_id,
-			});
+			Self::do_create_class(&who, metadata, properties, attributes)?;
 			Ok(().into())
 		}
 
@@ -325,30 +357,7 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
-			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
-			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			ensure!(
-				class_info.total_issuance == Zero::zero(),
-				Error::<T>::CannotDestroyClass
-			);
-
-			let data = class_info.data;
-
-			<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, data.deposit);
-
-			orml_nft::Pallet::<T>::destroy_class(&who, class_id)?;
-
-			// this should unresere proxy deposit
-			pallet_proxy::Pallet::<T>::remove_proxy_delegate(&who, dest.clone(), Default::default(), Zero::zero())?;
-
-			<T as module::Config>::Currency::transfer(
-				&who,
-				&dest,
-				<T as module::Config>::Currency::free_balance(&who),
-				AllowDeath,
-			)?;
-
-			Self::deposit_event(Event::DestroyedClass { owner: who, class_id });
+			Self::do_destroy_class(&who, class_id, &dest)?;
 			Ok(().into())
 		}
 
@@ -384,6 +393,214 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Create a NFT class owned by a new sub-account of the pallet, `who` pays the deposits and
+	/// becomes the proxy of the class owner.
+	#[transactional]
+	pub fn do_create_class(
+		who: &T::AccountId,
+		metadata: CID,
+		properties: Properties,
+		attributes: Attributes,
+	) -> Result<ClassIdOf<T>, DispatchError> {
+		let next_id = orml_nft::Pallet::<T>::next_class_id();
+		let owner: T::AccountId = T::PalletId::get().into_sub_account_truncating(next_id);
+		let class_deposit = T::CreateClassDeposit::get();
+
+		let data_deposit = Self::data_deposit(&metadata, &attributes)?;
+		let proxy_deposit = <pallet_proxy::Pallet<T>>::deposit(1u32);
+		let deposit = class_deposit.saturating_add(data_deposit);
+		let total_deposit = proxy_deposit.saturating_add(deposit);
+
+		// ensure enough token for proxy deposit + class deposit + data deposit
+		<T as module::Config>::Currency::transfer(who, &owner, total_deposit, KeepAlive)?;
+
+		<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &owner, deposit)?;
+
+		// owner add proxy delegate to origin
+		<pallet_proxy::Pallet<T>>::add_proxy_delegate(&owner, who.clone(), Default::default(), Zero::zero())?;
+
+		let data = ClassData {
+			deposit,
+			properties,
+			attributes,
+		};
+		orml_nft::Pallet::<T>::create_class(&owner, metadata, data)?;
+
+		Self::deposit_event(Event::CreatedClass {
+			owner,
+			class_id: next_id,
+		});
+		Ok(next_id)
+	}
+
+	/// Destroy the NFT class owned by `who`, remove `dest` from the proxies and send all the free
+	/// balance to `dest`.
+	#[transactional]
+	pub fn do_destroy_class(who: &T::AccountId, class_id: ClassIdOf<T>, dest: &T::AccountId) -> DispatchResult {
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(*who == class_info.owner, Error::<T>::NoPermission);
+		ensure!(
+			class_info.total_issuance == Zero::zero(),
+			Error::<T>::CannotDestroyClass
+		);
+
+		let data = class_info.data;
+
+		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, who, data.deposit);
+
+		orml_nft::Pallet::<T>::destroy_class(who, class_id)?;
+
+		// this should unresere proxy deposit
+		pallet_proxy::Pallet::<T>::remove_proxy_delegate(who, dest.clone(), Default::default(), Zero::zero())?;
+
+		<T as module::Config>::Currency::transfer(
+			who,
+			dest,
+			<T as module::Config>::Currency::free_balance(who),
+			AllowDeath,
+		)?;
+
+		Self::deposit_event(Event::DestroyedClass {
+			owner: who.clone(),
+			class_id,
+		});
+		Ok(())
+	}
+
+	/// Get the owner of the class if `who` is the owner or the proxy of the owner added at
+	/// class creation.
+	pub fn class_owner_for_operator(who: &T::AccountId, class_id: ClassIdOf<T>) -> Result<T::AccountId, DispatchError> {
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(
+			*who == class_info.owner
+				|| pallet_proxy::Pallet::<T>::proxies(&class_info.owner)
+					.0
+					.iter()
+					.any(|proxy| proxy.delegate == *who && proxy.proxy_type == Default::default()),
+			Error::<T>::NoPermission
+		);
+		Ok(class_info.owner)
+	}
+
+	/// Transfer the token of `from` by `operator`, which must be the owner, the approved
+	/// account of the token or an approved operator of the owner.
+	#[transactional]
+	pub fn do_transfer_from(
+		operator: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(*from == token_info.owner, Error::<T>::NoPermission);
+		ensure!(
+			operator == from
+				|| Self::token_approvals(token.0, token.1).map_or(false, |(approved, _)| approved == *operator)
+				|| Self::is_approved_for_all(from, token.0, operator),
+			Error::<T>::NoPermission
+		);
+
+		Self::do_transfer(from, to, token)
+	}
+
+	/// Set or clear the approved account of the token. `who` must be the owner or an approved
+	/// operator of the owner. The deposit of the approval is reserved on the owner. Returns the
+	/// owner of the token.
+	#[transactional]
+	pub fn do_approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> Result<T::AccountId, DispatchError> {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+		let owner = token_info.owner;
+		ensure!(
+			*who == owner || Self::is_approved_for_all(&owner, token.0, who),
+			Error::<T>::NoPermission
+		);
+
+		match approved {
+			Some(ref account) => {
+				let deposit = match Self::token_approvals(token.0, token.1) {
+					Some((_, deposit)) => deposit,
+					None => {
+						let deposit = Self::approval_deposit(&(token.0, token.1, account));
+						<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &owner, deposit)?;
+						deposit
+					}
+				};
+				TokenApprovals::<T>::insert(token.0, token.1, (account, deposit));
+			}
+			None => Self::clear_token_approval(&owner, token),
+		}
+
+		Self::deposit_event(Event::ApprovedToken {
+			owner: owner.clone(),
+			approved,
+			class_id: token.0,
+			token_id: token.1,
+		});
+		Ok(owner)
+	}
+
+	/// Approve or remove `operator` to manage all the tokens of `owner` in the class. The deposit
+	/// of the approval is reserved on the owner and refunded when it's removed, which is allowed
+	/// after the class is destroyed.
+	#[transactional]
+	pub fn do_set_approval_for_all(
+		owner: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		if approved {
+			ensure!(
+				orml_nft::Pallet::<T>::classes(class_id).is_some(),
+				Error::<T>::ClassIdNotFound
+			);
+			if !OperatorApprovals::<T>::contains_key((owner.clone(), class_id), operator) {
+				let deposit = Self::approval_deposit(&(owner, class_id, operator));
+				<T as module::Config>::Currency::reserve_named(&RESERVE_ID, owner, deposit)?;
+				OperatorApprovals::<T>::insert((owner.clone(), class_id), operator, deposit);
+			}
+		} else if let Some(deposit) = OperatorApprovals::<T>::take((owner.clone(), class_id), operator) {
+			<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, owner, deposit);
+		}
+
+		Self::deposit_event(Event::ApprovedOperator {
+			owner: owner.clone(),
+			operator: operator.clone(),
+			class_id,
+			approved,
+		});
+		Ok(())
+	}
+
+	pub fn is_approved_for_all(owner: &T::AccountId, class_id: ClassIdOf<T>, operator: &T::AccountId) -> bool {
+		OperatorApprovals::<T>::contains_key((owner.clone(), class_id), operator)
+	}
+
+	/// The deposit of the approval, paid for the bytes of the stored `approval`.
+	fn approval_deposit(approval: &impl Encode) -> BalanceOf<T> {
+		T::DataDepositPerByte::get().saturating_mul((approval.encoded_size() as u32).into())
+	}
+
+	/// Decrease the number of tokens of `who` in the class, the entry is removed at zero.
+	fn dec_class_balance(who: &T::AccountId, class_id: ClassIdOf<T>) {
+		ClassBalances::<T>::mutate_exists(who, class_id, |maybe_balance| {
+			*maybe_balance = maybe_balance
+				.map(|balance| balance.saturating_sub(1))
+				.filter(|balance| !balance.is_zero())
+		});
+	}
+
+	/// Clear the approved account of the token and refund the deposit to the owner.
+	fn clear_token_approval(owner: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) {
+		if let Some((_, deposit)) = TokenApprovals::<T>::take(token.0, token.1) {
+			<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, owner, deposit);
+		}
+	}
+
 	#[require_transactional]
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
 		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
@@ -396,6 +613,11 @@ impl<T: Config> Pallet<T> {
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		Self::clear_token_approval(from, token);
+		if from != to {
+			Self::dec_class_balance(from, token.0);
+			ClassBalances::<T>::mutate(to, token.0, |balance| *balance = balance.saturating_add(1));
+		}
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, from, token_info.data.deposit);
 		<T as module::Config>::Currency::transfer(from, to, token_info.data.deposit, AllowDeath)?;
@@ -410,8 +632,8 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	#[require_transactional]
-	fn do_mint(
+	#[transactional]
+	pub fn do_mint(
 		who: &T::AccountId,
 		to: &T::AccountId,
 		class_id: ClassIdOf<T>,
@@ -447,6 +669,9 @@ impl<T: Config> Pallet<T> {
 				data.clone(),
 			)?);
 		}
+		ClassBalances::<T>::mutate(to, class_id, |balance| {
+			*balance = balance.saturating_add(quantity.into())
+		});
 
 		Self::deposit_event(Event::MintedToken {
 			from: who.clone(),
@@ -457,7 +682,7 @@ impl<T: Config> Pallet<T> {
 		Ok(token_ids)
 	}
 
-	fn do_burn(who: T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>), remark: Option<Vec<u8>>) -> DispatchResult {
+	pub fn do_burn(who: T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>), remark: Option<Vec<u8>>) -> DispatchResult {
 		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
 		ensure!(
@@ -465,10 +690,27 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::NonBurnable
 		);
 
+		Self::burn_token(who, token, remark)
+	}
+
+	/// Burn a token regardless of the `Burnable` property of its class, for
+	/// the modules which manage the lifecycle of the tokens of their classes.
+	pub fn do_force_burn(who: T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+		ensure!(
+			orml_nft::Pallet::<T>::classes(token.0).is_some(),
+			Error::<T>::ClassIdNotFound
+		);
+
+		Self::burn_token(who, token, None)
+	}
+
+	fn burn_token(who: T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>), remark: Option<Vec<u8>>) -> DispatchResult {
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 		ensure!(who == token_info.owner, Error::<T>::NoPermission);
 
 		orml_nft::Pallet::<T>::burn(&who, token)?;
+		Self::clear_token_approval(&who, token);
+		Self::dec_class_balance(&who, token.0);
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, token_info.data.deposit);
 
@@ -0,0 +1,76 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+	log,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_std::marker::PhantomData;
+
+/// Count the tokens of the existing owners into ClassBalances, migrates the storage to v1
+pub struct PopulateClassBalances<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for PopulateClassBalances<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_storage_version >= 1 {
+			log::warn!(
+				target: "nft",
+				"PopulateClassBalances::on_runtime_upgrade skipped, storage version is {:?}",
+				on_chain_storage_version,
+			);
+			return T::DbWeight::get().reads(1);
+		}
+
+		log::info!(
+			target: "nft",
+			"PopulateClassBalances::on_runtime_upgrade execute, will populate Storage ClassBalances",
+		);
+
+		let mut count: u64 = 0;
+		for (owner, class_id, _) in orml_nft::TokensByOwner::<T>::iter_keys() {
+			ClassBalances::<T>::mutate(owner, class_id, |balance| *balance = balance.saturating_add(1));
+			count = count.saturating_add(1);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		// reads the token and the balance, writes the balance of every token
+		T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_add(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<(), &'static str> {
+		assert!(Pallet::<T>::on_chain_storage_version() < 1);
+		assert!(ClassBalances::<T>::iter().next().is_none());
+
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		assert_eq!(Pallet::<T>::on_chain_storage_version(), 1);
+
+		log::info!(
+			target: "nft",
+			"PopulateClassBalances done!",
+		);
+
+		Ok(())
+	}
+}
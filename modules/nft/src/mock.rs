@@ -181,6 +181,8 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<One, AccountId>;
 	type OnDust = ();
+	type DataDepositPerByte = ConstU128<DATA_DEPOSIT_PER_BYTE>;
+	type DepositCurrency = Balances;
 }
 
 parameter_types! {
@@ -499,6 +499,19 @@ fn burn_should_fail() {
 			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
 			Error::<Runtime>::NonBurnable
 		);
+
+		// the modules managing the tokens of a class can still burn them
+		assert_noop!(
+			NFTModule::do_force_burn(ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::do_force_burn(BOB, (CLASS_ID, TOKEN_ID)));
+		System::assert_last_event(Event::NFTModule(crate::Event::BurnedToken {
+			owner: BOB,
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID), None);
 	});
 }
 
@@ -712,3 +725,119 @@ fn update_class_properties_should_work() {
 		);
 	});
 }
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1];
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(),
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			Default::default(),
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * (CREATE_TOKEN_DEPOSIT + DATA_DEPOSIT_PER_BYTE)
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			metadata,
+			Default::default(),
+			2
+		));
+		assert_eq!(NFTModule::balance_of_class(&BOB, CLASS_ID), 2);
+		assert_eq!(NFTModule::balance_of_class(&BOB, CLASS_ID_NOT_EXIST), 0);
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), BOB, 10_000));
+
+		assert_noop!(
+			NFTModule::do_transfer_from(&ALICE, &BOB, &ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::do_approve(&ALICE, Some(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_eq!(NFTModule::do_approve(&BOB, Some(ALICE), (CLASS_ID, TOKEN_ID)), Ok(BOB));
+		System::assert_last_event(Event::NFTModule(crate::Event::ApprovedToken {
+			owner: BOB,
+			approved: Some(ALICE),
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		// (class_id, token_id, approved) is 44 bytes
+		let token_approval_deposit = 44 * DATA_DEPOSIT_PER_BYTE;
+		assert_eq!(
+			NFTModule::token_approvals(CLASS_ID, TOKEN_ID),
+			Some((ALICE, token_approval_deposit))
+		);
+		assert_eq!(reserved_balance(&BOB), token_approval_deposit);
+
+		// approving again doesn't reserve more
+		assert_ok!(NFTModule::do_approve(&BOB, Some(ALICE), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(reserved_balance(&BOB), token_approval_deposit);
+
+		// `from` must be the owner
+		assert_noop!(
+			NFTModule::do_transfer_from(&ALICE, &ALICE, &BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::do_transfer_from(&ALICE, &BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::balance_of_class(&ALICE, CLASS_ID), 1);
+		assert_eq!(NFTModule::balance_of_class(&BOB, CLASS_ID), 1);
+		// the approval is cleared and the deposit is refunded after transfer
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(reserved_balance(&BOB), 0);
+
+		assert_noop!(
+			NFTModule::do_set_approval_for_all(&BOB, CLASS_ID_NOT_EXIST, &ALICE, true),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_ok!(NFTModule::do_set_approval_for_all(&BOB, CLASS_ID, &ALICE, true));
+		System::assert_last_event(Event::NFTModule(crate::Event::ApprovedOperator {
+			owner: BOB,
+			operator: ALICE,
+			class_id: CLASS_ID,
+			approved: true,
+		}));
+		assert!(NFTModule::is_approved_for_all(&BOB, CLASS_ID, &ALICE));
+		assert!(!NFTModule::is_approved_for_all(&ALICE, CLASS_ID, &BOB));
+		// (owner, class_id, operator) is 68 bytes
+		let operator_approval_deposit = 68 * DATA_DEPOSIT_PER_BYTE;
+		assert_eq!(
+			OperatorApprovals::<Runtime>::get((BOB, CLASS_ID), ALICE),
+			Some(operator_approval_deposit)
+		);
+		assert_eq!(reserved_balance(&BOB), operator_approval_deposit);
+
+		// the operator can approve and transfer the tokens of the owner
+		assert_ok!(NFTModule::do_approve(&ALICE, Some(ALICE), (CLASS_ID, TOKEN_ID + 1)));
+		assert_eq!(
+			reserved_balance(&BOB),
+			operator_approval_deposit + token_approval_deposit
+		);
+		assert_ok!(NFTModule::do_approve(&ALICE, None, (CLASS_ID, TOKEN_ID + 1)));
+		assert_eq!(NFTModule::token_approvals(CLASS_ID, TOKEN_ID + 1), None);
+		assert_eq!(reserved_balance(&BOB), operator_approval_deposit);
+		assert_ok!(NFTModule::do_transfer_from(
+			&ALICE,
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID + 1)
+		));
+		assert_eq!(NFTModule::balance_of_class(&ALICE, CLASS_ID), 2);
+		assert_eq!(NFTModule::balance_of_class(&BOB, CLASS_ID), 0);
+
+		assert_ok!(NFTModule::do_set_approval_for_all(&BOB, CLASS_ID, &ALICE, false));
+		assert!(!NFTModule::is_approved_for_all(&BOB, CLASS_ID, &ALICE));
+		assert_eq!(reserved_balance(&BOB), 0);
+
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::balance_of_class(&ALICE, CLASS_ID), 1);
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID + 1)));
+		assert!(!ClassBalances::<Runtime>::contains_key(&ALICE, CLASS_ID));
+	});
+}
@@ -41,6 +41,18 @@ pub enum AggregatedSwapPath<CurrencyId> {
 	Taiga(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex),
 }
 
+/// One route of a swap split across multiple routes.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapRoute<CurrencyId, Balance> {
+	/// The aggregated swap path of this route.
+	pub path: Vec<AggregatedSwapPath<CurrencyId>>,
+	/// The supply amount swapped by this route.
+	pub supply_amount: Balance,
+	/// The target amount received by this route.
+	pub target_amount: Balance,
+}
+
 pub trait DEXManager<AccountId, Balance, CurrencyId> {
 	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
 
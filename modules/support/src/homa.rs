@@ -17,7 +17,9 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{ExchangeRate, Rate};
+use primitives::EraIndex;
 use sp_runtime::DispatchResult;
+use sp_std::prelude::*;
 use xcm::latest::prelude::*;
 
 pub trait HomaSubAccountXcm<AccountId, Balance> {
@@ -49,4 +51,16 @@ pub trait HomaManager<AccountId, Balance> {
 	fn get_commission_rate() -> Rate;
 	/// Fee for fast matching redeem request
 	fn get_fast_match_fee() -> Rate;
+	/// Claim the expired unbondings of the redeemer
+	fn claim_redemption(redeemer: AccountId) -> DispatchResult;
+	/// Execute fast match for the redeem requests of the redeemers
+	fn fast_match_redeems(redeemer_list: Vec<AccountId>) -> DispatchResult;
+	/// Gets the pending redeem request of the redeemer: (liquid amount, allow fast match)
+	fn get_redeem_request(redeemer: &AccountId) -> Option<(Balance, bool)>;
+	/// Gets the unbondings of the redeemer: (expired era index, staking amount)
+	fn get_unbondings(redeemer: &AccountId) -> Vec<(EraIndex, Balance)>;
+	/// Gets the current era index of the relaychain known by Homa
+	fn get_current_era() -> EraIndex;
+	/// Gets the total amount of staking currency belong to Homa
+	fn get_total_staking() -> Balance;
 }
@@ -161,6 +161,34 @@ pub trait HonzonManager<AccountId, CurrencyId, Amount, Balance> {
 	) -> DispatchResult;
 	/// Close CDP loan using DEX
 	fn close_loan_by_dex(who: AccountId, currency_id: CurrencyId, max_collateral_amount: Balance) -> DispatchResult;
+	/// Authorize `to` to manipulate the CDP loan of `from` under `currency_id`
+	fn authorize(from: &AccountId, currency_id: CurrencyId, to: &AccountId) -> DispatchResult;
+	/// Cancel the authorization of `from` for `to` under `currency_id`
+	fn unauthorize(from: &AccountId, currency_id: CurrencyId, to: &AccountId) -> DispatchResult;
+	/// Transfer the whole CDP loan of `from` under `currency_id` to `to`, `to` must have the
+	/// authorization of `from`
+	fn transfer_loan_from(to: &AccountId, currency_id: CurrencyId, from: &AccountId) -> DispatchResult;
+	/// Generate new debit in advance, buy collateral and deposit it into CDP
+	fn expand_position_collateral(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		increase_debit_value: Balance,
+		min_increase_collateral: Balance,
+	) -> DispatchResult;
+	/// Sell the collateral locked in CDP to get stable coin to repay the debit
+	fn shrink_position_debit(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		decrease_collateral: Balance,
+		min_decrease_debit_value: Balance,
+	) -> DispatchResult;
+	/// Transfer debit between two CDPs of `who`
+	fn transfer_debit(
+		who: &AccountId,
+		from_currency: CurrencyId,
+		to_currency: CurrencyId,
+		debit_transfer: Balance,
+	) -> DispatchResult;
 	/// Get open CDP corresponding to an account and collateral `CurrencyId`
 	fn get_position(who: &AccountId, currency_id: CurrencyId) -> Position;
 	/// Get liquidation ratio for collateral `CurrencyId`
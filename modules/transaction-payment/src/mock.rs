@@ -159,6 +159,8 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<Zero, AccountId>;
 	type OnDust = ();
+	type DataDepositPerByte = ConstU128<0>;
+	type DepositCurrency = PalletBalances;
 }
 
 thread_local! {
@@ -42,6 +42,7 @@ pub trait RuntimeApiCollection:
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -61,6 +62,7 @@ where
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
@@ -79,6 +79,47 @@ pub struct ExecutionInfo<T> {
 pub type CallInfo = ExecutionInfo<Vec<u8>>;
 pub type CreateInfo = ExecutionInfo<H160>;
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// A call of the simulated bundle. Creates a contract if `to` is none.
+pub struct BundledCall<Balance> {
+	pub from: H160,
+	pub to: Option<H160>,
+	pub data: Vec<u8>,
+	pub value: Balance,
+	pub gas_limit: u64,
+	pub storage_limit: u32,
+	pub access_list: Option<Vec<AccessListItem>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccessListInfo {
+	pub exit_reason: ExitReason,
+	/// The addresses and storage keys accessed by the execution
+	pub access_list: Vec<AccessListItem>,
+	/// Used gas with the access list applied
+	pub used_gas: U256,
+	/// Gas saved by applying the access list
+	pub gas_saved: U256,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+/// The storage deposit of a contract changed by the execution. The deposit is paid by the
+/// origin of the transaction.
+pub struct StorageDeposit {
+	/// The contract which reserves the deposit
+	pub contract: EvmAddress,
+	/// The maintainer of the contract, which receives the deposit when the contract is removed
+	pub maintainer: Option<EvmAddress>,
+	/// Charged storage in bytes
+	pub charged: u32,
+	/// Refunded storage in bytes
+	pub refunded: u32,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BlockLimits {
@@ -86,6 +127,18 @@ pub struct BlockLimits {
 	pub max_gas_limit: u64,
 	/// Max storage limit
 	pub max_storage_limit: u32,
+	/// Base fee per gas of the block
+	pub base_fee_per_gas: Balance,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// `BlockLimits` returned by the runtime api before version 7.
+pub struct LegacyBlockLimits {
+	/// Max gas limit
+	pub max_gas_limit: u64,
+	/// Max storage limit
+	pub max_storage_limit: u32,
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -107,6 +160,24 @@ pub struct EstimateResourcesRequest {
 	pub access_list: Option<Vec<AccessListItem>>,
 }
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Overrides of the account state, applied before simulating a call.
+pub struct AccountOverride {
+	/// Balance in EVM decimals
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<Nonce>,
+	/// Contract code
+	pub code: Option<Vec<u8>>,
+	/// Replace the whole storage of the account
+	pub state: Option<Vec<(H256, H256)>>,
+	/// Replace the given storage slots of the account
+	pub state_diff: Option<Vec<(H256, H256)>>,
+}
+
+pub type StateOverride = Vec<(EvmAddress, AccountOverride)>;
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EthereumTransactionMessage {
@@ -133,6 +204,9 @@ pub const PRECOMPILE_ADDRESS_START: EvmAddress = H160(hex!("00000000000000000000
 pub const PREDEPLOY_ADDRESS_START: EvmAddress = H160(hex!("0000000000000000000000000000000000000800"));
 pub const MIRRORED_TOKENS_ADDRESS_START: EvmAddress = H160(hex!("0000000000000000000100000000000000000000"));
 pub const MIRRORED_NFT_ADDRESS_START: u64 = 0x2000000;
+/// NFT classes mirrored as ERC721
+/// 0x0000000000000000000600000000000000000000 - 0x00000000000000000006000000000000ffffffff
+pub const MIRRORED_NFT_CLASS_ADDRESS_START: EvmAddress = H160(hex!("0000000000000000000600000000000000000000"));
 /// ERC20 Holding Account used for transfer ERC20 token
 pub const ERC20_HOLDING_ACCOUNT: EvmAddress = H160(hex_literal::hex!("000000000000000000ff00000000000000000000"));
 /// System contract address prefix
@@ -149,6 +223,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///                     ^^                     CurrencyId Type: 1-Token 2-DexShare 3-StableAsset
 ///                                                             4-LiquidCrowdloan
 ///                                                             5-ForeignAsset(ignore Erc20, without the prefix of system contracts)
+///                                                             6-NFT class(mirrored ERC721, not a CurrencyId)
 ///                                                             FF-Erc20 Holding Account
 ///                                         ^^ CurrencyId Type is 1-Token, Token
 ///                                   ^^^^^^^^ CurrencyId Type is 1-Token, NFT
@@ -161,6 +236,7 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 9] = [0u8; 9];
 ///                                   ^^^^^^^^ CurrencyId Type is 3-StableAsset, StableAssetPoolId
 ///                                   ^^^^^^^^ CurrencyId Type is 4-LiquidCrowdloan, Lease
 ///                                       ^^^^ CurrencyId Type is 5-ForeignAsset, ForeignAssetId
+///                                   ^^^^^^^^ Type is 6-NFT class, ClassId
 
 /// Check if the given `address` is a system contract.
 ///
@@ -179,6 +255,26 @@ pub const H160_POSITION_DEXSHARE_RIGHT_FIELD: Range<usize> = 16..20;
 pub const H160_POSITION_STABLE_ASSET: Range<usize> = 16..20;
 pub const H160_POSITION_LIQUID_CROADLOAN: Range<usize> = 16..20;
 pub const H160_POSITION_FOREIGN_ASSET: Range<usize> = 18..20;
+pub const H160_POSITION_NFT_CLASS: Range<usize> = 16..20;
+
+/// Generate the EvmAddress of the ERC721 mirror of the NFT class.
+pub fn nft_class_to_evm_address(class_id: u32) -> EvmAddress {
+	let mut address = MIRRORED_NFT_CLASS_ADDRESS_START;
+	address.as_bytes_mut()[H160_POSITION_NFT_CLASS].copy_from_slice(&class_id.to_be_bytes());
+	address
+}
+
+/// Decode the NFT class id from the EvmAddress of its ERC721 mirror.
+pub fn nft_class_from_evm_address(address: EvmAddress) -> Option<u32> {
+	let prefix = &MIRRORED_NFT_CLASS_ADDRESS_START.as_bytes()[..H160_POSITION_NFT_CLASS.start];
+	if !address.as_bytes().starts_with(prefix) {
+		return None;
+	}
+
+	let mut class_id = [0u8; 4];
+	class_id.copy_from_slice(&address.as_bytes()[H160_POSITION_NFT_CLASS]);
+	Some(u32::from_be_bytes(class_id))
+}
 
 /// Generate the EvmAddress from CurrencyId so that evm contracts can call the erc20 contract.
 /// NOTE: Can not be used directly, need to check the erc20 is mapped.
@@ -254,6 +350,12 @@ mod convert {
 			None
 		}
 	}
+
+	/// Convert decimal from EVM(18) to native(KAR/ACA 12), rounding down the remainder.
+	pub fn convert_decimals_from_evm_rounded<B: CheckedDiv + From<u32>>(b: B) -> B {
+		b.checked_div(&Into::<B>::into(DECIMALS_VALUE))
+			.expect("divisor is non-zero; qed")
+	}
 }
 
 #[cfg(feature = "evm-tests")]
@@ -265,6 +367,93 @@ mod convert {
 	pub fn convert_decimals_from_evm<B>(b: B) -> Option<B> {
 		Some(b)
 	}
+
+	pub fn convert_decimals_from_evm_rounded<B>(b: B) -> B {
+		b
+	}
 }
 
 pub use convert::*;
+
+pub mod tracing {
+	use super::*;
+
+	#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "UPPERCASE"))]
+	pub enum CallType {
+		Call,
+		CallCode,
+		StaticCall,
+		DelegateCall,
+		Create,
+		Suicide,
+	}
+
+	/// A call frame recorded by the call tracer.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct CallTrace {
+		#[cfg_attr(feature = "std", serde(rename = "type"))]
+		pub call_type: CallType,
+		pub from: H160,
+		pub to: H160,
+		#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+		pub input: Vec<u8>,
+		pub value: U256,
+		/// Gas limit of the frame
+		pub gas: u64,
+		/// Gas used by the frame, including its sub calls
+		pub gas_used: u64,
+		/// Storage charged (positive) or refunded (negative) by the frame, including its sub calls
+		pub used_storage: i32,
+		#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+		pub output: Vec<u8>,
+		/// `None` if the frame is not finished, e.g. when the transaction failed before the
+		/// execution.
+		pub exit_reason: Option<ExitReason>,
+		pub depth: u32,
+		pub calls: Vec<CallTrace>,
+	}
+
+	/// An executed opcode recorded by the opcode tracer.
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub struct Step {
+		/// Opcode byte
+		pub op: u8,
+		/// Program counter
+		pub pc: u64,
+		pub depth: u32,
+		/// Gas left before executing the opcode
+		pub gas: u64,
+		/// Gas charged by the opcode
+		pub gas_cost: u64,
+		/// Empty if disabled by the tracer config
+		pub stack: Vec<H256>,
+		/// Empty if not enabled by the tracer config
+		#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+		pub memory: Vec<u8>,
+	}
+
+	#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum TracerConfig {
+		/// Record the call frames.
+		CallTracer,
+		/// Record the executed opcodes. Requires the runtime built with `tracing` feature.
+		#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+		OpcodeTracer { disable_stack: bool, enable_memory: bool },
+	}
+
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+	pub enum TraceOutcome {
+		Calls(Vec<CallTrace>),
+		Steps(Vec<Step>),
+	}
+}
@@ -187,6 +187,8 @@ pub enum ReserveIdentifier {
 	Nft,
 	TransactionPayment,
 	TransactionPaymentDeposit,
+	LimitOrder,
+	Currencies,
 
 	// always the last, indicate number of variants
 	Count,
@@ -17,7 +17,10 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::evm::{is_system_contract, EvmAddress, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use crate::evm::{
+	is_system_contract, nft_class_from_evm_address, nft_class_to_evm_address, EvmAddress,
+	SYSTEM_CONTRACT_ADDRESS_PREFIX,
+};
 use frame_support::assert_ok;
 use sp_core::H160;
 use std::str::FromStr;
@@ -179,3 +182,35 @@ fn is_system_contract_works() {
 
 	assert!(!is_system_contract(bytes.into()));
 }
+
+#[test]
+fn nft_class_evm_address_works() {
+	assert_eq!(
+		nft_class_to_evm_address(0),
+		EvmAddress::from_str("0x0000000000000000000600000000000000000000").unwrap()
+	);
+	assert_eq!(
+		nft_class_to_evm_address(0x01020304),
+		EvmAddress::from_str("0x0000000000000000000600000000000001020304").unwrap()
+	);
+	assert_eq!(nft_class_from_evm_address(nft_class_to_evm_address(0)), Some(0));
+	assert_eq!(
+		nft_class_from_evm_address(nft_class_to_evm_address(u32::MAX)),
+		Some(u32::MAX)
+	);
+
+	// mirrored ERC20 of Token
+	assert_eq!(
+		nft_class_from_evm_address(EvmAddress::from_str("0x0000000000000000000100000000000000000000").unwrap()),
+		None
+	);
+	// bytes between the type and the class id must be zero
+	assert_eq!(
+		nft_class_from_evm_address(EvmAddress::from_str("0x0000000000000000000600000000000100000000").unwrap()),
+		None
+	);
+	assert_eq!(
+		nft_class_from_evm_address(EvmAddress::from_str("0x1000000000000000000600000000000000000000").unwrap()),
+		None
+	);
+}
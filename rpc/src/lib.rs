@@ -40,7 +40,7 @@ use orml_oracle_rpc::{Oracle, OracleApiServer};
 use orml_tokens_rpc::{Tokens, TokensApiServer};
 
 /// module rpc
-pub use evm_rpc::{EVMApiServer, EVMRuntimeRPCApi, EVM};
+pub use evm_rpc::{EVMApiServer, EVMRuntimeRPCApi, EVMTraceApi, EVM};
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
@@ -68,6 +68,7 @@ where
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EVMTraceApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
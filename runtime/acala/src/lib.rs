@@ -56,7 +56,7 @@ use module_currencies::BasicCurrencyAdapter;
 use module_evm::{runner::RunnerExtended, CallInfo, CreateInfo, EvmChainId, EvmTask};
 use module_evm_accounts::EvmAddressMapping;
 use module_relaychain::RelayChainCallBuilder;
-use module_support::{AssetIdMapping, DispatchableTask, PoolId};
+use module_support::{AssetIdMapping, DispatchableTask, PoolId, SwapLimit, SwapRoute};
 use module_transaction_payment::TargetedFeeAdjustment;
 
 use cumulus_pallet_parachain_system::RelaychainBlockNumberProvider;
@@ -90,7 +90,11 @@ use module_support::{ExchangeRateProvider, FractionalRate};
 use primitives::currency::AssetIds;
 pub use primitives::{
 	define_combined_task,
-	evm::{AccessListItem, BlockLimits, EstimateResourcesRequest, EthereumTransactionMessage},
+	evm::{
+		tracing::{TraceOutcome, TracerConfig},
+		AccessListInfo, AccessListItem, BlockLimits, BundledCall, EstimateResourcesRequest, EthereumTransactionMessage,
+		StateOverride, StorageDeposit,
+	},
 	task::TaskResult,
 	unchecked_extrinsic::AcalaUncheckedExtrinsic,
 	AccountId, AccountIndex, Address, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber, CurrencyId,
@@ -862,6 +866,8 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = GasToWeight;
 	type SweepOrigin = EnsureRootOrOneGeneralCouncil;
 	type OnDust = module_currencies::TransferDust<Runtime, AcalaTreasuryAccount>;
+	type DataDepositPerByte = DataDepositPerByte;
+	type DepositCurrency = Balances;
 }
 
 parameter_types! {
@@ -1120,6 +1126,7 @@ impl module_aggregated_dex::Config for Runtime {
 	type GovernanceOrigin = EnsureRootOrHalfGeneralCouncil;
 	type DexSwapJointList = AlternativeSwapPathJointList;
 	type SwapPathLimit = ConstU32<3>;
+	type MaxSplitSwapRoutes = ConstU32<3>;
 	type WeightInfo = weights::module_aggregated_dex::WeightInfo<Runtime>;
 }
 
@@ -1195,7 +1202,7 @@ impl module_transaction_payment::Config for Runtime {
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
-	type OnTransactionPayment = DealWithFees;
+	type OnTransactionPayment = module_evm::BurnBaseFee<Runtime, DealWithFees>;
 	type AlternativeFeeSwapDeposit = NativeTokenExistentialDeposit;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type TipPerWeightStep = TipPerWeightStep;
@@ -1324,6 +1331,8 @@ impl InstanceFilter<Call> for ProxyType {
 						| Call::Dex(module_dex::Call::swap_with_exact_target { .. })
 						| Call::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_supply { .. })
 						| Call::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_target { .. })
+						| Call::AggregatedDex(module_aggregated_dex::Call::split_swap_with_exact_supply { .. })
+						| Call::AggregatedDex(module_aggregated_dex::Call::split_swap_with_exact_target { .. })
 				)
 			}
 			ProxyType::Loan => {
@@ -1388,9 +1397,13 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const NewContractExtraBytes: u32 = 10_000;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub const AbandonedContractPeriod: BlockNumber = 365 * DAYS;
+	pub const CodeUpgradeDelay: BlockNumber = 7 * DAYS;
+	pub MinBaseFeePerGas: Balance = microcent(ACA) / 100;
+	pub BlockGasTarget: u64 = runtime_common::EvmLimits::<Runtime>::max_gas_limit() / 2;
 	pub DeveloperDeposit: Balance = 50 * dollar(ACA);
 	pub PublicationFee: Balance = 10 * dollar(ACA);
-	pub PrecompilesValue: AllPrecompiles<Runtime, module_transaction_pause::PausedPrecompileFilter<Runtime>> = AllPrecompiles::<_, _>::acala();
+	pub PrecompilesValue: AllPrecompiles<Runtime, module_transaction_pause::PausedPrecompileFilter<Runtime>, Nothing> = AllPrecompiles::<_, _, _>::acala();
 }
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -1421,7 +1434,7 @@ impl module_evm::Config for Runtime {
 	type StorageDepositPerByte = StorageDepositPerByte;
 	type TxFeePerGas = TxFeePerGas;
 	type Event = Event;
-	type PrecompilesType = AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>>;
+	type PrecompilesType = AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>, Nothing>;
 	type PrecompilesValue = PrecompilesValue;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
@@ -1430,6 +1443,10 @@ impl module_evm::Config for Runtime {
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
 	type TreasuryAccount = AcalaTreasuryAccount;
+	type AbandonedContractPeriod = AbandonedContractPeriod;
+	type CodeUpgradeDelay = CodeUpgradeDelay;
+	type MinBaseFeePerGas = MinBaseFeePerGas;
+	type BlockGasTarget = BlockGasTarget;
 	type FreePublicationOrigin = EnsureRootOrHalfGeneralCouncil;
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
@@ -1779,7 +1796,10 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	(module_incentives::migration::ResetRewardsRecord<Runtime, GetAusdIbtcPoolId>,),
+	(
+		module_incentives::migration::ResetRewardsRecord<Runtime, GetAusdIbtcPoolId>,
+		module_nft::migration::PopulateClassBalances<Runtime>,
+	),
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -1952,11 +1972,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_aggregated_dex_runtime_api::AggregatedDexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_split_swap_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			limit: SwapLimit<Balance>,
+		) -> Option<Vec<SwapRoute<CurrencyId, Balance>>> {
+			AggregatedDex::get_split_swap_routes(supply_currency_id, target_currency_id, limit)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn block_limits() -> BlockLimits {
 			BlockLimits {
 				max_gas_limit: runtime_common::EvmLimits::<Runtime>::max_gas_limit(),
 				max_storage_limit: runtime_common::EvmLimits::<Runtime>::max_storage_limit(),
+				base_fee_per_gas: EVM::base_fee_per_gas(),
 			}
 		}
 
@@ -1968,8 +2003,13 @@ impl_runtime_apis! {
 			gas_limit: u64,
 			storage_limit: u32,
 			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
 			_estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
 			<Runtime as module_evm::Config>::Runner::rpc_call(
 				from,
 				from,
@@ -1990,8 +2030,13 @@ impl_runtime_apis! {
 			gas_limit: u64,
 			storage_limit: u32,
 			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
 			_estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
 			<Runtime as module_evm::Config>::Runner::rpc_create(
 				from,
 				data,
@@ -2003,6 +2048,57 @@ impl_runtime_apis! {
 			)
 		}
 
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError> {
+			<Runtime as module_evm::Config>::Runner::rpc_create_access_list(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				<Runtime as module_evm::Config>::config(),
+			)
+		}
+
+		fn storage_deposits(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
+		) -> Result<Vec<StorageDeposit>, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
+			<Runtime as module_evm::Config>::Runner::rpc_storage_deposits(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				<Runtime as module_evm::Config>::config(),
+			)
+		}
+
+		fn call_bundle(calls: Vec<BundledCall<Balance>>) -> Vec<Result<CallInfo, sp_runtime::DispatchError>> {
+			<Runtime as module_evm::Config>::Runner::rpc_call_bundle(calls, <Runtime as module_evm::Config>::config())
+		}
+
 		fn get_estimate_resources_request(extrinsic: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError> {
 			let utx = UncheckedExtrinsic::decode_all_with_depth_limit(sp_api::MAX_EXTRINSIC_DEPTH, &mut &*extrinsic)
 				.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter extrinsic, decode failed"))?;
@@ -2037,6 +2133,70 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				<Runtime as module_evm::Config>::Runner::rpc_call(
+					from,
+					from,
+					to,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+					<Runtime as module_evm::Config>::config(),
+				)
+			})?;
+			result?;
+			Ok(outcome)
+		}
+
+		fn trace_create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				<Runtime as module_evm::Config>::Runner::rpc_create(
+					from,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+					<Runtime as module_evm::Config>::config(),
+				)
+			})?;
+			result?;
+			Ok(outcome)
+		}
+
+		fn trace_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				Executive::apply_extrinsic(extrinsic)
+			})?;
+			result.map_err(|_| sp_runtime::DispatchError::Other("Invalid extrinsic"))?;
+			Ok(outcome)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -2165,6 +2325,10 @@ impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtr
 				return Err(InvalidTransaction::Stale);
 			}
 
+			if !EVM::is_base_fee_covered(TxFeePerGas::get()) {
+				return Err(InvalidTransaction::Payment);
+			}
+
 			let (_, _, _, _, mortality, check_nonce, _, _, charge) = extra.clone();
 
 			if mortality != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
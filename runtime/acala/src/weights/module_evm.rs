@@ -184,4 +184,61 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(8 as Weight))
 			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: EVM Accounts (r:1 w:1)
+	// Storage: EVM ContractLastUsedAt (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:1 w:0)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	// Storage: IdleScheduler NextTaskId (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: IdleScheduler Tasks (r:0 w:1)
+	// Storage: EVM Codes (r:0 w:1)
+	fn reclaim_abandoned_contract() -> Weight {
+		(112_374_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM PendingCodeUpgrades (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	fn propose_code_upgrade(c: u32, ) -> Weight {
+		(32_548_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((1_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM PendingCodeUpgrades (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:1)
+	fn cancel_code_upgrade() -> Weight {
+		(30_641_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EVM CodeDeposits (r:1 w:1)
+	// Storage: EVM CodeInfos (r:1 w:0)
+	// Storage: EVM ParkedCodeDeposits (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	fn refund_code_deposit() -> Weight {
+		(50_217_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: EVM Accounts (r:1 w:0)
+	// Storage: EVM CodeInfos (r:1 w:0)
+	// Storage: EVM CodeDeposits (r:1 w:1)
+	// Storage: EVM ContractCodeDeposits (r:1 w:1)
+	// Storage: Balances Reserves (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: EVM ContractStorageSizes (r:1 w:1)
+	fn rebalance_code_deposit() -> Weight {
+		(61_975_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
 }
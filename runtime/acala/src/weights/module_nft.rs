@@ -63,12 +63,13 @@ impl<T: frame_system::Config> module_nft::WeightInfo for WeightInfo<T> {
 	// Storage: OrmlNFT NextTokenId (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:0 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn mint(i: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 22_000
 			.saturating_add((20_111_000 as Weight).saturating_mul(i as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(i as Weight)))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:0)
@@ -77,32 +78,38 @@ impl<T: frame_system::Config> module_nft::WeightInfo for WeightInfo<T> {
 	// Storage: System Account (r:1 w:1)
 	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:2)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:2 w:2)
 	fn transfer() -> Weight {
 		(95_157_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn burn() -> Weight {
 		(70_465_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn burn_with_remark(b: u32, ) -> Weight {
 		(121_147_000 as Weight)
 			// Standard Error: 0
 			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
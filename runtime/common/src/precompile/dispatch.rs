@@ -0,0 +1,311 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use codec::DecodeLimit;
+use frame_support::{
+	dispatch::{Dispatchable, GetCallMetadata},
+	ensure, log,
+	traits::Contains,
+	weights::{GetDispatchInfo, PostDispatchInfo},
+};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed,
+};
+use module_support::AddressMapping;
+use module_transaction_pause::PausedTransactionFilter;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The maximum depth of the nested calls when decoding the call.
+const MAX_DECODE_DEPTH: u32 = 256;
+
+/// The Dispatch precompile
+///
+/// Dispatch the SCALE encoded runtime call with the caller as the signed origin. Only the calls
+/// allowed by `CallFilter` and not paused by `module_transaction_pause` can be dispatched.
+///
+/// It can be called by any account, the origin is always the direct caller as delegate calls to
+/// the precompiles are rejected.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Dispatch. Rest `input` bytes: `call_len`, `call`.
+pub struct DispatchPrecompile<R, CallFilter>(PhantomData<(R, CallFilter)>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Dispatch = "dispatch(bytes)",
+}
+
+impl<Runtime, CallFilter> Precompile for DispatchPrecompile<Runtime, CallFilter>
+where
+	Runtime: module_evm::Config + module_transaction_pause::Config,
+	<Runtime as frame_system::Config>::Call: Dispatchable<Origin = <Runtime as frame_system::Config>::Origin, PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ GetCallMetadata
+		+ DecodeLimit,
+	CallFilter: Contains<<Runtime as frame_system::Config>::Call>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Dispatch => {
+				let call = decode_call::<Runtime, _>(&input, target_gas)?;
+				let gas_cost = Pricer::<Runtime>::dispatch_cost(&call);
+
+				if let Some(gas_limit) = target_gas {
+					if gas_limit < gas_cost {
+						return Err(PrecompileFailure::Error {
+							exit_status: ExitError::OutOfGas,
+						});
+					}
+				}
+
+				ensure!(
+					!is_static,
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "cannot dispatch in static call".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				);
+
+				let who = Runtime::AddressMapping::get_account_id(&context.caller);
+
+				log::debug!(
+					target: "evm",
+					"dispatch: who: {:?}, call: {:?}",
+					who, call.get_call_metadata()
+				);
+
+				ensure!(
+					CallFilter::contains(&call),
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "call is not allowed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				);
+				ensure!(
+					!PausedTransactionFilter::<Runtime>::contains(&call),
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "call is paused".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				);
+
+				let info = call.get_dispatch_info();
+				let post_info = call
+					.dispatch(frame_system::RawOrigin::Signed(who).into())
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e.error).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				// refund the unused weight
+				let unused_weight = info.weight.saturating_sub(post_info.calc_actual_weight(&info));
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost.saturating_sub(WeightToGas::convert(unused_weight)),
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+fn decode_call<Runtime, I>(input: &I, target_gas: Option<u64>) -> Result<Runtime::Call, PrecompileFailure>
+where
+	Runtime: frame_system::Config,
+	Runtime::Call: DecodeLimit,
+	I: InputT<Error = PrecompileFailure>,
+{
+	// solidity abi encode bytes will add an length at input[1]
+	let call_len = input.u32_at(2)?;
+	let call = input.bytes_at(3, call_len as usize)?;
+
+	Runtime::Call::decode_all_with_depth_limit(MAX_DECODE_DEPTH, &mut &call[..]).map_err(|_| {
+		PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid call".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		}
+	})
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config,
+	<Runtime as frame_system::Config>::Call: GetDispatchInfo,
+{
+	const BASE_COST: u64 = 200;
+
+	fn dispatch_cost(call: &<Runtime as frame_system::Config>::Call) -> u64 {
+		let read_account = InputPricer::<Runtime>::read_accounts(1);
+		let weight = call.get_dispatch_info().weight;
+
+		Self::BASE_COST
+			.saturating_add(read_account)
+			.saturating_add(WeightToGas::convert(weight))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::{
+		mock::{
+			alice, alice_evm_addr, bob, new_test_ext, Balances, Call, One, Origin, PrecompilesValue, TransactionPause,
+		},
+		DISPATCH,
+	};
+	use codec::Encode;
+	use frame_support::assert_ok;
+	use hex_literal::hex;
+	use module_evm::runner::state::PrecompileSet;
+
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		PrecompilesValue::get()
+			.execute(DISPATCH, input, target_gas, context, is_static)
+			.unwrap()
+	}
+
+	fn dispatch_input(call: Call) -> Vec<u8> {
+		// dispatch(bytes) -> 0xab7fff18
+		let mut input = hex!("ab7fff18").to_vec();
+		input.extend_from_slice(&ethabi::encode(&[ethabi::Token::Bytes(call.encode())]));
+		input
+	}
+
+	#[test]
+	fn dispatch_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: DISPATCH,
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			let alice_balance = Balances::free_balance(alice());
+			let bob_balance = Balances::free_balance(bob());
+			let input = dispatch_input(Call::Balances(pallet_balances::Call::transfer {
+				dest: bob(),
+				value: 1_000,
+			}));
+
+			// the caller is not a system contract and is the origin of the call
+			let res = execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Balances::free_balance(alice()), alice_balance - 1_000);
+			assert_eq!(Balances::free_balance(bob()), bob_balance + 1_000);
+
+			// static call
+			assert_eq!(
+				execute(&input, Some(100_000_000), &context, true),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "cannot dispatch in static call".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn dispatch_filter_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: DISPATCH,
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// not in the allow-list
+			let input = dispatch_input(Call::Balances(pallet_balances::Call::set_balance {
+				who: alice(),
+				new_free: 0,
+				new_reserved: 0,
+			}));
+			assert_eq!(
+				execute(&input, Some(100_000_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "call is not allowed".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+
+			// paused
+			assert_ok!(TransactionPause::pause_transaction(
+				Origin::signed(One::get()),
+				b"Balances".to_vec(),
+				b"transfer".to_vec()
+			));
+			let input = dispatch_input(Call::Balances(pallet_balances::Call::transfer {
+				dest: bob(),
+				value: 1_000,
+			}));
+			assert_eq!(
+				execute(&input, Some(100_000_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "call is paused".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+
+			// invalid call
+			let mut input = dispatch_input(Call::Balances(pallet_balances::Call::transfer {
+				dest: bob(),
+				value: 1_000,
+			}));
+			input[4 + 64] = 0xff;
+			assert_eq!(
+				execute(&input, Some(100_000_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid call".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+}
@@ -0,0 +1,711 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputT, Output},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use frame_support::{ensure, log, traits::Get};
+use hex_literal::hex;
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
+};
+use module_nft::WeightInfo;
+use module_support::AddressMapping;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::evm::nft_class_from_evm_address;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// `Transfer(address,address,uint256)`
+pub const TRANSFER_EVENT: [u8; 32] = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `Approval(address,address,uint256)`
+pub const APPROVAL_EVENT: [u8; 32] = hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+/// `ApprovalForAll(address,address,bool)`
+pub const APPROVAL_FOR_ALL_EVENT: [u8; 32] = hex!("17307eab39ab6107e8899845ad3d59bd9653f200f220920489ca2b5937696c31");
+
+/// ERC-165 interface ids supported by the mirror.
+const INTERFACE_ID_ERC165: [u8; 4] = hex!("01ffc9a7");
+const INTERFACE_ID_ERC721: [u8; 4] = hex!("80ac58cd");
+const INTERFACE_ID_ERC721_METADATA: [u8; 4] = hex!("5b5e139f");
+
+/// The ERC-721 mirror of the NFT classes.
+///
+/// Every NFT class is presented as an ERC-721 contract at
+/// `primitives::evm::nft_class_to_evm_address(class_id)`, the class is decoded from the called
+/// address. Unlike the other Acala precompiles it can be called by any account.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Query name, the `name` attribute of the class.
+/// - Query symbol, the `symbol` attribute of the class.
+/// - Query token URI. Rest `input` bytes: `token_id`.
+/// - Query balance. Rest `input` bytes: `owner`.
+/// - Query owner. Rest `input` bytes: `token_id`.
+/// - Transfer from. Rest `input` bytes: `from`, `to`, `token_id`.
+/// - Safe transfer from. Rest `input` bytes: `from`, `to`, `token_id`, (`data_len`, `data`).
+///   `onERC721Received` of the receiver can't be invoked by the precompile, so it reverts when
+///   `to` is a contract.
+/// - Approve. Rest `input` bytes: `approved`, `token_id`.
+/// - Query approved. Rest `input` bytes: `token_id`.
+/// - Set approval for all. Rest `input` bytes: `operator`, `approved`.
+/// - Query approved for all. Rest `input` bytes: `owner`, `operator`.
+/// - Supports interface. Rest `input` bytes: `interface_id`.
+pub struct ERC721Precompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	QueryName = "name()",
+	QuerySymbol = "symbol()",
+	QueryTokenURI = "tokenURI(uint256)",
+	QueryBalance = "balanceOf(address)",
+	QueryOwner = "ownerOf(uint256)",
+	TransferFrom = "transferFrom(address,address,uint256)",
+	SafeTransferFrom = "safeTransferFrom(address,address,uint256)",
+	SafeTransferFromWithData = "safeTransferFrom(address,address,uint256,bytes)",
+	Approve = "approve(address,uint256)",
+	QueryApproved = "getApproved(uint256)",
+	SetApprovalForAll = "setApprovalForAll(address,bool)",
+	QueryApprovedForAll = "isApprovedForAll(address,address)",
+	SupportsInterface = "supportsInterface(bytes4)",
+}
+
+impl<Runtime> Precompile for ERC721Precompile<Runtime>
+where
+	Runtime: module_evm::Config + module_nft::Config + orml_nft::Config<ClassId = u32, TokenId = u64>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let class_id = nft_class_from_evm_address(context.address).ok_or_else(|| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: "invalid nft class".into(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		})?;
+
+		let action = input.action()?;
+
+		let revert = |e: sp_runtime::DispatchError| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: Into::<&str>::into(e).as_bytes().to_vec(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		};
+
+		if is_static
+			&& matches!(
+				action,
+				Action::TransferFrom
+					| Action::SafeTransferFrom
+					| Action::SafeTransferFromWithData
+					| Action::Approve
+					| Action::SetApprovalForAll
+			) {
+			return Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "cannot modify state in static call".into(),
+				cost: target_gas_limit(target_gas).unwrap_or_default(),
+			});
+		}
+
+		match action {
+			Action::QueryName | Action::QuerySymbol => {
+				let key: &[u8] = if action == Action::QueryName {
+					b"name"
+				} else {
+					b"symbol"
+				};
+
+				let value = orml_nft::Pallet::<Runtime>::classes(class_id)
+					.and_then(|class_info| class_info.data.attributes.get(key).cloned())
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&value),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryTokenURI => {
+				let token_id = input.u64_at(1)?;
+
+				let token_info = orml_nft::Pallet::<Runtime>::tokens(class_id, token_id)
+					.ok_or_else(|| revert(module_nft::Error::<Runtime>::TokenIdNotFound.into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&token_info.metadata.into_inner()),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryBalance => {
+				let who = input.account_id_at(1)?;
+
+				log::debug!(target: "evm", "erc721: query_balance class_id: {:?}, who: {:?}", class_id, who);
+
+				let balance = module_nft::Pallet::<Runtime>::balance_of_class(&who, class_id);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(balance),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryOwner => {
+				let token_id = input.u64_at(1)?;
+
+				let token_info = orml_nft::Pallet::<Runtime>::tokens(class_id, token_id)
+					.ok_or_else(|| revert(module_nft::Error::<Runtime>::TokenIdNotFound.into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_address(evm_address::<Runtime>(&token_info.owner)),
+					logs: Default::default(),
+				})
+			}
+			Action::TransferFrom | Action::SafeTransferFrom | Action::SafeTransferFromWithData => {
+				let operator = Runtime::AddressMapping::get_account_id(&context.caller);
+				let from = input.account_id_at(1)?;
+				let to = input.account_id_at(2)?;
+				let token_id = input.u64_at(3)?;
+
+				if action != Action::TransferFrom
+					&& module_evm::Pallet::<Runtime>::is_contract(&input.evm_address_at(2)?)
+				{
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "ERC721: transfer to non ERC721Receiver implementer".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
+				log::debug!(target: "evm", "erc721: transfer_from operator: {:?}, from: {:?}, to: {:?}, class_id: {:?}, token_id: {:?}", operator, from, to, class_id, token_id);
+
+				module_nft::Pallet::<Runtime>::do_transfer_from(&operator, &from, &to, (class_id, token_id))
+					.map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![transfer_log(
+						context.address,
+						input.evm_address_at(1)?,
+						input.evm_address_at(2)?,
+						token_id,
+					)],
+				})
+			}
+			Action::Approve => {
+				let who = Runtime::AddressMapping::get_account_id(&context.caller);
+				let approved_address = input.evm_address_at(1)?;
+				let approved = if approved_address.is_zero() {
+					None
+				} else {
+					Some(input.account_id_at(1)?)
+				};
+				let token_id = input.u64_at(2)?;
+
+				log::debug!(target: "evm", "erc721: approve who: {:?}, approved: {:?}, class_id: {:?}, token_id: {:?}", who, approved, class_id, token_id);
+
+				let owner =
+					module_nft::Pallet::<Runtime>::do_approve(&who, approved, (class_id, token_id)).map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_log(
+						context.address,
+						evm_address::<Runtime>(&owner),
+						approved_address,
+						token_id,
+					)],
+				})
+			}
+			Action::QueryApproved => {
+				let token_id = input.u64_at(1)?;
+
+				ensure_token_exists::<Runtime>(class_id, token_id).map_err(revert)?;
+				let approved = module_nft::Pallet::<Runtime>::token_approvals(class_id, token_id)
+					.map(|(approved, _)| evm_address::<Runtime>(&approved))
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_address(approved),
+					logs: Default::default(),
+				})
+			}
+			Action::SetApprovalForAll => {
+				let owner = Runtime::AddressMapping::get_account_id(&context.caller);
+				let operator = input.account_id_at(1)?;
+				let approved = input.bool_at(2)?;
+
+				log::debug!(target: "evm", "erc721: set_approval_for_all owner: {:?}, operator: {:?}, class_id: {:?}, approved: {:?}", owner, operator, class_id, approved);
+
+				module_nft::Pallet::<Runtime>::do_set_approval_for_all(&owner, class_id, &operator, approved)
+					.map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_for_all_log(
+						context.address,
+						context.caller,
+						input.evm_address_at(1)?,
+						approved,
+					)],
+				})
+			}
+			Action::QueryApprovedForAll => {
+				let owner = input.account_id_at(1)?;
+				let operator = input.account_id_at(2)?;
+
+				let approved = module_nft::Pallet::<Runtime>::is_approved_for_all(&owner, class_id, &operator);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bool(approved),
+					logs: Default::default(),
+				})
+			}
+			Action::SupportsInterface => {
+				let interface_id = input.bytes_at(1, 4)?;
+
+				let supported = [INTERFACE_ID_ERC165, INTERFACE_ID_ERC721, INTERFACE_ID_ERC721_METADATA]
+					.iter()
+					.any(|id| id[..] == interface_id[..]);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bool(supported),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+fn evm_address<Runtime: module_evm::Config>(account_id: &Runtime::AccountId) -> H160 {
+	Runtime::AddressMapping::get_evm_address(account_id)
+		.unwrap_or_else(|| Runtime::AddressMapping::get_default_evm_address(account_id))
+}
+
+fn ensure_token_exists<Runtime>(class_id: u32, token_id: u64) -> sp_runtime::DispatchResult
+where
+	Runtime: module_nft::Config + orml_nft::Config<ClassId = u32, TokenId = u64>,
+{
+	ensure!(
+		orml_nft::Tokens::<Runtime>::contains_key(class_id, token_id),
+		module_nft::Error::<Runtime>::TokenIdNotFound
+	);
+	Ok(())
+}
+
+fn transfer_log(address: H160, from: H160, to: H160, token_id: u64) -> Log {
+	Log {
+		address,
+		topics: vec![
+			H256::from(TRANSFER_EVENT),
+			H256::from(from),
+			H256::from(to),
+			H256::from_uint(&U256::from(token_id)),
+		],
+		data: vec![],
+	}
+}
+
+fn approval_log(address: H160, owner: H160, approved: H160, token_id: u64) -> Log {
+	Log {
+		address,
+		topics: vec![
+			H256::from(APPROVAL_EVENT),
+			H256::from(owner),
+			H256::from(approved),
+			H256::from_uint(&U256::from(token_id)),
+		],
+		data: vec![],
+	}
+}
+
+fn approval_for_all_log(address: H160, owner: H160, operator: H160, approved: bool) -> Log {
+	Log {
+		address,
+		topics: vec![
+			H256::from(APPROVAL_FOR_ALL_EVENT),
+			H256::from(owner),
+			H256::from(operator),
+		],
+		data: Output::encode_bool(approved),
+	}
+}
+
+pub struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_nft::Config,
+{
+	pub const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let weight = match action {
+			Action::SupportsInterface => return Ok(Self::BASE_COST),
+			// OrmlNFT::Classes (r: 1)
+			Action::QueryName | Action::QuerySymbol => <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+			// OrmlNFT::Tokens (r: 1)
+			Action::QueryTokenURI | Action::QueryOwner => <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+			// NFT::ClassBalances (r: 1)
+			Action::QueryBalance => <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+			// OrmlNFT::Tokens (r: 1), NFT::TokenApprovals (r: 1), NFT::OperatorApprovals (r: 1)
+			Action::TransferFrom => <Runtime as module_nft::Config>::WeightInfo::transfer()
+				.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(3)),
+			// OrmlNFT::Tokens (r: 1), NFT::TokenApprovals (r: 1), NFT::OperatorApprovals (r: 1),
+			// EVM::Accounts (r: 1)
+			Action::SafeTransferFrom | Action::SafeTransferFromWithData => {
+				<Runtime as module_nft::Config>::WeightInfo::transfer()
+					.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(4))
+			}
+			// OrmlNFT::Tokens (r: 1), NFT::OperatorApprovals (r: 1), NFT::TokenApprovals (r: 1, w: 1),
+			// Balances::Reserves (r: 1, w: 1), System::Account (r: 1, w: 1)
+			Action::Approve => <Runtime as frame_system::Config>::DbWeight::get().reads_writes(5, 3),
+			// OrmlNFT::Tokens (r: 1), NFT::TokenApprovals (r: 1)
+			Action::QueryApproved => <Runtime as frame_system::Config>::DbWeight::get().reads(2),
+			// OrmlNFT::Classes (r: 1), NFT::OperatorApprovals (r: 1, w: 1), Balances::Reserves (r: 1, w: 1),
+			// System::Account (r: 1, w: 1)
+			Action::SetApprovalForAll => <Runtime as frame_system::Config>::DbWeight::get().reads_writes(4, 3),
+			// NFT::OperatorApprovals (r: 1)
+			Action::QueryApprovedForAll => <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+		};
+
+		Ok(Self::BASE_COST.saturating_add(WeightToGas::convert(weight)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::{
+		aca_evm_address, alice, alice_evm_addr, bob, bob_evm_addr, new_test_ext, Currencies, NFTModule, Origin, Test,
+		ACA,
+	};
+	use ethabi::Token;
+	use frame_support::assert_ok;
+	use primitives::{
+		evm::nft_class_to_evm_address,
+		nft::{Attributes, ClassProperty, Properties},
+	};
+
+	type ERC721Precompile = super::ERC721Precompile<Test>;
+
+	fn context(class_id: u32, caller: H160) -> Context {
+		Context {
+			address: nft_class_to_evm_address(class_id),
+			caller,
+			apparent_value: Default::default(),
+		}
+	}
+
+	/// Create a class with `name` and `symbol` attributes and mint 2 tokens to alice.
+	fn create_class_and_mint() -> u32 {
+		let mut attributes = Attributes::new();
+		attributes.insert(b"name".to_vec(), b"Acala NFT".to_vec());
+		attributes.insert(b"symbol".to_vec(), b"ANFT".to_vec());
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice()),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable),
+			attributes,
+		));
+		let class_id = 0;
+		let class_owner = orml_nft::Pallet::<Test>::classes(class_id).unwrap().owner;
+		assert_ok!(Currencies::update_balance(
+			Origin::root(),
+			class_owner.clone(),
+			ACA,
+			100_000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_owner),
+			alice(),
+			class_id,
+			b"token".to_vec(),
+			Default::default(),
+			2
+		));
+		class_id
+	}
+
+	#[test]
+	fn query_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class_and_mint();
+			let context = context(class_id, bob_evm_addr());
+
+			// name() -> 0x06fdde03
+			let res = ERC721Precompile::execute(&hex!("06fdde03"), None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"Acala NFT"));
+
+			// symbol() -> 0x95d89b41
+			let res = ERC721Precompile::execute(&hex!("95d89b41"), None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"ANFT"));
+
+			// tokenURI(uint256) -> 0xc87b56dd
+			// token_id
+			let mut input = hex!("c87b56dd").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(1.into())]));
+			let res = ERC721Precompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"token"));
+
+			// balanceOf(address) -> 0x70a08231
+			// owner
+			let mut input = hex!("70a08231").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Address(alice_evm_addr())]));
+			let res = ERC721Precompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_uint(2u128));
+
+			// ownerOf(uint256) -> 0x6352211e
+			// token_id
+			let mut input = hex!("6352211e").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(0.into())]));
+			let res = ERC721Precompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_address(alice_evm_addr()));
+
+			let mut input = hex!("6352211e").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(2.into())]));
+			assert_eq!(
+				ERC721Precompile::execute(&input, Some(100_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "TokenIdNotFound".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				})
+			);
+
+			// supportsInterface(bytes4) -> 0x01ffc9a7
+			// interface_id
+			let mut input = hex!("01ffc9a7").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::FixedBytes(INTERFACE_ID_ERC721.to_vec())]));
+			let res = ERC721Precompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_bool(true));
+
+			let mut input = hex!("01ffc9a7").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::FixedBytes(hex!("ffffffff").to_vec())]));
+			let res = ERC721Precompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.output, Output::encode_bool(false));
+		});
+	}
+
+	#[test]
+	fn transfer_from_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class_and_mint();
+			let address = nft_class_to_evm_address(class_id);
+
+			// transferFrom(address,address,uint256) -> 0x23b872dd
+			// from
+			// to
+			// token_id
+			let mut input = hex!("23b872dd").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(0.into()),
+			]));
+
+			// bob is not approved
+			assert_eq!(
+				ERC721Precompile::execute(&input, Some(100_000_000), &context(class_id, bob_evm_addr()), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "NoPermission".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+
+			// static call
+			assert_eq!(
+				ERC721Precompile::execute(&input, Some(100_000_000), &context(class_id, alice_evm_addr()), true),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "cannot modify state in static call".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.logs,
+				vec![transfer_log(address, alice_evm_addr(), bob_evm_addr(), 0)]
+			);
+			assert_eq!(orml_nft::Pallet::<Test>::tokens(class_id, 0).unwrap().owner, bob());
+
+			// safeTransferFrom(address,address,uint256) -> 0x42842e0e
+			// from
+			// to
+			// token_id
+			let mut input = hex!("42842e0e").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(aca_evm_address()),
+				Token::Uint(1.into()),
+			]));
+
+			// the recipient is a contract
+			assert_eq!(
+				ERC721Precompile::execute(&input, Some(100_000_000), &context(class_id, alice_evm_addr()), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "ERC721: transfer to non ERC721Receiver implementer".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+
+			// safeTransferFrom(address,address,uint256,bytes) -> 0xb88d4fde
+			// from
+			// to
+			// token_id
+			// data
+			let mut input = hex!("b88d4fde").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(1.into()),
+				Token::Bytes(vec![]),
+			]));
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(NFTModule::balance_of_class(&bob(), class_id), 2);
+		});
+	}
+
+	#[test]
+	fn approve_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class_and_mint();
+			let address = nft_class_to_evm_address(class_id);
+
+			// approve(address,uint256) -> 0x095ea7b3
+			// approved
+			// token_id
+			let mut input = hex!("095ea7b3").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(bob_evm_addr()),
+				Token::Uint(0.into()),
+			]));
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(
+				res.logs,
+				vec![approval_log(address, alice_evm_addr(), bob_evm_addr(), 0)]
+			);
+
+			// getApproved(uint256) -> 0x081812fc
+			// token_id
+			let mut input = hex!("081812fc").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(0.into())]));
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_address(bob_evm_addr()));
+
+			// the approved account transfers the token
+			let mut input = hex!("23b872dd").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(0.into()),
+			]));
+			assert_ok!(ERC721Precompile::execute(
+				&input,
+				None,
+				&context(class_id, bob_evm_addr()),
+				false
+			));
+			assert_eq!(orml_nft::Pallet::<Test>::tokens(class_id, 0).unwrap().owner, bob());
+			assert_eq!(NFTModule::token_approvals(class_id, 0), None);
+
+			// setApprovalForAll(address,bool) -> 0xa22cb465
+			// operator
+			// approved
+			let mut input = hex!("a22cb465").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Address(bob_evm_addr()), Token::Bool(true)]));
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(
+				res.logs,
+				vec![approval_for_all_log(address, alice_evm_addr(), bob_evm_addr(), true)]
+			);
+
+			// isApprovedForAll(address,address) -> 0xe985e9c5
+			// owner
+			// operator
+			let mut input = hex!("e985e9c5").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+			]));
+			let res = ERC721Precompile::execute(&input, None, &context(class_id, alice_evm_addr()), false).unwrap();
+			assert_eq!(res.output, Output::encode_bool(true));
+
+			// the operator transfers the token of alice
+			let mut input = hex!("42842e0e").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(1.into()),
+			]));
+			assert_ok!(ERC721Precompile::execute(
+				&input,
+				None,
+				&context(class_id, bob_evm_addr()),
+				false
+			));
+			assert_eq!(NFTModule::balance_of_class(&alice(), class_id), 0);
+		});
+	}
+}
@@ -21,6 +21,7 @@ use super::{
 	target_gas_limit,
 };
 use crate::WeightToGas;
+use ethabi::Token;
 use frame_support::{log, traits::Get};
 use module_evm::{
 	precompiles::Precompile,
@@ -46,6 +47,12 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - Get estimated reward rate.
 /// - Get commission rate.
 /// - Get fast match fee.
+/// - Claim redemption. Rest `input` bytes: `redeemer`.
+/// - Fast match redeems. Rest `input` bytes: `redeemer_list`.
+/// - Get redeem request. Rest `input` bytes: `redeemer`.
+/// - Get unbondings. Rest `input` bytes: `redeemer`.
+/// - Get current era.
+/// - Get total staking.
 
 pub struct HomaPrecompile<R>(PhantomData<R>);
 
@@ -59,6 +66,12 @@ pub enum Action {
 	GetEstimatedRewardRate = "getEstimatedRewardRate()",
 	GetCommissionRate = "getCommissionRate()",
 	GetFastMatchFee = "getFastMatchFee()",
+	ClaimRedemption = "claimRedemption(address)",
+	FastMatchRedeems = "fastMatchRedeems(address[])",
+	GetRedeemRequest = "getRedeemRequest(address)",
+	GetUnbondings = "getUnbondings(address)",
+	GetCurrentEra = "getCurrentEra()",
+	GetTotalStaking = "getTotalStaking()",
 }
 
 impl<Runtime> Precompile for HomaPrecompile<Runtime>
@@ -178,6 +191,127 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::ClaimRedemption => {
+				let redeemer = input.account_id_at(1)?;
+
+				log::debug!(
+					target: "evm",
+					"homa: claim_redemption, redeemer: {:?}",
+					&redeemer
+				);
+
+				<module_homa::Pallet<Runtime> as HomaManager<Runtime::AccountId, Balance>>::claim_redemption(redeemer)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::FastMatchRedeems => {
+				// solidity abi encode array will add an offset at input[1]
+				let redeemer_len = input.u32_at(2)?;
+				let mut redeemer_list = vec![];
+				for i in 0..redeemer_len {
+					redeemer_list.push(input.account_id_at((3 + i) as usize)?);
+				}
+
+				log::debug!(
+					target: "evm",
+					"homa: fast_match_redeems, redeemer_list: {:?}",
+					&redeemer_list
+				);
+
+				<module_homa::Pallet<Runtime> as HomaManager<Runtime::AccountId, Balance>>::fast_match_redeems(
+					redeemer_list,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::GetRedeemRequest => {
+				let redeemer = input.account_id_at(1)?;
+
+				// no redeem request is returned as (0, false)
+				let (amount, allow_fast_match) = <module_homa::Pallet<Runtime> as HomaManager<
+					Runtime::AccountId,
+					Balance,
+				>>::get_redeem_request(&redeemer)
+				.unwrap_or_default();
+
+				log::debug!(
+					target: "evm",
+					"homa: get_redeem_request, redeemer: {:?}, amount: {:?}, allow_fast_match: {:?}",
+					&redeemer, amount, allow_fast_match
+				);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: ethabi::encode(&[Token::Uint(amount.into()), Token::Bool(allow_fast_match)]),
+					logs: Default::default(),
+				})
+			}
+			Action::GetUnbondings => {
+				let redeemer = input.account_id_at(1)?;
+
+				let unbondings =
+					<module_homa::Pallet<Runtime> as HomaManager<Runtime::AccountId, Balance>>::get_unbondings(
+						&redeemer,
+					);
+
+				log::debug!(
+					target: "evm",
+					"homa: get_unbondings, redeemer: {:?}, unbondings: {:?}",
+					&redeemer, unbondings
+				);
+
+				let (era_indexes, amounts): (Vec<_>, Vec<_>) = unbondings.into_iter().unzip();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: ethabi::encode(&[
+						Token::Array(era_indexes.into_iter().map(|era| Token::Uint(era.into())).collect()),
+						Token::Array(amounts.into_iter().map(|amount| Token::Uint(amount.into())).collect()),
+					]),
+					logs: Default::default(),
+				})
+			}
+			Action::GetCurrentEra => {
+				let era = <module_homa::Pallet<Runtime> as HomaManager<Runtime::AccountId, Balance>>::get_current_era();
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(era),
+					logs: Default::default(),
+				})
+			}
+			Action::GetTotalStaking => {
+				let total_staking =
+					<module_homa::Pallet<Runtime> as HomaManager<Runtime::AccountId, Balance>>::get_total_staking();
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(total_staking),
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
@@ -227,6 +361,46 @@ where
 				// Homa::FastMatchFeeRate (r: 1)
 				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
 			}
+			Action::ClaimRedemption => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				let weight = <Runtime as module_homa::Config>::WeightInfo::claim_redemption();
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::FastMatchRedeems => {
+				let redeemer_len = input.u32_at(2)?;
+				let cost = InputPricer::<Runtime>::read_accounts(redeemer_len.into());
+				let weight = <Runtime as module_homa::Config>::WeightInfo::fast_match_redeems(redeemer_len);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetRedeemRequest => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// Homa::RedeemRequests (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetUnbondings => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// Homa::Unbondings (r: BondingDuration + 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(
+					<Runtime as module_homa::Config>::BondingDuration::get()
+						.saturating_add(1)
+						.into(),
+				);
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetCurrentEra => {
+				// Homa::RelayChainCurrentEra (r: 1)
+				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+			}
+			Action::GetTotalStaking => {
+				// Homa::TotalStakingBonded (r: 1)
+				// Homa::ToBondPool (r: 1)
+				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(2))
+			}
 		};
 		Ok(Self::BASE_COST.saturating_add(cost))
 	}
@@ -240,6 +414,7 @@ mod tests {
 	};
 	use frame_support::assert_ok;
 	use hex_literal::hex;
+	use orml_traits::MultiCurrency;
 	use sp_runtime::{FixedPointNumber, FixedU128};
 
 	type HomaPrecompile = super::HomaPrecompile<Test>;
@@ -439,4 +614,194 @@ mod tests {
 			assert_eq!(res.output, expected_output);
 		});
 	}
+
+	#[test]
+	fn fast_match_redeems_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Homa::update_homa_params(
+				Origin::signed(HomaAdmin::get()),
+				Some(1_000_000_000_000),
+				Some(FixedU128::saturating_from_rational(1, 10)),
+				Some(FixedU128::saturating_from_rational(1, 10)),
+				Some(FixedU128::saturating_from_rational(1, 10)),
+			));
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), ACA, 1_000_000_000));
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				alice(),
+				StakingCurrencyId::get(),
+				1_000_000_000_000
+			));
+			assert_ok!(Homa::mint(Origin::signed(alice()), 1_000_000_000));
+			assert_ok!(Homa::request_redeem(Origin::signed(alice()), 1_000_000, true));
+
+			// getRedeemRequest(address) -> 0x3c05e18f
+			// redeemer
+			let input = hex! {"
+				3c05e18f
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			// amount
+			// allow_fast_match
+			let expected_output = hex! {"
+				00000000000000000000000000000000 000000000000000000000000000f4240
+				00000000000000000000000000000000 00000000000000000000000000000001
+			"};
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output.to_vec());
+
+			// fastMatchRedeems(address[]) -> 0xf3d71053
+			// offset
+			// redeemer_list_len
+			// redeemer_list
+			let input = hex! {"
+				f3d71053
+				00000000000000000000000000000000 00000000000000000000000000000020
+				00000000000000000000000000000000 00000000000000000000000000000001
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Homa::redeem_requests(alice()), None);
+
+			// getRedeemRequest(address) -> 0x3c05e18f
+			// redeemer
+			let input = hex! {"
+				3c05e18f
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, [0u8; 64].to_vec());
+		});
+	}
+
+	#[test]
+	fn claim_redemption_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				Homa::account_id(),
+				StakingCurrencyId::get(),
+				3_000
+			));
+			module_homa::Unbondings::<Test>::insert(alice(), 1, 1_000);
+			module_homa::Unbondings::<Test>::insert(alice(), 2, 2_000);
+			module_homa::UnclaimedRedemption::<Test>::put(3_000);
+			assert_ok!(Homa::reset_current_era(Origin::signed(HomaAdmin::get()), 1));
+
+			// getUnbondings(address) -> 0x43a1b9e8
+			// redeemer
+			let input = hex! {"
+				43a1b9e8
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			// era_indexes offset
+			// amounts offset
+			// era_indexes
+			// amounts
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000040
+				00000000000000000000000000000000 000000000000000000000000000000a0
+				00000000000000000000000000000000 00000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000001
+				00000000000000000000000000000000 00000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000002
+				00000000000000000000000000000000 000000000000000000000000000003e8
+				00000000000000000000000000000000 000000000000000000000000000007d0
+			"};
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output.to_vec());
+
+			// claimRedemption(address) -> 0xc0598b0d
+			// redeemer
+			let input = hex! {"
+				c0598b0d
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Currencies::free_balance(StakingCurrencyId::get(), &alice()), 1_000);
+			assert_eq!(Homa::unbondings(alice(), 1), 0);
+			assert_eq!(Homa::unbondings(alice(), 2), 2_000);
+		});
+	}
+
+	#[test]
+	fn get_current_era_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Homa::reset_current_era(Origin::signed(HomaAdmin::get()), 10));
+
+			// getCurrentEra() -> 0xd250b6cb
+			let input = hex! {"d250b6cb"};
+
+			let expected_output = hex! {"00000000000000000000000000000000 0000000000000000000000000000000a"}.to_vec();
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output);
+		});
+	}
+
+	#[test]
+	fn get_total_staking_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Homa::update_homa_params(
+				Origin::signed(HomaAdmin::get()),
+				Some(1_000_000_000_000),
+				None,
+				None,
+				None,
+			));
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				alice(),
+				StakingCurrencyId::get(),
+				1_000_000_000_000
+			));
+			assert_ok!(Homa::mint(Origin::signed(alice()), 1_000_000_000));
+
+			// getTotalStaking() -> 0x58d57545
+			let input = hex! {"58d57545"};
+
+			let expected_output = hex! {"00000000000000000000000000000000 0000000000000000000000003b9aca00"}.to_vec();
+
+			let res = HomaPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output);
+		});
+	}
 }
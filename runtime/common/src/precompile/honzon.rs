@@ -45,6 +45,15 @@ use sp_std::{marker::PhantomData, prelude::*};
 ///  - Get position. `input` bytes: `who`, `currency_id`.
 ///  - Get liquidation ratio. `input` bytes: `currency_id`.
 ///  - Get current collateral ratio. `input` bytes: `who`, `currency_id`.
+///  - Get debit exchange rate. `input` bytes: `currency_id`.
+///  - Authorize. `input` bytes: `who`, `currency_id`, `to`.
+///  - Unauthorize. `input` bytes: `who`, `currency_id`, `to`.
+///  - Transfer loan from. `input` bytes: `who`, `currency_id`, `from`.
+///  - Expand position collateral. `input` bytes: `who`, `currency_id`, `increase_debit_value`,
+///    `min_increase_collateral`.
+///  - Shrink position debit. `input` bytes: `who`, `currency_id`, `decrease_collateral`,
+///    `min_decrease_debit_value`.
+///  - Transfer debit. `input` bytes: `who`, `from_currency`, `to_currency`, `debit_transfer`.
 pub struct HonzonPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -57,6 +66,12 @@ pub enum Action {
 	GetCollateralParameters = "getCollateralParameters(address)",
 	GetCurrentCollateralRatio = "getCurrentCollateralRatio(address,address)",
 	GetDebitExchangeRate = "getDebitExchangeRate(address)",
+	Authorize = "authorize(address,address,address)",
+	Unauthorize = "unauthorize(address,address,address)",
+	TransferLoanFrom = "transferLoanFrom(address,address,address)",
+	ExpandPositionCollateral = "expandPositionCollateral(address,address,uint256,uint256)",
+	ShrinkPositionDebit = "shrinkPositionDebit(address,address,uint256,uint256)",
+	TransferDebit = "transferDebit(address,address,address,uint256)",
 }
 
 impl<Runtime> Precompile for HonzonPrecompile<Runtime>
@@ -214,6 +229,195 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::Authorize => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let to = input.account_id_at(3)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: authorize who: {:?}, currency_id: {:?}, to: {:?}",
+					who, currency_id, to
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::authorize(&who, currency_id, &to).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::Unauthorize => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let to = input.account_id_at(3)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: unauthorize who: {:?}, currency_id: {:?}, to: {:?}",
+					who, currency_id, to
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::unauthorize(&who, currency_id, &to).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::TransferLoanFrom => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let from = input.account_id_at(3)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: transfer_loan_from who: {:?}, currency_id: {:?}, from: {:?}",
+					who, currency_id, from
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::transfer_loan_from(&who, currency_id, &from).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::ExpandPositionCollateral => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let increase_debit_value = input.balance_at(3)?;
+				let min_increase_collateral = input.balance_at(4)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: expand_position_collateral who: {:?}, currency_id: {:?}, increase_debit_value: {:?}, min_increase_collateral: {:?}",
+					who, currency_id, increase_debit_value, min_increase_collateral
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::expand_position_collateral(&who, currency_id, increase_debit_value, min_increase_collateral).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::ShrinkPositionDebit => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let decrease_collateral = input.balance_at(3)?;
+				let min_decrease_debit_value = input.balance_at(4)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: shrink_position_debit who: {:?}, currency_id: {:?}, decrease_collateral: {:?}, min_decrease_debit_value: {:?}",
+					who, currency_id, decrease_collateral, min_decrease_debit_value
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::shrink_position_debit(&who, currency_id, decrease_collateral, min_decrease_debit_value).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
+			Action::TransferDebit => {
+				let who = input.account_id_at(1)?;
+				let from_currency = input.currency_id_at(2)?;
+				let to_currency = input.currency_id_at(3)?;
+				let debit_transfer = input.balance_at(4)?;
+
+				log::debug!(
+					target: "evm",
+					"honzon: transfer_debit who: {:?}, from_currency: {:?}, to_currency: {:?}, debit_transfer: {:?}",
+					who, from_currency, to_currency, debit_transfer
+				);
+
+				<module_honzon::Pallet<Runtime> as HonzonManager<
+					Runtime::AccountId,
+					CurrencyId,
+					Amount,
+					Balance,
+				>>::transfer_debit(&who, from_currency, to_currency, debit_transfer).map_err(|e|
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
@@ -296,6 +500,81 @@ where
 					.saturating_add(read_currency)
 					.saturating_add(WeightToGas::convert(weight))
 			}
+			Action::Authorize => {
+				let read_account = InputPricer::<Runtime>::read_accounts(2);
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::authorize();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::Unauthorize => {
+				let read_account = InputPricer::<Runtime>::read_accounts(2);
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::unauthorize();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::TransferLoanFrom => {
+				let read_account = InputPricer::<Runtime>::read_accounts(2);
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::transfer_loan_from();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::ExpandPositionCollateral => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::expand_position_collateral();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::ShrinkPositionDebit => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let currency_id = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::shrink_position_debit();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::TransferDebit => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let from_currency = input.currency_id_at(2)?;
+				let to_currency = input.currency_id_at(3)?;
+				let read_currency =
+					InputPricer::<Runtime>::read_currency(from_currency)
+						.saturating_add(InputPricer::<Runtime>::read_currency(to_currency));
+
+				let weight = <Runtime as module_honzon::Config>::WeightInfo::transfer_debit();
+
+				Self::BASE_COST
+					.saturating_add(read_account)
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
 		};
 		Ok(cost)
 	}
@@ -306,8 +585,8 @@ mod tests {
 	use super::*;
 
 	use crate::precompile::mock::{
-		alice, alice_evm_addr, new_test_ext, CDPEngine, Currencies, DexModule, Honzon, Loans, One, Origin, Test, AUSD,
-		BOB, DOT,
+		alice, alice_evm_addr, bob, new_test_ext, CDPEngine, Currencies, DexModule, Honzon, Loans, One, Origin, Test,
+		AUSD, BOB, DOT, RENBTC,
 	};
 	use frame_support::assert_ok;
 	use hex_literal::hex;
@@ -608,4 +887,234 @@ mod tests {
 			assert_eq!(res.output, expected_output.to_vec());
 		})
 	}
+
+	#[test]
+	fn authorize_and_transfer_loan_from_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CDPEngine::set_collateral_params(
+				Origin::signed(One::get()),
+				DOT,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(1_000_000_000)
+			));
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				alice(),
+				DOT,
+				1_000_000_000_000
+			));
+			assert_ok!(Honzon::adjust_loan(
+				Origin::signed(alice()),
+				DOT,
+				100_000_000_000,
+				1_000_000
+			));
+
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+			// authorize(address,address,address) => 0xc4b2e400
+			// who
+			// currency_id
+			// to
+			let input = hex! {"
+				c4b2e400
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				000000000000000000000000 1000000000000000000000000000000000000002
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Honzon::authorization(alice(), (DOT, bob())), Some(100));
+
+			// transferLoanFrom(address,address,address) => 0xca5f04da
+			// who
+			// currency_id
+			// from
+			let input = hex! {"
+				ca5f04da
+				000000000000000000000000 1000000000000000000000000000000000000002
+				000000000000000000000000 0000000000000000000100000000000000000002
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Loans::positions(DOT, alice()).collateral, 0);
+			assert_eq!(Loans::positions(DOT, alice()).debit, 0);
+			assert_eq!(Loans::positions(DOT, bob()).collateral, 100_000_000_000);
+			assert_eq!(Loans::positions(DOT, bob()).debit, 1_000_000);
+
+			// unauthorize(address,address,address) => 0xe86d7e5d
+			// who
+			// currency_id
+			// to
+			let input = hex! {"
+				e86d7e5d
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				000000000000000000000000 1000000000000000000000000000000000000002
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Honzon::authorization(alice(), (DOT, bob())), None);
+
+			// no authorization
+			let input = hex! {"
+				ca5f04da
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				000000000000000000000000 1000000000000000000000000000000000000002
+			"};
+			assert_eq!(
+				HonzonPrecompile::execute(&input, Some(100_000_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "NoPermission".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn expand_and_shrink_position_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CDPEngine::set_collateral_params(
+				Origin::signed(One::get()),
+				DOT,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(1_000_000_000)
+			));
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				alice(),
+				DOT,
+				1_000_000_000_000
+			));
+			assert_ok!(Honzon::adjust_loan(
+				Origin::signed(alice()),
+				DOT,
+				100_000_000_000,
+				1_000_000
+			));
+
+			assert_ok!(DexModule::enable_trading_pair(Origin::signed(One::get()), DOT, AUSD));
+			assert_ok!(Currencies::update_balance(Origin::root(), BOB, AUSD, 1_000_000_000_000));
+			assert_ok!(Currencies::update_balance(Origin::root(), BOB, DOT, 1_000_000_000_000));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				AUSD,
+				1_000_000_000,
+				1_000_000_000,
+				0,
+				false
+			));
+
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+			// expandPositionCollateral(address,address,uint256,uint256) => 0x3ab49d74
+			// who
+			// currency_id
+			// increase_debit_value
+			// min_increase_collateral
+			let input = hex! {"
+				3ab49d74
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				00000000000000000000000000000000 000000000000000000000000000f4240
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Loans::positions(DOT, alice()).debit, 2_000_000);
+			let expanded_collateral = Loans::positions(DOT, alice()).collateral;
+			assert!(expanded_collateral > 100_000_000_000);
+
+			// shrinkPositionDebit(address,address,uint256,uint256) => 0xff23666a
+			// who
+			// currency_id
+			// decrease_collateral
+			// min_decrease_debit_value
+			let input = hex! {"
+				ff23666a
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				00000000000000000000000000000000 0000000000000000000000000007a120
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Loans::positions(DOT, alice()).collateral, expanded_collateral - 500_000);
+			assert!(Loans::positions(DOT, alice()).debit < 2_000_000);
+		});
+	}
+
+	#[test]
+	fn transfer_debit_works() {
+		new_test_ext().execute_with(|| {
+			for currency_id in [DOT, RENBTC] {
+				assert_ok!(CDPEngine::set_collateral_params(
+					Origin::signed(One::get()),
+					currency_id,
+					Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+					Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+					Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+					Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+					Change::NewValue(1_000_000_000)
+				));
+				assert_ok!(Currencies::update_balance(
+					Origin::root(),
+					alice(),
+					currency_id,
+					1_000_000_000
+				));
+				assert_ok!(Honzon::adjust_loan(
+					Origin::signed(alice()),
+					currency_id,
+					100_000_000,
+					1_000_000
+				));
+			}
+
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+			// transferDebit(address,address,address,uint256) => 0xc11eb064
+			// who
+			// from_currency
+			// to_currency
+			// debit_transfer
+			let input = hex! {"
+				c11eb064
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 0000000000000000000100000000000000000002
+				000000000000000000000000 0000000000000000000100000000000000000014
+				00000000000000000000000000000000 0000000000000000000000000007a120
+			"};
+
+			let res = HonzonPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(Loans::positions(DOT, alice()).debit, 500_000);
+			assert_eq!(Loans::positions(RENBTC, alice()).debit, 1_500_000);
+		});
+	}
 }
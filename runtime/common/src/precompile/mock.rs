@@ -23,7 +23,7 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	ord_parameter_types, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly, Everything, InstanceFilter, Nothing, OnFinalize,
+		ConstU128, ConstU32, ConstU64, Contains, EqualPrivilegeOnly, Everything, InstanceFilter, Nothing, OnFinalize,
 		OnInitialize, SortedMembers,
 	},
 	weights::IdentityFee,
@@ -103,10 +103,10 @@ impl SortedMembers<AccountId> for Members {
 	}
 }
 
-impl orml_oracle::Config for Test {
+impl orml_oracle::Config<orml_oracle::Instance1> for Test {
 	type Event = Event;
 	type OnNewData = ();
-	type CombineData = orml_oracle::DefaultCombineData<Self, MinimumCount, ExpiresIn>;
+	type CombineData = orml_oracle::DefaultCombineData<Self, MinimumCount, ExpiresIn, orml_oracle::Instance1>;
 	type Time = Timestamp;
 	type OracleKey = Key;
 	type OracleValue = Price;
@@ -182,6 +182,8 @@ impl module_currencies::Config for Test {
 	type GasToWeight = ();
 	type SweepOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type OnDust = ();
+	type DataDepositPerByte = ConstU128<0>;
+	type DepositCurrency = Balances;
 }
 
 impl module_evm_bridge::Config for Test {
@@ -381,7 +383,14 @@ impl module_dex::Config for Test {
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type ExtendedProvisioningBlocks = ConstU32<0>;
-	type OnLiquidityPoolUpdated = ();
+	type OnLiquidityPoolUpdated = DexOracle;
+}
+
+impl module_dex_oracle::Config for Test {
+	type DEX = DexModule;
+	type Time = Timestamp;
+	type UpdateOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type WeightInfo = ();
 }
 
 parameter_types! {
@@ -555,7 +564,18 @@ pub type EvmErc20InfoMapping = module_asset_registry::EvmErc20InfoMapping<Test>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
-	pub PrecompilesValue: AllPrecompiles<Test, module_transaction_pause::PausedPrecompileFilter<Test>> = AllPrecompiles::<_, _>::mandala();
+	pub PrecompilesValue: AllPrecompiles<Test, module_transaction_pause::PausedPrecompileFilter<Test>, DispatchCallFilter> = AllPrecompiles::<_, _, _>::mandala();
+}
+
+pub struct DispatchCallFilter;
+impl Contains<Call> for DispatchCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Balances(pallet_balances::Call::transfer { .. })
+				| Call::Currencies(module_currencies::Call::transfer { .. })
+		)
+	}
 }
 
 ord_parameter_types! {
@@ -580,7 +600,8 @@ impl module_evm::Config for Test {
 	type StorageDepositPerByte = StorageDepositPerByte;
 	type TxFeePerGas = ConstU128<10>;
 	type Event = Event;
-	type PrecompilesType = AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>>;
+	type PrecompilesType =
+		AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>, DispatchCallFilter>;
 	type PrecompilesValue = PrecompilesValue;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Test>;
@@ -589,6 +610,10 @@ impl module_evm::Config for Test {
 	type DeveloperDeposit = ConstU128<1000>;
 	type PublicationFee = ConstU128<200>;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = ConstU32<100>;
+	type CodeUpgradeDelay = ConstU32<10>;
+	type MinBaseFeePerGas = ConstU128<0>;
+	type BlockGasTarget = ConstU64<10_000_000>;
 	type FreePublicationOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = ();
@@ -726,6 +751,69 @@ impl module_homa::Config for Test {
 	type WeightInfo = ();
 }
 
+pub struct CurrencyIdConvert;
+impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
+	fn convert(id: CurrencyId) -> Option<MultiLocation> {
+		match id {
+			DOT => Some(MultiLocation::parent()),
+			_ => None,
+		}
+	}
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+	fn convert(account: AccountId) -> MultiLocation {
+		X1(Junction::AccountId32 {
+			network: NetworkId::Any,
+			id: account.into(),
+		})
+		.into()
+	}
+}
+
+/// Executes nothing, the assets are not moved.
+pub struct MockXcmExecutor;
+impl ExecuteXcm<Call> for MockXcmExecutor {
+	fn execute_xcm_in_credit(
+		_origin: impl Into<MultiLocation>,
+		_message: Xcm<Call>,
+		_weight_limit: Weight,
+		_weight_credit: Weight,
+	) -> Outcome {
+		Outcome::Complete(0)
+	}
+}
+
+parameter_types! {
+	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(2000)));
+	pub Ancestry: MultiLocation = Parachain(2000).into();
+	pub const MaxAssetsForTransfer: usize = 2;
+}
+
+parameter_type_with_key! {
+	pub ParachainMinFee: |_location: MultiLocation| -> Option<u128> {
+		None
+	};
+}
+
+impl orml_xtokens::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type SelfLocation = SelfLocation;
+	type XcmExecutor = MockXcmExecutor;
+	type Weigher = xcm_builder::FixedWeightBounds<ConstU64<10>, Call, ConstU32<100>>;
+	type BaseXcmWeight = ConstU64<100_000_000>;
+	type LocationInverter = xcm_builder::LocationInverter<Ancestry>;
+	type MaxAssetsForTransfer = MaxAssetsForTransfer;
+	type MinXcmFee = ParachainMinFee;
+	type MultiLocationsFilter = Everything;
+	type ReserveProvider = orml_traits::location::AbsoluteReserveProvider;
+}
+
 impl orml_rewards::Config for Test {
 	type Share = Balance;
 	type Balance = Balance;
@@ -810,7 +898,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
-		Oracle: orml_oracle,
+		Oracle: orml_oracle::<Instance1>,
 		Timestamp: pallet_timestamp,
 		Tokens: orml_tokens exclude_parts { Call },
 		Balances: pallet_balances,
@@ -829,6 +917,7 @@ frame_support::construct_runtime!(
 		Utility: pallet_utility,
 		Scheduler: pallet_scheduler,
 		DexModule: module_dex,
+		DexOracle: module_dex_oracle,
 		EVMModule: module_evm,
 		EvmAccounts: module_evm_accounts,
 		IdleScheduler: module_idle_scheduler,
@@ -836,6 +925,7 @@ frame_support::construct_runtime!(
 		Incentives: module_incentives,
 		Rewards: orml_rewards,
 		StableAsset: nutsfinance_stable_asset,
+		XTokens: orml_xtokens,
 	}
 );
 
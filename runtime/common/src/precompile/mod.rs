@@ -18,6 +18,7 @@
 
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - ERC721 mirrors of the NFT classes at `primitives::evm::nft_class_to_evm_address`.
 
 #![allow(clippy::upper_case_acronyms)]
 
@@ -36,10 +37,13 @@ use module_evm::{
 	Context, ExitRevert,
 };
 use module_support::{PrecompileCallerFilter, PrecompilePauseFilter};
+use primitives::evm::nft_class_from_evm_address;
 use sp_core::H160;
 use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData};
 
 pub mod dex;
+pub mod dispatch;
+pub mod erc721;
 pub mod evm;
 pub mod evm_accounts;
 pub mod homa;
@@ -51,9 +55,13 @@ pub mod nft;
 pub mod oracle;
 pub mod schedule;
 pub mod stable_asset;
+pub mod state_query;
+pub mod xtokens;
 
 use crate::SystemContractsFilter;
 pub use dex::DEXPrecompile;
+pub use dispatch::DispatchPrecompile;
+pub use erc721::ERC721Precompile;
 pub use evm::EVMPrecompile;
 pub use evm_accounts::EVMAccountsPrecompile;
 pub use homa::HomaPrecompile;
@@ -64,6 +72,8 @@ pub use nft::NFTPrecompile;
 pub use oracle::OraclePrecompile;
 pub use schedule::SchedulePrecompile;
 pub use stable_asset::StableAssetPrecompile;
+pub use state_query::StateQueryPrecompile;
+pub use xtokens::XtokensPrecompile;
 
 pub const ECRECOVER: H160 = H160(hex!("0000000000000000000000000000000000000001"));
 pub const SHA256: H160 = H160(hex!("0000000000000000000000000000000000000002"));
@@ -92,17 +102,20 @@ pub const HOMA: H160 = H160(hex!("0000000000000000000000000000000000000407"));
 pub const EVM_ACCOUNTS: H160 = H160(hex!("0000000000000000000000000000000000000408"));
 pub const HONZON: H160 = H160(hex!("0000000000000000000000000000000000000409"));
 pub const INCENTIVES: H160 = H160(hex!("000000000000000000000000000000000000040a"));
+pub const STATE_QUERY: H160 = H160(hex!("000000000000000000000000000000000000040b"));
+pub const DISPATCH: H160 = H160(hex!("000000000000000000000000000000000000040c"));
+pub const XTOKENS: H160 = H160(hex!("000000000000000000000000000000000000040d"));
 
 pub fn target_gas_limit(target_gas: Option<u64>) -> Option<u64> {
 	target_gas.map(|x| x.saturating_div(10).saturating_mul(9)) // 90%
 }
 
-pub struct AllPrecompiles<R, F> {
+pub struct AllPrecompiles<R, F, C> {
 	set: BTreeSet<H160>,
-	_marker: PhantomData<(R, F)>,
+	_marker: PhantomData<(R, F, C)>,
 }
 
-impl<R, F> AllPrecompiles<R, F>
+impl<R, F, C> AllPrecompiles<R, F, C>
 where
 	R: module_evm::Config,
 {
@@ -133,7 +146,10 @@ where
 				// HOMA,
 				EVM_ACCOUNTS,
 				/* HONZON
-				 * INCENTIVES */
+				 * INCENTIVES
+				 * STATE_QUERY
+				 * DISPATCH
+				 * XTOKENS */
 			]),
 			_marker: Default::default(),
 		}
@@ -166,7 +182,10 @@ where
 				// HOMA,
 				EVM_ACCOUNTS,
 				/* HONZON
-				 * INCENTIVES */
+				 * INCENTIVES
+				 * STATE_QUERY
+				 * DISPATCH
+				 * XTOKENS */
 			]),
 			_marker: Default::default(),
 		}
@@ -200,18 +219,22 @@ where
 				EVM_ACCOUNTS,
 				HONZON,
 				INCENTIVES,
+				STATE_QUERY,
+				DISPATCH,
+				XTOKENS,
 			]),
 			_marker: Default::default(),
 		}
 	}
 }
 
-impl<R, PausedPrecompile> PrecompileSet for AllPrecompiles<R, PausedPrecompile>
+impl<R, PausedPrecompile, DispatchCallFilter> PrecompileSet for AllPrecompiles<R, PausedPrecompile, DispatchCallFilter>
 where
 	R: module_evm::Config,
 	PausedPrecompile: PrecompilePauseFilter,
 	MultiCurrencyPrecompile<R>: Precompile,
 	NFTPrecompile<R>: Precompile,
+	ERC721Precompile<R>: Precompile,
 	EVMPrecompile<R>: Precompile,
 	EVMAccountsPrecompile<R>: Precompile,
 	OraclePrecompile<R>: Precompile,
@@ -221,6 +244,9 @@ where
 	HomaPrecompile<R>: Precompile,
 	HonzonPrecompile<R>: Precompile,
 	IncentivesPrecompile<R>: Precompile,
+	StateQueryPrecompile<R>: Precompile,
+	DispatchPrecompile<R, DispatchCallFilter>: Precompile,
+	XtokensPrecompile<R>: Precompile,
 {
 	fn execute(
 		&self,
@@ -234,8 +260,10 @@ where
 			return None;
 		}
 
-		// ensure precompile is not paused
-		if PausedPrecompile::is_paused(address) {
+		let is_nft_mirror = nft_class_from_evm_address(address).is_some();
+
+		// ensure precompile is not paused, the NFT mirrors are paused along with the NFT precompile
+		if PausedPrecompile::is_paused(address) || (is_nft_mirror && PausedPrecompile::is_paused(NFT)) {
 			log::debug!(target: "evm", "Precompile {:?} is paused", address);
 			return Some(Err(PrecompileFailure::Revert {
 				exit_status: ExitRevert::Reverted,
@@ -287,6 +315,26 @@ where
 		} else if address == SHA3_512 {
 			Some(Sha3FIPS512::execute(input, target_gas, context, is_static))
 		}
+		// ERC721 mirrors of the NFT classes, can be called by any account
+		else if is_nft_mirror {
+			Some(ERC721Precompile::<R>::execute(input, target_gas, context, is_static))
+		}
+		// dispatches with the caller as the origin, can be called by any account
+		else if address == DISPATCH {
+			Some(DispatchPrecompile::<R, DispatchCallFilter>::execute(
+				input, target_gas, context, is_static,
+			))
+		}
+		// transfers from the caller, can be called by any account
+		else if address == XTOKENS {
+			Some(XtokensPrecompile::<R>::execute(input, target_gas, context, is_static))
+		}
+		// approves and transfers the currencies on behalf of the caller, can be called by any account
+		else if address == MULTI_CURRENCY && multicurrency::is_caller_action(input) {
+			Some(MultiCurrencyPrecompile::<R>::execute(
+				input, target_gas, context, is_static,
+			))
+		}
 		// Acala precompile
 		else {
 			if !SystemContractsFilter::is_allowed(context.caller) {
@@ -337,6 +385,10 @@ where
 				Some(IncentivesPrecompile::<R>::execute(
 					input, target_gas, context, is_static,
 				))
+			} else if address == STATE_QUERY {
+				Some(StateQueryPrecompile::<R>::execute(
+					input, target_gas, context, is_static,
+				))
 			} else {
 				None
 			}
@@ -350,7 +402,8 @@ where
 	}
 
 	fn is_precompile(&self, address: H160) -> bool {
-		self.set.contains(&address)
+		// the NFT mirrors are enabled along with the NFT precompile
+		self.set.contains(&address) || (self.set.contains(&NFT) && nft_class_from_evm_address(address).is_some())
 	}
 }
 
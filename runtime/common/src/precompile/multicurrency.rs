@@ -21,6 +21,7 @@ use super::{
 	target_gas_limit,
 };
 use crate::WeightToGas;
+use ethabi::Token;
 use frame_support::{
 	log,
 	traits::{Currency, Get},
@@ -29,24 +30,57 @@ use module_currencies::WeightInfo;
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
-	Context, ExitError, ExitRevert, ExitSucceed,
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
 };
-use module_support::Erc20InfoMapping as Erc20InfoMappingT;
+use module_evm_accounts::recover_signer;
+use module_support::{AddressMapping, Erc20InfoMapping as Erc20InfoMappingT};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use orml_traits::MultiCurrency as MultiCurrencyT;
 use primitives::{currency::DexShare, Balance, CurrencyId};
-use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
+/// `Transfer(address,address,uint256)`
+pub const TRANSFER_EVENT: [u8; 32] = *module_evm_utility_macro::keccak256!("Transfer(address,address,uint256)");
+/// `Approval(address,address,uint256)`
+pub const APPROVAL_EVENT: [u8; 32] = *module_evm_utility_macro::keccak256!("Approval(address,address,uint256)");
+
 /// The `MultiCurrency` impl precompile.
 ///
 ///
-/// `input` data starts with `action` and `currency_id`.
+/// `input` data starts with `action`. The currency is decoded from the caller, which must be a
+/// system contract, except for the actions of the caller itself which take the currency address
+/// and can be called by any account, see `is_caller_action`.
 ///
 /// Actions:
 /// - Query total issuance.
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Transfer. Rest `input` bytes: `from`, `to`, `amount`.
+/// - Approve. Rest `input` bytes: `owner`, `spender`, `amount`.
+/// - Query allowance. Rest `input` bytes: `owner`, `spender`.
+/// - Transfer from. Rest `input` bytes: `spender`, `from`, `to`, `amount`.
+/// - Permit. Rest `input` bytes: `owner`, `spender`, `value`, `deadline`, `v`, `r`, `s`.
+/// - Query permit nonce. Rest `input` bytes: `owner`.
+/// - Query EIP-712 domain separator.
+/// - Approve currency. Rest `input` bytes: `currency`, `spender`, `amount`. The caller is the owner.
+/// - Query currency allowance. Rest `input` bytes: `currency`, `owner`, `spender`.
+/// - Transfer currency from. Rest `input` bytes: `currency`, `from`, `to`, `amount`. The caller is
+///   the spender.
+///
+/// The ERC20 `Approval` and `Transfer` logs are emitted from the currency address by approve,
+/// permit and transfer from.
+///
+/// The amount of approve saturates at `Balance::max_value()`, which is the unlimited allowance
+/// and is returned as `uint256` max by the allowance query.
+///
+/// Permit follows EIP-2612, the EIP-712 domain of the currency is
+/// `EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)` with the
+/// currency name, version `1` and the currency address as the verifying contract.
 pub struct MultiCurrencyPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -59,15 +93,29 @@ pub enum Action {
 	QueryTotalIssuance = "totalSupply()",
 	QueryBalance = "balanceOf(address)",
 	Transfer = "transfer(address,address,uint256)",
+	Approve = "approve(address,address,uint256)",
+	QueryAllowance = "allowance(address,address)",
+	TransferFrom = "transferFrom(address,address,address,uint256)",
+	Permit = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+	QueryNonces = "nonces(address)",
+	QueryDomainSeparator = "DOMAIN_SEPARATOR()",
+	ApproveCurrency = "approveCurrency(address,address,uint256)",
+	QueryCurrencyAllowance = "currencyAllowance(address,address,address)",
+	TransferCurrencyFrom = "transferCurrencyFrom(address,address,address,uint256)",
 }
 
 impl<Runtime> Precompile for MultiCurrencyPrecompile<Runtime>
 where
-	Runtime:
-		module_currencies::Config + module_evm::Config + module_prices::Config + module_transaction_payment::Config,
+	Runtime: module_currencies::Config
+		+ module_evm::Config
+		+ module_evm_accounts::Config
+		+ module_prices::Config
+		+ module_transaction_payment::Config,
 	module_currencies::Pallet<Runtime>: MultiCurrencyT<Runtime::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
+	<Runtime as module_currencies::Config>::MultiCurrency:
+		MultiCurrencyT<Runtime::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
 {
-	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, _is_static: bool) -> PrecompileResult {
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
 		let input = Input::<
 			Action,
 			Runtime::AccountId,
@@ -75,12 +123,22 @@ where
 			Runtime::Erc20InfoMapping,
 		>::new(input, target_gas_limit(target_gas));
 
-		let currency_id =
-			Runtime::Erc20InfoMapping::decode_evm_address(context.caller).ok_or_else(|| PrecompileFailure::Revert {
+		let action = input.action()?;
+
+		let currency_address = match action {
+			Action::ApproveCurrency | Action::QueryCurrencyAllowance | Action::TransferCurrencyFrom => {
+				input.evm_address_at(1)?
+			}
+			_ => context.caller,
+		};
+
+		let currency_id = Runtime::Erc20InfoMapping::decode_evm_address(currency_address).ok_or_else(|| {
+			PrecompileFailure::Revert {
 				exit_status: ExitRevert::Reverted,
 				output: "invalid currency id".into(),
 				cost: target_gas_limit(target_gas).unwrap_or_default(),
-			})?;
+			}
+		})?;
 
 		let gas_cost = Pricer::<Runtime>::cost(&input, currency_id)?;
 
@@ -92,7 +150,21 @@ where
 			}
 		}
 
-		let action = input.action()?;
+		if is_static
+			&& matches!(
+				action,
+				Action::Approve
+					| Action::TransferFrom
+					| Action::Permit
+					| Action::ApproveCurrency
+					| Action::TransferCurrencyFrom
+			) {
+			return Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "cannot modify state in static call".into(),
+				cost: target_gas_limit(target_gas).unwrap_or_default(),
+			});
+		}
 
 		log::debug!(target: "evm", "multicurrency: currency id: {:?}", currency_id);
 
@@ -198,10 +270,269 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::Approve | Action::ApproveCurrency => {
+				let owner_address = if action == Action::Approve {
+					input.evm_address_at(1)?
+				} else {
+					context.caller
+				};
+				let owner = <Runtime as module_evm::Config>::AddressMapping::get_account_id(&owner_address);
+				let spender_address = input.evm_address_at(2)?;
+				let spender = input.account_id_at(2)?;
+				let value = input.u256_at(3)?;
+				let amount = value.try_into().unwrap_or(Balance::max_value());
+				log::debug!(target: "evm", "multicurrency: approve owner: {:?}, spender: {:?}, amount: {:?}", owner, spender, amount);
+
+				module_currencies::Pallet::<Runtime>::do_approve(currency_id, &owner, &spender, amount).map_err(
+					|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					},
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_log(currency_address, owner_address, spender_address, value)],
+				})
+			}
+			Action::QueryAllowance | Action::QueryCurrencyAllowance => {
+				let index = if action == Action::QueryAllowance { 1 } else { 2 };
+				let owner = input.account_id_at(index)?;
+				let spender = input.account_id_at(index + 1)?;
+				let allowance = module_currencies::Pallet::<Runtime>::allowance((currency_id, owner), spender);
+				log::debug!(target: "evm", "multicurrency: allowance: {:?}", allowance);
+
+				let allowance = if allowance == Balance::max_value() {
+					U256::max_value()
+				} else {
+					allowance.into()
+				};
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(allowance),
+					logs: Default::default(),
+				})
+			}
+			Action::TransferFrom | Action::TransferCurrencyFrom => {
+				let spender = if action == Action::TransferFrom {
+					input.account_id_at(1)?
+				} else {
+					<Runtime as module_evm::Config>::AddressMapping::get_account_id(&context.caller)
+				};
+				let from_address = input.evm_address_at(2)?;
+				let from = input.account_id_at(2)?;
+				let to_address = input.evm_address_at(3)?;
+				let to = input.account_id_at(3)?;
+				let amount = input.balance_at(4)?;
+				log::debug!(target: "evm", "multicurrency: transfer_from spender: {:?}, from: {:?}, to: {:?}, amount: {:?}", spender, from, to, amount);
+
+				module_currencies::Pallet::<Runtime>::do_transfer_from(currency_id, &spender, &from, &to, amount)
+					.map_err(|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![transfer_log(currency_address, from_address, to_address, amount.into())],
+				})
+			}
+			Action::Permit => {
+				let owner_address = input.evm_address_at(1)?;
+				let owner = input.account_id_at(1)?;
+				let spender_address = input.evm_address_at(2)?;
+				let spender = input.account_id_at(2)?;
+				let value = input.u256_at(3)?;
+				let deadline = input.u256_at(4)?;
+				let v = u8::try_from(input.u32_at(5)?).map_err(|_| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid signature".into(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+				let r = input.bytes_at(6, 32)?;
+				let s = input.bytes_at(7, 32)?;
+				log::debug!(target: "evm", "multicurrency: permit owner: {:?}, spender: {:?}, value: {:?}, deadline: {:?}", owner, spender, value, deadline);
+
+				let now: u128 = pallet_timestamp::Pallet::<Runtime>::get().unique_saturated_into();
+				if deadline < U256::from(now / 1000) {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "permit expired".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
+				let domain_separator = domain_separator::<Runtime>(currency_id, context.caller).ok_or_else(|| {
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "Get name failed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				})?;
+				let nonce = module_currencies::Pallet::<Runtime>::permit_nonces(currency_id, &owner);
+				let digest = permit_digest(
+					domain_separator,
+					owner_address,
+					spender_address,
+					value,
+					nonce.into(),
+					deadline,
+				);
+
+				let mut signature = [0u8; 65];
+				signature[0..32].copy_from_slice(&r);
+				signature[32..64].copy_from_slice(&s);
+				signature[64] = v;
+				if recover_signer(&signature, &digest) != Some(owner_address) {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "invalid signature".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
+				let amount = value.try_into().unwrap_or(Balance::max_value());
+				module_currencies::Pallet::<Runtime>::do_permit(currency_id, &owner, &spender, amount).map_err(
+					|e| PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					},
+				)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![approval_log(currency_address, owner_address, spender_address, value)],
+				})
+			}
+			Action::QueryNonces => {
+				let owner = input.account_id_at(1)?;
+				let nonce = module_currencies::Pallet::<Runtime>::permit_nonces(currency_id, &owner);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(nonce),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryDomainSeparator => {
+				let domain_separator = domain_separator::<Runtime>(currency_id, context.caller).ok_or_else(|| {
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "Get name failed".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_fixed_bytes(&domain_separator),
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
 
+/// Whether `input` is an action of the caller itself, which can be called by any account.
+pub fn is_caller_action(input: &[u8]) -> bool {
+	input
+		.get(0..4)
+		.and_then(|selector| Action::try_from(u32::from_be_bytes(selector.try_into().ok()?)).ok())
+		.map_or(false, |action| {
+			matches!(
+				action,
+				Action::ApproveCurrency | Action::QueryCurrencyAllowance | Action::TransferCurrencyFrom
+			)
+		})
+}
+
+fn transfer_log(address: H160, from: H160, to: H160, value: U256) -> Log {
+	Log {
+		address,
+		topics: vec![H256::from(TRANSFER_EVENT), H256::from(from), H256::from(to)],
+		data: {
+			let mut buf = [0u8; 32];
+			value.to_big_endian(&mut buf);
+			buf.to_vec()
+		},
+	}
+}
+
+fn approval_log(address: H160, owner: H160, spender: H160, value: U256) -> Log {
+	Log {
+		address,
+		topics: vec![H256::from(APPROVAL_EVENT), H256::from(owner), H256::from(spender)],
+		data: {
+			let mut buf = [0u8; 32];
+			value.to_big_endian(&mut buf);
+			buf.to_vec()
+		},
+	}
+}
+
+/// The EIP-712 domain separator of the currency at `verifying_contract`.
+fn domain_separator<Runtime>(currency_id: CurrencyId, verifying_contract: H160) -> Option<[u8; 32]>
+where
+	Runtime: module_evm::Config + module_prices::Config,
+{
+	let name = Runtime::Erc20InfoMapping::name(currency_id)?;
+
+	Some(keccak_256(&ethabi::encode(&[
+		Token::FixedBytes(
+			module_evm_utility_macro::keccak256!(
+				"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+			)
+			.to_vec(),
+		),
+		Token::FixedBytes(keccak_256(&name).to_vec()),
+		Token::FixedBytes(module_evm_utility_macro::keccak256!("1").to_vec()),
+		Token::Uint(module_evm::Pallet::<Runtime>::chain_id().into()),
+		Token::Address(verifying_contract),
+	])))
+}
+
+/// The EIP-712 digest of the EIP-2612 permit to be signed by the owner.
+fn permit_digest(
+	domain_separator: [u8; 32],
+	owner: H160,
+	spender: H160,
+	value: U256,
+	nonce: U256,
+	deadline: U256,
+) -> [u8; 32] {
+	let struct_hash = keccak_256(&ethabi::encode(&[
+		Token::FixedBytes(
+			module_evm_utility_macro::keccak256!(
+				"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+			)
+			.to_vec(),
+		),
+		Token::Address(owner),
+		Token::Address(spender),
+		Token::Uint(value),
+		Token::Uint(nonce),
+		Token::Uint(deadline),
+	]));
+
+	let mut msg = b"\x19\x01".to_vec();
+	msg.extend_from_slice(&domain_separator);
+	msg.extend_from_slice(&struct_hash);
+	keccak_256(&msg)
+}
+
 struct Pricer<R>(PhantomData<R>);
 
 impl<Runtime> Pricer<Runtime>
@@ -210,6 +541,8 @@ where
 		module_currencies::Config + module_evm::Config + module_prices::Config + module_transaction_payment::Config,
 {
 	const BASE_COST: u64 = 200;
+	/// The gas of the `ECRecover` precompile.
+	const ECRECOVER_COST: u64 = 3_000;
 
 	fn cost(
 		input: &Input<
@@ -251,6 +584,59 @@ where
 
 				cost.saturating_add(WeightToGas::convert(weight))
 			}
+			Action::Approve | Action::ApproveCurrency => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// Currencies::Allowances (r: 1, w: 1), Balances::Reserves (r: 1, w: 1), System::Account (r: 1, w: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads_writes(3, 3),
+				))
+			}
+			Action::QueryAllowance | Action::QueryCurrencyAllowance => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// Currencies::Allowances (r: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+				))
+			}
+			Action::TransferFrom | Action::TransferCurrencyFrom => {
+				let cost = InputPricer::<Runtime>::read_accounts(3);
+
+				// transfer weight
+				let weight = if currency_id == <Runtime as module_transaction_payment::Config>::NativeCurrencyId::get()
+				{
+					<Runtime as module_currencies::Config>::WeightInfo::transfer_native_currency()
+				} else {
+					<Runtime as module_currencies::Config>::WeightInfo::transfer_non_native_currency()
+				};
+				// Currencies::Allowances (r: 1, w: 1), Balances::Reserves (r: 1, w: 1), System::Account (r: 1, w: 1)
+				let weight =
+					weight.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(3, 3));
+
+				cost.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::Permit => {
+				let cost = InputPricer::<Runtime>::read_accounts(2);
+				// Currencies::PermitNonces (r: 1, w: 1), Currencies::Allowances (r: 1, w: 1), EVM::ChainId (r: 1),
+				// Balances::Reserves (r: 1, w: 1), System::Account (r: 1, w: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads_writes(5, 4);
+
+				cost.saturating_add(Self::erc20_info(currency_id))
+					.saturating_add(Self::ECRECOVER_COST)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::QueryNonces => {
+				let cost = InputPricer::<Runtime>::read_accounts(1);
+				// Currencies::PermitNonces (r: 1)
+				cost.saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+				))
+			}
+			Action::QueryDomainSeparator => {
+				// EVM::ChainId (r: 1)
+				Self::erc20_info(currency_id).saturating_add(WeightToGas::convert(
+					<Runtime as frame_system::Config>::DbWeight::get().reads(1),
+				))
+			}
 		};
 
 		Ok(Self::BASE_COST.saturating_add(read_currency).saturating_add(cost))
@@ -280,12 +666,17 @@ where
 mod tests {
 	use super::*;
 
-	use crate::precompile::mock::{
-		aca_evm_address, alice, ausd_evm_address, bob, erc20_address_not_exists, lp_aca_ausd_evm_address, new_test_ext,
-		Balances, Test,
+	use crate::precompile::{
+		mock::{
+			aca_evm_address, alice, alice_evm_addr, ausd_evm_address, bob, bob_evm_addr, erc20_address_not_exists,
+			lp_aca_ausd_evm_address, new_test_ext, Balances, PrecompilesValue, Test, Timestamp, ACA,
+		},
+		MULTI_CURRENCY,
 	};
-	use frame_support::assert_noop;
+	use frame_support::{assert_noop, assert_ok};
 	use hex_literal::hex;
+	use module_evm::runner::state::PrecompileSet;
+	use sp_core::Pair;
 
 	type MultiCurrencyPrecompile = crate::MultiCurrencyPrecompile<Test>;
 
@@ -558,4 +949,406 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn approve_and_transfer_from_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: aca_evm_address(),
+				apparent_value: Default::default(),
+			};
+
+			// approve(address,address,uint256) -> 0xe1f21c67
+			// owner
+			// spender
+			// amount
+			let input = hex! {"
+				e1f21c67
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 1000000000000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000002
+			"};
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				resp.logs,
+				vec![approval_log(
+					aca_evm_address(),
+					alice_evm_addr(),
+					bob_evm_addr(),
+					2.into()
+				)]
+			);
+
+			// allowance(address,address) -> 0xdd62ed3e
+			// owner
+			// spender
+			let allowance_input = hex! {"
+				dd62ed3e
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 1000000000000000000000000000000000000002
+			"};
+			let resp = MultiCurrencyPrecompile::execute(&allowance_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(2u128));
+
+			// transferFrom(address,address,address,uint256) -> 0x15dacbea
+			// spender
+			// from
+			// to
+			// amount
+			let input = hex! {"
+				15dacbea
+				000000000000000000000000 1000000000000000000000000000000000000002
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 1000000000000000000000000000000000000002
+				00000000000000000000000000000000 00000000000000000000000000000001
+			"};
+
+			let from_balance = Balances::free_balance(alice());
+			let to_balance = Balances::free_balance(bob());
+
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				resp.logs,
+				vec![transfer_log(
+					aca_evm_address(),
+					alice_evm_addr(),
+					bob_evm_addr(),
+					1.into()
+				)]
+			);
+			assert_eq!(Balances::free_balance(alice()), from_balance - 1);
+			assert_eq!(Balances::free_balance(bob()), to_balance + 1);
+
+			let resp = MultiCurrencyPrecompile::execute(&allowance_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(1u128));
+
+			assert_ok!(MultiCurrencyPrecompile::execute(&input, None, &context, false));
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&input, Some(100_000), &context, false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "InsufficientAllowance".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				}
+			);
+
+			// unlimited allowance
+			let input = hex! {"
+				e1f21c67
+				000000000000000000000000 1000000000000000000000000000000000000001
+				000000000000000000000000 1000000000000000000000000000000000000002
+				ffffffffffffffffffffffffffffffff ffffffffffffffffffffffffffffffff
+			"};
+			assert_ok!(MultiCurrencyPrecompile::execute(&input, None, &context, false));
+			let resp = MultiCurrencyPrecompile::execute(&allowance_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(U256::max_value()));
+		})
+	}
+
+	#[test]
+	fn permit_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: aca_evm_address(),
+				apparent_value: Default::default(),
+			};
+
+			let pair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+			let owner = recover_signer(&pair.sign_prehashed(&[0u8; 32]).0, &[0u8; 32]).unwrap();
+
+			// DOMAIN_SEPARATOR() -> 0x3644e515
+			let resp = MultiCurrencyPrecompile::execute(&hex!("3644e515"), None, &context, false).unwrap();
+			let domain_separator = keccak_256(&ethabi::encode(&[
+				Token::FixedBytes(
+					keccak_256(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+						.to_vec(),
+				),
+				Token::FixedBytes(
+					keccak_256(&<Test as module_prices::Config>::Erc20InfoMapping::name(ACA).unwrap()).to_vec(),
+				),
+				Token::FixedBytes(keccak_256(b"1").to_vec()),
+				Token::Uint(595.into()),
+				Token::Address(aca_evm_address()),
+			]));
+			assert_eq!(resp.output, domain_separator.to_vec());
+
+			// nonces(address) -> 0x7ecebe00
+			// owner
+			let mut nonces_input = hex!("7ecebe00").to_vec();
+			nonces_input.extend_from_slice(&ethabi::encode(&[Token::Address(owner)]));
+			let resp = MultiCurrencyPrecompile::execute(&nonces_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(0u64));
+
+			let permit_input = |deadline: u64| {
+				let struct_hash = keccak_256(&ethabi::encode(&[
+					Token::FixedBytes(
+						keccak_256(
+							b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+						)
+						.to_vec(),
+					),
+					Token::Address(owner),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(10.into()),
+					Token::Uint(0.into()),
+					Token::Uint(deadline.into()),
+				]));
+				let mut msg = b"\x19\x01".to_vec();
+				msg.extend_from_slice(&domain_separator);
+				msg.extend_from_slice(&struct_hash);
+				let signature = pair.sign_prehashed(&keccak_256(&msg)).0;
+
+				// permit(address,address,uint256,uint256,uint8,bytes32,bytes32) -> 0xd505accf
+				// owner
+				// spender
+				// value
+				// deadline
+				// v
+				// r
+				// s
+				let mut input = hex!("d505accf").to_vec();
+				input.extend_from_slice(&ethabi::encode(&[
+					Token::Address(owner),
+					Token::Address(bob_evm_addr()),
+					Token::Uint(10.into()),
+					Token::Uint(deadline.into()),
+					Token::Uint((signature[64] + 27).into()),
+					Token::FixedBytes(signature[0..32].to_vec()),
+					Token::FixedBytes(signature[32..64].to_vec()),
+				]));
+				input
+			};
+
+			Timestamp::set_timestamp(2_000_000);
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&permit_input(1_999), Some(100_000), &context, false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "permit expired".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				}
+			);
+
+			let resp = MultiCurrencyPrecompile::execute(&permit_input(2_000), None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				resp.logs,
+				vec![approval_log(aca_evm_address(), owner, bob_evm_addr(), 10.into())]
+			);
+
+			let resp = MultiCurrencyPrecompile::execute(&nonces_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(1u64));
+
+			// allowance(address,address) -> 0xdd62ed3e
+			// owner
+			// spender
+			let mut input = hex!("dd62ed3e").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(owner),
+				Token::Address(bob_evm_addr()),
+			]));
+			let resp = MultiCurrencyPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(10u128));
+
+			// replay
+			assert_noop!(
+				MultiCurrencyPrecompile::execute(&permit_input(2_000), Some(100_000), &context, false),
+				PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid signature".into(),
+					cost: target_gas_limit(Some(100_000)).unwrap(),
+				}
+			);
+		})
+	}
+
+	#[test]
+	fn caller_actions_work() {
+		new_test_ext().execute_with(|| {
+			let execute = |input: &[u8], caller: H160| {
+				let context = Context {
+					address: MULTI_CURRENCY,
+					caller,
+					apparent_value: Default::default(),
+				};
+				PrecompilesValue::get()
+					.execute(MULTI_CURRENCY, input, Some(100_000_000), &context, false)
+					.unwrap()
+			};
+
+			// the actions of the currency contracts can't be called by an account
+			// approve(address,address,uint256) -> 0xe1f21c67
+			// owner
+			// spender
+			// amount
+			let mut input = hex!("e1f21c67").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+			assert_eq!(
+				execute(&input, alice_evm_addr()),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "NoPermission".into(),
+					cost: 100_000_000,
+				})
+			);
+
+			// approveCurrency(address,address,uint256) -> 0xef81ca6d
+			// currency
+			// spender
+			// amount
+			let mut input = hex!("ef81ca6d").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(aca_evm_address()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+			let resp = execute(&input, alice_evm_addr()).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				resp.logs,
+				vec![approval_log(
+					aca_evm_address(),
+					alice_evm_addr(),
+					bob_evm_addr(),
+					2.into()
+				)]
+			);
+
+			// currencyAllowance(address,address,address) -> 0x1f19d0f3
+			// currency
+			// owner
+			// spender
+			let mut allowance_input = hex!("1f19d0f3").to_vec();
+			allowance_input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(aca_evm_address()),
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+			]));
+			let resp = execute(&allowance_input, bob_evm_addr()).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(2u128));
+
+			// transferCurrencyFrom(address,address,address,uint256) -> 0x350efdd5
+			// currency
+			// from
+			// to
+			// amount
+			let mut input = hex!("350efdd5").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(aca_evm_address()),
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+
+			// bob transfers with the allowance
+			let from_balance = Balances::free_balance(alice());
+			let to_balance = Balances::free_balance(bob());
+			let resp = execute(&input, bob_evm_addr()).unwrap();
+			assert_eq!(
+				resp.logs,
+				vec![transfer_log(
+					aca_evm_address(),
+					alice_evm_addr(),
+					bob_evm_addr(),
+					2.into()
+				)]
+			);
+			assert_eq!(Balances::free_balance(alice()), from_balance - 2);
+			assert_eq!(Balances::free_balance(bob()), to_balance + 2);
+
+			let resp = execute(&allowance_input, bob_evm_addr()).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(0u128));
+			assert_eq!(
+				execute(&input, bob_evm_addr()),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "InsufficientAllowance".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+		})
+	}
+
+	#[test]
+	fn state_changing_actions_in_static_call_fail() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: aca_evm_address(),
+				apparent_value: Default::default(),
+			};
+			let static_call_fails = |input: &[u8]| {
+				assert_eq!(
+					MultiCurrencyPrecompile::execute(input, Some(100_000), &context, true),
+					Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "cannot modify state in static call".into(),
+						cost: target_gas_limit(Some(100_000)).unwrap(),
+					})
+				);
+			};
+
+			// approve(address,address,uint256) -> 0xe1f21c67
+			let mut input = hex!("e1f21c67").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+			static_call_fails(&input);
+
+			// transferFrom(address,address,address,uint256) -> 0x15dacbea
+			let mut input = hex!("15dacbea").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(bob_evm_addr()),
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(1.into()),
+			]));
+			static_call_fails(&input);
+
+			// permit(address,address,uint256,uint256,uint8,bytes32,bytes32) -> 0xd505accf
+			let mut input = hex!("d505accf").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+				Token::Uint(U256::max_value()),
+				Token::Uint(27.into()),
+				Token::FixedBytes(vec![0u8; 32]),
+				Token::FixedBytes(vec![0u8; 32]),
+			]));
+			static_call_fails(&input);
+
+			// approveCurrency(address,address,uint256) -> 0xef81ca6d
+			let mut input = hex!("ef81ca6d").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(aca_evm_address()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+			static_call_fails(&input);
+
+			// transferCurrencyFrom(address,address,address,uint256) -> 0x350efdd5
+			let mut input = hex!("350efdd5").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(aca_evm_address()),
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(2.into()),
+			]));
+			static_call_fails(&input);
+
+			// nothing is approved
+			assert_eq!(module_currencies::Pallet::<Test>::allowance((ACA, alice()), bob()), 0);
+		})
+	}
 }
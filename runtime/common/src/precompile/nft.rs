@@ -20,23 +20,37 @@ use super::{
 	input::{Input, InputT, Output},
 	target_gas_limit,
 };
+use crate::WeightToGas;
+use codec::Decode;
 use frame_support::{
 	log,
-	traits::tokens::nonfungibles::{Inspect, Transfer},
+	traits::{
+		tokens::nonfungibles::{Inspect, Transfer},
+		Get,
+	},
 };
+use hex_literal::hex;
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
-	Context, ExitError, ExitRevert, ExitSucceed,
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
 };
+use module_nft::WeightInfo;
 use module_support::AddressMapping;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use orml_traits::InspectExtended;
-use primitives::nft::NFTBalance;
-use sp_core::H160;
-use sp_runtime::RuntimeDebug;
+use primitives::{
+	evm::nft_class_to_evm_address,
+	nft::{Attributes, NFTBalance, Properties},
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::Convert, RuntimeDebug};
 use sp_std::{marker::PhantomData, prelude::*};
 
+/// `Transfer(address,address,uint256)`, the ERC-721 transfer event. It's emitted from the ERC721
+/// mirror of the class so the logs of the classes don't collide.
+pub const TRANSFER_EVENT: [u8; 32] = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
 /// The `NFT` impl precompile.
 ///
 /// `input` data starts with `action`.
@@ -45,6 +59,19 @@ use sp_std::{marker::PhantomData, prelude::*};
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Query owner. Rest `input` bytes: `class_id`, `token_id`.
 /// - Transfer. Rest `input`bytes: `from`, `to`, `class_id`, `token_id`.
+/// - Create class. Rest `input` bytes: `who`, `name`, `symbol`, `properties`, `metadata`. The
+///   `name` and `symbol` are stored as the class attributes read by the ERC721 mirror.
+/// - Mint. Rest `input` bytes: `who`, `to`, `class_id`, `quantity`, `metadata_len`, `metadata`.
+/// - Burn. Rest `input` bytes: `who`, `class_id`, `token_id`.
+/// - Burn with remark. Rest `input` bytes: `who`, `class_id`, `token_id`, `remark_len`, `remark`.
+/// - Destroy class. Rest `input` bytes: `who`, `class_id`, `dest`.
+/// - Query token metadata. Rest `input` bytes: `class_id`, `token_id`.
+/// - Query class metadata. Rest `input` bytes: `class_id`.
+/// - Query class attribute. Rest `input` bytes: `class_id`, `key_len`, `key`.
+/// - Query token attribute. Rest `input` bytes: `class_id`, `token_id`, `key_len`, `key`.
+///
+/// `who` of the class actions must be the owner of the class or the proxy added at class
+/// creation.
 pub struct NFTPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -54,11 +81,23 @@ pub enum Action {
 	QueryBalance = "balanceOf(address)",
 	QueryOwner = "ownerOf(uint256,uint256)",
 	Transfer = "transfer(address,address,uint256,uint256)",
+	CreateClass = "createClass(address,string,string,uint8,bytes)",
+	Mint = "mint(address,address,uint256,uint32,bytes)",
+	Burn = "burn(address,uint256,uint256)",
+	BurnWithRemark = "burnWithRemark(address,uint256,uint256,bytes)",
+	DestroyClass = "destroyClass(address,uint256,address)",
+	QueryTokenURI = "tokenURI(uint256,uint256)",
+	QueryClassURI = "classURI(uint256)",
+	QueryClassAttribute = "getClassAttribute(uint256,bytes)",
+	QueryTokenAttribute = "getTokenAttribute(uint256,uint256,bytes)",
 }
 
 impl<Runtime> Precompile for NFTPrecompile<Runtime>
 where
-	Runtime: module_evm::Config + module_prices::Config + module_nft::Config,
+	Runtime: module_evm::Config
+		+ module_prices::Config
+		+ module_nft::Config
+		+ orml_nft::Config<ClassId = u32, TokenId = u64>,
 	module_nft::Pallet<Runtime>: InspectExtended<Runtime::AccountId, Balance = NFTBalance>
 		+ Inspect<Runtime::AccountId, ItemId = u64, CollectionId = u32>
 		+ Transfer<Runtime::AccountId>,
@@ -81,6 +120,12 @@ where
 
 		let action = input.action()?;
 
+		let revert = |e: sp_runtime::DispatchError| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: Into::<&str>::into(e).as_bytes().to_vec(),
+			cost: target_gas_limit(target_gas).unwrap_or_default(),
+		};
+
 		match action {
 			Action::QueryBalance => {
 				let who = input.account_id_at(1)?;
@@ -125,24 +170,231 @@ where
 
 				log::debug!(target: "evm", "nft: transfer from: {:?}, to: {:?}, class_id: {:?}, token_id: {:?}", from, to, class_id, token_id);
 
+				if module_nft::Pallet::<Runtime>::owner(&class_id, &token_id).as_ref() != Some(&from) {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "NoPermission".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					});
+				}
+
 				<module_nft::Pallet<Runtime> as Transfer<Runtime::AccountId>>::transfer(&class_id, &token_id, &to)
-					.map_err(|e| PrecompileFailure::Revert {
+					.map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![transfer_log(
+						input.evm_address_at(1)?,
+						input.evm_address_at(2)?,
+						class_id,
+						token_id,
+					)],
+				})
+			}
+			Action::CreateClass => {
+				let who = input.account_id_at(1)?;
+				let name = dynamic_bytes_at(&input, 2)?;
+				let symbol = dynamic_bytes_at(&input, 3)?;
+				let properties = u8::try_from(input.u32_at(4)?)
+					.ok()
+					.and_then(|bits| Properties::decode(&mut &[bits][..]).ok())
+					.ok_or_else(|| PrecompileFailure::Revert {
 						exit_status: ExitRevert::Reverted,
-						output: Into::<&str>::into(e).as_bytes().to_vec(),
+						output: "invalid properties".into(),
 						cost: target_gas_limit(target_gas).unwrap_or_default(),
 					})?;
+				let metadata = dynamic_bytes_at(&input, 5)?;
+
+				log::debug!(target: "evm", "nft: create_class who: {:?}, name: {:?}, symbol: {:?}, properties: {:?}, metadata: {:?}", who, name, symbol, properties, metadata);
+
+				let attributes = Attributes::from([(b"name".to_vec(), name), (b"symbol".to_vec(), symbol)]);
+				let class_id = module_nft::Pallet::<Runtime>::do_create_class(&who, metadata, properties, attributes)
+					.map_err(revert)?;
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
-					cost: 0,
+					cost: gas_cost,
+					output: Output::encode_uint(class_id),
+					logs: Default::default(),
+				})
+			}
+			Action::Mint => {
+				let who = input.account_id_at(1)?;
+				let to = input.account_id_at(2)?;
+				let class_id = input.u32_at(3)?;
+				let quantity = input.u32_at(4)?;
+				// solidity abi encode bytes will add an length at input[5]
+				let metadata_len = input.u32_at(6)?;
+				let metadata = input.bytes_at(7, metadata_len as usize)?;
+
+				log::debug!(target: "evm", "nft: mint who: {:?}, to: {:?}, class_id: {:?}, quantity: {:?}, metadata: {:?}", who, to, class_id, quantity, metadata);
+
+				let class_owner =
+					module_nft::Pallet::<Runtime>::class_owner_for_operator(&who, class_id).map_err(revert)?;
+				let token_ids = module_nft::Pallet::<Runtime>::do_mint(
+					&class_owner,
+					&to,
+					class_id,
+					metadata,
+					Default::default(),
+					quantity,
+				)
+				.map_err(revert)?;
+
+				let to_address = input.evm_address_at(2)?;
+				let logs = token_ids
+					.iter()
+					.map(|token_id| transfer_log(H160::default(), to_address, class_id, *token_id))
+					.collect();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint_array(token_ids),
+					logs,
+				})
+			}
+			Action::Burn | Action::BurnWithRemark => {
+				let who = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+				let token_id = input.u64_at(3)?;
+				let remark = if action == Action::BurnWithRemark {
+					// solidity abi encode bytes will add an length at input[4]
+					let remark_len = input.u32_at(5)?;
+					Some(input.bytes_at(6, remark_len as usize)?)
+				} else {
+					None
+				};
+
+				log::debug!(target: "evm", "nft: burn who: {:?}, class_id: {:?}, token_id: {:?}, remark: {:?}", who, class_id, token_id, remark);
+
+				module_nft::Pallet::<Runtime>::do_burn(who, (class_id, token_id), remark).map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![transfer_log(
+						input.evm_address_at(1)?,
+						H160::default(),
+						class_id,
+						token_id,
+					)],
+				})
+			}
+			Action::DestroyClass => {
+				let who = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+				let dest = input.account_id_at(3)?;
+
+				log::debug!(target: "evm", "nft: destroy_class who: {:?}, class_id: {:?}, dest: {:?}", who, class_id, dest);
+
+				let class_owner =
+					module_nft::Pallet::<Runtime>::class_owner_for_operator(&who, class_id).map_err(revert)?;
+				module_nft::Pallet::<Runtime>::do_destroy_class(&class_owner, class_id, &dest).map_err(revert)?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
 					output: vec![],
 					logs: Default::default(),
 				})
 			}
+			Action::QueryTokenURI => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+
+				let metadata = orml_nft::Pallet::<Runtime>::tokens(class_id, token_id)
+					.map(|token_info| token_info.metadata.into_inner())
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&metadata),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryClassURI => {
+				let class_id = input.u32_at(1)?;
+
+				let metadata = orml_nft::Pallet::<Runtime>::classes(class_id)
+					.map(|class_info| class_info.metadata.into_inner())
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&metadata),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryClassAttribute => {
+				let class_id = input.u32_at(1)?;
+				// solidity abi encode bytes will add an length at input[2]
+				let key_len = input.u32_at(3)?;
+				let key = input.bytes_at(4, key_len as usize)?;
+
+				let value = orml_nft::Pallet::<Runtime>::classes(class_id)
+					.and_then(|class_info| class_info.data.attributes.get(&key).cloned())
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&value),
+					logs: Default::default(),
+				})
+			}
+			Action::QueryTokenAttribute => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+				// solidity abi encode bytes will add an length at input[3]
+				let key_len = input.u32_at(4)?;
+				let key = input.bytes_at(5, key_len as usize)?;
+
+				let value = orml_nft::Pallet::<Runtime>::tokens(class_id, token_id)
+					.and_then(|token_info| token_info.data.attributes.get(&key).cloned())
+					.unwrap_or_default();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bytes(&value),
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
 
+/// Read the dynamic `bytes` or `string` param at `index`, the param is the offset of the length
+/// and the data.
+fn dynamic_bytes_at<I: InputT<Error = PrecompileFailure>>(
+	input: &I,
+	index: usize,
+) -> Result<Vec<u8>, PrecompileFailure> {
+	let offset = input.u32_at(index)? as usize;
+	let len_index = offset / 32 + 1;
+	let len = input.u32_at(len_index)?;
+	input.bytes_at(len_index + 1, len as usize)
+}
+
+fn transfer_log(from: H160, to: H160, class_id: u32, token_id: u64) -> Log {
+	Log {
+		address: nft_class_to_evm_address(class_id),
+		topics: vec![
+			H256::from(TRANSFER_EVENT),
+			H256::from(from),
+			H256::from(to),
+			H256::from_uint(&U256::from(token_id)),
+		],
+		data: vec![],
+	}
+}
+
 pub struct Pricer<R>(PhantomData<R>);
 
 impl<Runtime> Pricer<Runtime>
@@ -154,8 +406,321 @@ where
 	fn cost(
 		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
 	) -> Result<u64, PrecompileFailure> {
-		let _action = input.action()?;
-		// TODO: gas cost
-		Ok(Self::BASE_COST)
+		let action = input.action()?;
+
+		let weight = match action {
+			// TODO: gas cost
+			Action::QueryBalance | Action::QueryOwner => return Ok(Self::BASE_COST),
+			Action::Transfer => <Runtime as module_nft::Config>::WeightInfo::transfer(),
+			Action::CreateClass => <Runtime as module_nft::Config>::WeightInfo::create_class(),
+			Action::Mint => {
+				let quantity = input.u32_at(4)?;
+				<Runtime as module_nft::Config>::WeightInfo::mint(quantity)
+			}
+			Action::Burn => <Runtime as module_nft::Config>::WeightInfo::burn(),
+			Action::BurnWithRemark => {
+				let remark_len = input.u32_at(5)?;
+				<Runtime as module_nft::Config>::WeightInfo::burn_with_remark(remark_len)
+			}
+			Action::DestroyClass => <Runtime as module_nft::Config>::WeightInfo::destroy_class(),
+			// OrmlNFT::Tokens (r: 1)
+			Action::QueryTokenURI | Action::QueryTokenAttribute => {
+				<Runtime as frame_system::Config>::DbWeight::get().reads(1)
+			}
+			// OrmlNFT::Classes (r: 1)
+			Action::QueryClassURI | Action::QueryClassAttribute => {
+				<Runtime as frame_system::Config>::DbWeight::get().reads(1)
+			}
+		};
+
+		Ok(Self::BASE_COST.saturating_add(WeightToGas::convert(weight)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::{
+		mock::{alice, alice_evm_addr, bob, bob_evm_addr, new_test_ext, Currencies, NFTModule, Origin, Test, ACA},
+		NFT,
+	};
+	use ethabi::Token;
+	use frame_support::assert_ok;
+	use primitives::nft::ClassProperty;
+
+	type NFTPrecompile = super::NFTPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: NFT,
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	fn create_class() -> u32 {
+		// createClass(address,string,string,uint8,bytes) -> 0xd2063566
+		// who
+		// name
+		// symbol
+		// properties: Transferable | Burnable | Mintable
+		// metadata
+		let mut input = hex!("d2063566").to_vec();
+		input.extend_from_slice(&ethabi::encode(&[
+			Token::Address(alice_evm_addr()),
+			Token::String("Acala NFT".into()),
+			Token::String("ANFT".into()),
+			Token::Uint(7.into()),
+			Token::Bytes(b"class".to_vec()),
+		]));
+
+		let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+		assert_eq!(res.exit_status, ExitSucceed::Returned);
+		let class_id = U256::from_big_endian(&res.output).as_u32();
+
+		// the class owner pays the deposits of the tokens
+		let class_owner = orml_nft::Pallet::<Test>::classes(class_id).unwrap().owner;
+		assert_ok!(Currencies::update_balance(Origin::root(), class_owner, ACA, 100_000));
+		class_id
+	}
+
+	fn mint_input(class_id: u32, quantity: u32) -> Vec<u8> {
+		// mint(address,address,uint256,uint32,bytes) -> 0x44577d81
+		// who
+		// to
+		// class_id
+		// quantity
+		// metadata
+		let mut input = hex!("44577d81").to_vec();
+		input.extend_from_slice(&ethabi::encode(&[
+			Token::Address(alice_evm_addr()),
+			Token::Address(alice_evm_addr()),
+			Token::Uint(class_id.into()),
+			Token::Uint(quantity.into()),
+			Token::Bytes(b"token".to_vec()),
+		]));
+		input
+	}
+
+	#[test]
+	fn create_class_and_mint_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class();
+			assert_eq!(
+				orml_nft::Pallet::<Test>::classes(class_id).unwrap().data.properties,
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mintable)
+			);
+			assert_eq!(
+				orml_nft::Pallet::<Test>::classes(class_id).unwrap().data.attributes,
+				Attributes::from([
+					(b"name".to_vec(), b"Acala NFT".to_vec()),
+					(b"symbol".to_vec(), b"ANFT".to_vec())
+				])
+			);
+
+			let res = NFTPrecompile::execute(&mint_input(class_id, 2), None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, Output::encode_uint_array(vec![0u64, 1u64]));
+			assert_eq!(
+				res.logs,
+				vec![
+					transfer_log(H160::default(), alice_evm_addr(), class_id, 0),
+					transfer_log(H160::default(), alice_evm_addr(), class_id, 1),
+				]
+			);
+			assert_eq!(NFTModule::balance(&alice()), 2);
+
+			// tokenURI(uint256,uint256) -> 0x92cb829d
+			// class_id
+			// token_id
+			let mut input = hex!("92cb829d").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(class_id.into()), Token::Uint(1.into())]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"token"));
+
+			// classURI(uint256) -> 0x0d95fed4
+			// class_id
+			let mut input = hex!("0d95fed4").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(class_id.into())]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"class"));
+
+			// not the class operator
+			let mut input = hex!("44577d81").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(H160::from(hex!("1000000000000000000000000000000000000002"))),
+				Token::Address(alice_evm_addr()),
+				Token::Uint(class_id.into()),
+				Token::Uint(1.into()),
+				Token::Bytes(vec![]),
+			]));
+			assert_eq!(
+				NFTPrecompile::execute(&input, Some(100_000_000), &context(), false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "NoPermission".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class();
+			assert_ok!(NFTPrecompile::execute(
+				&mint_input(class_id, 1),
+				None,
+				&context(),
+				false
+			));
+
+			// transfer(address,address,uint256,uint256) -> 0x0411b252
+			// from
+			// to
+			// class_id
+			// token_id
+			let mut input = hex!("0411b252").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(bob_evm_addr()),
+				Token::Uint(class_id.into()),
+				Token::Uint(0.into()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.cost,
+				Pricer::<Test>::BASE_COST + WeightToGas::convert(<Test as module_nft::Config>::WeightInfo::transfer())
+			);
+			// the log is emitted from the ERC721 mirror of the class
+			assert_eq!(
+				res.logs,
+				vec![transfer_log(alice_evm_addr(), bob_evm_addr(), class_id, 0)]
+			);
+			assert_eq!(res.logs[0].address, nft_class_to_evm_address(class_id));
+			assert_eq!(NFTModule::balance(&bob()), 1);
+		});
+	}
+
+	#[test]
+	fn burn_and_destroy_class_works() {
+		new_test_ext().execute_with(|| {
+			let class_id = create_class();
+			assert_ok!(NFTPrecompile::execute(
+				&mint_input(class_id, 2),
+				None,
+				&context(),
+				false
+			));
+
+			// burn(address,uint256,uint256) -> 0xf5298aca
+			// who
+			// class_id
+			// token_id
+			let mut input = hex!("f5298aca").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Uint(class_id.into()),
+				Token::Uint(0.into()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.logs,
+				vec![transfer_log(alice_evm_addr(), H160::default(), class_id, 0)]
+			);
+
+			// burnWithRemark(address,uint256,uint256,bytes) -> 0x126b523c
+			// who
+			// class_id
+			// token_id
+			// remark
+			let mut input = hex!("126b523c").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Uint(class_id.into()),
+				Token::Uint(1.into()),
+				Token::Bytes(b"remark".to_vec()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(NFTModule::balance(&alice()), 0);
+
+			// destroyClass(address,uint256,address) -> 0x5bd5b7b5
+			// who
+			// class_id
+			// dest
+			let mut input = hex!("5bd5b7b5").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Uint(class_id.into()),
+				Token::Address(alice_evm_addr()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert!(orml_nft::Pallet::<Test>::classes(class_id).is_none());
+		});
+	}
+
+	#[test]
+	fn query_attribute_works() {
+		new_test_ext().execute_with(|| {
+			let mut attributes = Attributes::new();
+			attributes.insert(b"key".to_vec(), b"value".to_vec());
+			assert_ok!(NFTModule::create_class(
+				Origin::signed(alice()),
+				vec![1],
+				Properties(ClassProperty::Mintable.into()),
+				attributes.clone(),
+			));
+			let class_owner = orml_nft::Pallet::<Test>::classes(0).unwrap().owner;
+			assert_ok!(Currencies::update_balance(
+				Origin::root(),
+				class_owner.clone(),
+				ACA,
+				100_000
+			));
+			assert_ok!(NFTModule::mint(
+				Origin::signed(class_owner),
+				bob(),
+				0,
+				vec![2],
+				attributes,
+				1
+			));
+
+			// getClassAttribute(uint256,bytes) -> 0x5d7975e7
+			// class_id
+			// key
+			let mut input = hex!("5d7975e7").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[Token::Uint(0.into()), Token::Bytes(b"key".to_vec())]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"value"));
+
+			// getTokenAttribute(uint256,uint256,bytes) -> 0xe68e29bc
+			// class_id
+			// token_id
+			// key
+			let mut input = hex!("e68e29bc").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Uint(0.into()),
+				Token::Uint(0.into()),
+				Token::Bytes(b"key".to_vec()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(b"value"));
+
+			// not exists
+			let mut input = hex!("e68e29bc").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Uint(0.into()),
+				Token::Uint(0.into()),
+				Token::Bytes(b"none".to_vec()),
+			]));
+			let res = NFTPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.output, Output::encode_bytes(&[]));
+		});
 	}
 }
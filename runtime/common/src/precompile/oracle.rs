@@ -22,15 +22,21 @@ use super::{
 	weights::PrecompileWeights,
 };
 use crate::WeightToGas;
-use frame_support::log;
+use frame_support::{log, traits::Get};
 use module_evm::{
 	precompiles::Precompile,
 	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
 	Context, ExitError, ExitSucceed,
 };
-use module_support::{Erc20InfoMapping as Erc20InfoMappingT, PriceProvider as PriceProviderT};
+use module_support::{
+	DEXPriceProvider as DEXPriceProviderT, Erc20InfoMapping as Erc20InfoMappingT, PriceProvider as PriceProviderT,
+};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use sp_runtime::{traits::Convert, RuntimeDebug};
+use primitives::CurrencyId;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// The `Oracle` impl precompile.
@@ -40,6 +46,9 @@ use sp_std::{marker::PhantomData, prelude::*};
 ///
 /// Actions:
 /// - Get price. Rest `input` bytes: `currency_id`.
+/// - Get the timestamp (in seconds) of the last oracle feed. Rest `input` bytes: `currency_id`.
+/// - Get the DEX average price of `base` in `quote`. Rest `input` bytes: `base`, `quote`.
+/// - Check whether the price is locked. Rest `input` bytes: `currency_id`.
 pub struct OraclePrecompile<R>(PhantomData<R>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -47,11 +56,17 @@ pub struct OraclePrecompile<R>(PhantomData<R>);
 #[repr(u32)]
 pub enum Action {
 	GetPrice = "getPrice(address)",
+	GetPriceTimestamp = "getPriceTimestamp(address)",
+	GetDexAveragePrice = "getDexAveragePrice(address,address)",
+	IsPriceLocked = "isPriceLocked(address)",
 }
 
 impl<Runtime> Precompile for OraclePrecompile<Runtime>
 where
-	Runtime: module_evm::Config + module_prices::Config,
+	Runtime: module_evm::Config
+		+ module_prices::Config
+		+ module_dex_oracle::Config
+		+ orml_oracle::Config<orml_oracle::Instance1, OracleKey = CurrencyId>,
 {
 	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
 		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
@@ -109,6 +124,47 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::GetPriceTimestamp => {
+				let currency_id = input.currency_id_at(1)?;
+				// convert to seconds to be comparable with `block.timestamp`, 0 if no feed yet.
+				let timestamp: u64 = <orml_oracle::Pallet<Runtime, orml_oracle::Instance1>>::get(&currency_id)
+					.map(|v| v.timestamp.unique_saturated_into())
+					.unwrap_or_default()
+					/ 1000;
+
+				log::debug!(target: "evm", "oracle: getPriceTimestamp currency_id: {:?}, timestamp: {:?}", currency_id, timestamp);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(timestamp),
+					logs: Default::default(),
+				})
+			}
+			Action::GetDexAveragePrice => {
+				let base = input.currency_id_at(1)?;
+				let quote = input.currency_id_at(2)?;
+				let price = <module_dex_oracle::AverageDEXPriceProvider<Runtime>>::get_relative_price(base, quote)
+					.unwrap_or_default();
+
+				log::debug!(target: "evm", "oracle: getDexAveragePrice base: {:?}, quote: {:?}, price: {:?}", base, quote, price);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(price.into_inner()),
+					logs: Default::default(),
+				})
+			}
+			Action::IsPriceLocked => {
+				let currency_id = input.currency_id_at(1)?;
+				let locked = <module_prices::Pallet<Runtime>>::locked_price(currency_id).is_some();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_bool(locked),
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
@@ -117,7 +173,10 @@ struct Pricer<R>(PhantomData<R>);
 
 impl<Runtime> Pricer<Runtime>
 where
-	Runtime: module_evm::Config + module_prices::Config,
+	Runtime: module_evm::Config
+		+ module_prices::Config
+		+ module_dex_oracle::Config
+		+ orml_oracle::Config<orml_oracle::Instance1, OracleKey = CurrencyId>,
 {
 	const BASE_COST: u64 = 200;
 
@@ -133,6 +192,22 @@ where
 				let get_price = WeightToGas::convert(PrecompileWeights::<Runtime>::oracle_get_price());
 				WeightToGas::convert(read_currency).saturating_add(get_price)
 			}
+			Action::GetPriceTimestamp | Action::IsPriceLocked => {
+				let currency_id = input.currency_id_at(1)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+				// Oracle::Values or Prices::LockedPrice (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				read_currency.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetDexAveragePrice => {
+				let base = input.currency_id_at(1)?;
+				let quote = input.currency_id_at(2)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(base)
+					.saturating_add(InputPricer::<Runtime>::read_currency(quote));
+				// DexOracle::AveragePrices (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				read_currency.saturating_add(WeightToGas::convert(weight))
+			}
 		};
 		Ok(Self::BASE_COST.saturating_add(cost))
 	}
@@ -142,7 +217,10 @@ where
 mod tests {
 	use super::*;
 
-	use crate::precompile::mock::{alice_evm_addr, new_test_ext, Oracle, Price, Test, ALICE, RENBTC};
+	use crate::precompile::mock::{
+		alice_evm_addr, new_test_ext, DexModule, DexOracle, Oracle, Origin, Price, Prices, Test, Timestamp, ALICE,
+		AUSD, RENBTC,
+	};
 	use frame_support::{assert_noop, assert_ok};
 	use hex_literal::hex;
 	use module_evm::ExitRevert;
@@ -197,6 +275,138 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn get_price_timestamp_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// getPriceTimestamp(address) -> 0xa5c81b6d
+			// RENBTC
+			let input = hex! {"
+				a5c81b6d
+				000000000000000000000000 0000000000000000000100000000000000000014
+			"};
+
+			// no price yet
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			Timestamp::set_timestamp(2_000_000);
+			assert_ok!(Oracle::feed_value(ALICE, RENBTC, Price::from(30_000)));
+
+			// 2_000 seconds
+			let expected_output = hex! {"
+				00000000000000000000000000000000 000000000000000000000000000007d0
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
+	#[test]
+	fn get_dex_average_price_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				RENBTC,
+				AUSD,
+				1_000,
+				1_000_000,
+				0,
+				true
+			));
+
+			// getDexAveragePrice(address,address) -> 0xbcf986ea
+			// RENBTC
+			// AUSD
+			let input = hex! {"
+				bcf986ea
+				000000000000000000000000 0000000000000000000100000000000000000014
+				000000000000000000000000 0000000000000000000100000000000000000001
+			"};
+
+			// average price not enabled
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			assert_ok!(DexOracle::enable_average_price(
+				Origin::signed(ALICE),
+				RENBTC,
+				AUSD,
+				12_000
+			));
+
+			// 1_000 AUSD per RENBTC
+			let expected_output = hex! {"
+				00000000000000000000000000000000 000000000000003635c9adc5dea00000
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
+	#[test]
+	fn is_price_locked_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// isPriceLocked(address) -> 0x2a7df44f
+			// RENBTC
+			let input = hex! {"
+				2a7df44f
+				000000000000000000000000 0000000000000000000100000000000000000014
+			"};
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000000
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+
+			assert_ok!(Oracle::feed_value(ALICE, RENBTC, Price::from(30_000)));
+			assert_ok!(Prices::lock_price(Origin::signed(ALICE), RENBTC));
+
+			let expected_output = hex! {"
+				00000000000000000000000000000000 00000000000000000000000000000001
+			"};
+
+			let resp = OraclePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			assert_eq!(resp.output, expected_output.to_vec());
+		});
+	}
+
 	#[test]
 	fn oracle_precompile_should_handle_invalid_input() {
 		new_test_ext().execute_with(|| {
@@ -23,13 +23,14 @@ use super::{
 	input::{Input, InputT, Output},
 	target_gas_limit,
 };
+use crate::WeightToGas;
 use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::Dispatchable,
 	ensure, log, parameter_types,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		Currency, IsType, OriginTrait,
+		Currency, Get, IsType, OriginTrait,
 	},
 };
 use module_evm::{
@@ -42,7 +43,10 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use pallet_scheduler::TaskAddress;
 use primitives::{Balance, BlockNumber};
 use sp_core::H160;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 
 parameter_types! {
@@ -58,6 +62,32 @@ pub struct TaskInfo {
 	pub fee: Balance,
 }
 
+/// Task id of a periodic call. Encoded as a `TaskInfo` followed by the schedule, so
+/// it can always be decoded as `TaskInfo` first. `fee` is the fee of one execution.
+#[derive(RuntimeDebug, PartialEq, Eq, Encode, Decode)]
+pub struct PeriodicTaskInfo {
+	pub task: TaskInfo,
+	pub first: BlockNumber,
+	pub period: BlockNumber,
+	pub count: u32,
+}
+
+impl PeriodicTaskInfo {
+	/// The number of executions left, given the block of the next execution.
+	pub fn remaining_executions(&self, next: BlockNumber) -> u32 {
+		let executed = next.saturating_sub(self.first) / self.period.max(1);
+		self.count.saturating_sub(executed)
+	}
+}
+
+fn decode_periodic_task(task_info: &TaskInfo, task_id: &[u8]) -> Option<PeriodicTaskInfo> {
+	if task_info.prefix == b"SchedulePeriodicCall" {
+		PeriodicTaskInfo::decode(&mut &task_id[..]).ok()
+	} else {
+		None
+	}
+}
+
 /// The `Schedule` impl precompile.
 ///
 ///
@@ -66,6 +96,12 @@ pub struct TaskInfo {
 /// Actions:
 /// - Schedule. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`, `storage_limit`,
 ///   `min_delay`, `input_len`, `input_data`.
+/// - Schedule periodic. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`,
+///   `storage_limit`, `min_delay`, `period`, `count`, `input_len`, `input_data`.
+/// - Cancel. Rest `input` bytes: `from`, `task_id_len`, `task_id`.
+/// - Reschedule. Rest `input` bytes: `from`, `min_delay`, `task_id_len`, `task_id`.
+/// - Get task status. Rest `input` bytes: `task_id_len`, `task_id`.
+/// - Get next execution block. Rest `input` bytes: `task_id_len`, `task_id`.
 pub struct SchedulePrecompile<Runtime>(PhantomData<Runtime>);
 
 #[module_evm_utility_macro::generate_function_selector]
@@ -75,6 +111,9 @@ pub enum Action {
 	Schedule = "scheduleCall(address,address,uint256,uint256,uint256,bytes)",
 	Cancel = "cancelCall(address,bytes)",
 	Reschedule = "rescheduleCall(address,uint256,bytes)",
+	SchedulePeriodic = "schedulePeriodicCall(address,address,uint256,uint256,uint256,uint256,uint256,uint256,bytes)",
+	GetTaskStatus = "getTaskStatus(bytes)",
+	GetNextExecutionBlock = "getNextExecutionBlock(bytes)",
 }
 
 type PalletBalanceOf<T> =
@@ -125,7 +164,7 @@ where
 		let action = input.action()?;
 
 		match action {
-			Action::Schedule => {
+			Action::Schedule | Action::SchedulePeriodic => {
 				let from = input.evm_address_at(1)?;
 				let target = input.evm_address_at(2)?;
 
@@ -133,19 +172,35 @@ where
 				let gas_limit = input.u64_at(4)?;
 				let storage_limit = input.u32_at(5)?;
 				let min_delay = input.u32_at(6)?;
-				// solidity abi encode bytes will add an length at input[7]
-				let input_len = input.u32_at(8)?;
-				let input_data = input.bytes_at(9, input_len as usize)?;
+				let (maybe_periodic, input_index) = if action == Action::SchedulePeriodic {
+					let period = input.u32_at(7)?;
+					let count = input.u32_at(8)?;
+					ensure!(
+						!period.is_zero() && !count.is_zero(),
+						PrecompileFailure::Revert {
+							exit_status: ExitRevert::Reverted,
+							output: "Invalid periodic schedule".into(),
+							cost: target_gas_limit(target_gas).unwrap_or_default(),
+						}
+					);
+					(Some((period, count)), 9)
+				} else {
+					(None, 7)
+				};
+				// solidity abi encode bytes will add an length at input[input_index]
+				let input_len = input.u32_at(input_index + 1)?;
+				let input_data = input.bytes_at(input_index + 2, input_len as usize)?;
 
 				log::debug!(
 					target: "evm",
-					"schedule call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, min_delay: {:?}, input_len: {:?}, input_data: {:?}",
+					"schedule call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, min_delay: {:?}, periodic: {:?}, input_len: {:?}, input_data: {:?}",
 					from,
 					target,
 					value,
 					gas_limit,
 					storage_limit,
 					min_delay,
+					maybe_periodic,
 					input_len,
 					input_data,
 				);
@@ -156,13 +211,14 @@ where
 					// reserve the transaction fee for gas_limit and storage_limit
 					// TODO: reserve storage_limit here
 					// Manually charge weight fee in scheduled_call
-					use sp_runtime::traits::Convert;
+					// A periodic call reserves the fee of every execution upfront
+					let executions = maybe_periodic.map_or(1, |(_, count)| count);
 					let from_account = Runtime::AddressMapping::get_account_id(&from);
 					let weight = <Runtime as module_evm::Config>::GasToWeight::convert(gas_limit);
 					let fee = <module_transaction_payment::ChargeTransactionPayment<Runtime>>::weight_to_fee(weight);
-					_fee = <module_transaction_payment::ChargeTransactionPayment<Runtime>>::reserve_fee(
+					<module_transaction_payment::ChargeTransactionPayment<Runtime>>::reserve_fee(
 						&from_account,
-						fee,
+						fee.saturating_mul(executions.into()),
 						None,
 					)
 					.map_err(|e| PrecompileFailure::Revert {
@@ -170,6 +226,7 @@ where
 						output: Into::<&str>::into(e).as_bytes().to_vec(),
 						cost: target_gas_limit(target_gas).unwrap_or_default(),
 					})?;
+					_fee = fee;
 				}
 
 				let call = module_evm::Call::<Runtime>::scheduled_call {
@@ -191,13 +248,31 @@ where
 				})?;
 				EvmSchedulerNextID::set(&next_id);
 
-				let task_id = TaskInfo {
-					prefix: b"ScheduleCall".to_vec(),
-					id: current_id,
-					sender: from,
-					fee: _fee,
-				}
-				.encode();
+				let now: BlockNumber = frame_system::Pallet::<Runtime>::block_number().unique_saturated_into();
+				// same as `DispatchTime::After(min_delay)`
+				let first = now.saturating_add(min_delay).saturating_add(1);
+
+				let task_id = match maybe_periodic {
+					Some((period, count)) => PeriodicTaskInfo {
+						task: TaskInfo {
+							prefix: b"SchedulePeriodicCall".to_vec(),
+							id: current_id,
+							sender: from,
+							fee: _fee,
+						},
+						first,
+						period,
+						count,
+					}
+					.encode(),
+					None => TaskInfo {
+						prefix: b"ScheduleCall".to_vec(),
+						id: current_id,
+						sender: from,
+						fee: _fee,
+					}
+					.encode(),
+				};
 
 				log::debug!(
 					target: "evm",
@@ -211,8 +286,8 @@ where
 					<Runtime as pallet_scheduler::Config>::PalletsOrigin,
 				>>::schedule_named(
 					task_id.clone(),
-					DispatchTime::After(min_delay),
-					None,
+					DispatchTime::At(first),
+					maybe_periodic,
 					0,
 					<<<Runtime as pallet_scheduler::Config>::Call as Dispatchable>::Origin>::root()
 						.caller()
@@ -259,6 +334,18 @@ where
 					}
 				);
 
+				// the fee of the executions already done has been charged
+				#[cfg(not(feature = "with-ethereum-compatibility"))]
+				let executions = match decode_periodic_task(&task_info, &task_id) {
+					Some(periodic_task) => <pallet_scheduler::Pallet<Runtime> as ScheduleNamed<
+						BlockNumber,
+						<Runtime as pallet_scheduler::Config>::Call,
+						<Runtime as pallet_scheduler::Config>::PalletsOrigin,
+					>>::next_dispatch_time(task_id.clone())
+					.map_or(0, |next| periodic_task.remaining_executions(next)),
+					None => 1,
+				};
+
 				<pallet_scheduler::Pallet<Runtime> as ScheduleNamed<
 					BlockNumber,
 					<Runtime as pallet_scheduler::Config>::Call,
@@ -276,7 +363,7 @@ where
 					let from_account = Runtime::AddressMapping::get_account_id(&from);
 					let _err_amount = <module_transaction_payment::ChargeTransactionPayment<Runtime>>::unreserve_fee(
 						&from_account,
-						task_info.fee,
+						task_info.fee.saturating_mul(executions.into()),
 						None,
 					);
 				}
@@ -316,6 +403,15 @@ where
 						cost: target_gas_limit(target_gas).unwrap_or_default(),
 					}
 				);
+				// the remaining executions of a periodic call are derived from its first block
+				ensure!(
+					decode_periodic_task(&task_info, &task_id).is_none(),
+					PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: "Cannot reschedule periodic call".into(),
+						cost: target_gas_limit(target_gas).unwrap_or_default(),
+					}
+				);
 
 				<pallet_scheduler::Pallet<Runtime> as ScheduleNamed<
 					BlockNumber,
@@ -335,6 +431,46 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::GetTaskStatus | Action::GetNextExecutionBlock => {
+				// solidity abi encode bytes will add an length at input[1]
+				let task_id_len = input.u32_at(2)?;
+				let task_id = input.bytes_at(3, task_id_len as usize)?;
+
+				let task_info = TaskInfo::decode(&mut &task_id[..]).map_err(|_| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "Decode task_id failed".into(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				// executed or cancelled tasks are no longer in the scheduler
+				let maybe_next = <pallet_scheduler::Pallet<Runtime> as ScheduleNamed<
+					BlockNumber,
+					<Runtime as pallet_scheduler::Config>::Call,
+					<Runtime as pallet_scheduler::Config>::PalletsOrigin,
+				>>::next_dispatch_time(task_id.clone())
+				.ok();
+
+				let output = if action == Action::GetTaskStatus {
+					let remaining = match (maybe_next, decode_periodic_task(&task_info, &task_id)) {
+						(Some(next), Some(periodic_task)) => periodic_task.remaining_executions(next),
+						(Some(_), None) => 1,
+						(None, _) => 0,
+					};
+					ethabi::encode(&[
+						ethabi::Token::Bool(maybe_next.is_some()),
+						ethabi::Token::Uint(remaining.into()),
+					])
+				} else {
+					Output::encode_uint(maybe_next.unwrap_or_default())
+				};
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output,
+					logs: Default::default(),
+				})
+			}
 		}
 	}
 }
@@ -350,9 +486,17 @@ where
 	fn cost(
 		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
 	) -> Result<u64, PrecompileFailure> {
-		let _action = input.action()?;
-		// TODO: gas cost
-		Ok(Self::BASE_COST)
+		let action = input.action()?;
+		let cost = match action {
+			Action::GetTaskStatus | Action::GetNextExecutionBlock => {
+				// Scheduler::Lookup (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				WeightToGas::convert(weight)
+			}
+			// TODO: gas cost
+			_ => 0,
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
 	}
 }
 
@@ -361,8 +505,10 @@ mod tests {
 	use super::*;
 
 	use crate::precompile::mock::{
-		alice_evm_addr, bob_evm_addr, new_test_ext, run_to_block, Balances, Event as TestEvent, System, Test,
+		aca_evm_address, alice_evm_addr, bob_evm_addr, new_test_ext, run_to_block, Balances, Event as TestEvent,
+		System, Test,
 	};
+	use ethabi::Token;
 	use hex_literal::hex;
 	use sp_core::H160;
 
@@ -594,6 +740,143 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn schedule_periodic_call_should_work() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: Default::default(),
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			// transfer bytes4(keccak256(signature)) 0xa9059cbb
+			// to address
+			// amount
+			let mut transfer = hex!("a9059cbb").to_vec();
+			transfer.extend_from_slice(&ethabi::encode(&[
+				Token::Address(bob_evm_addr()),
+				Token::Uint(1_000.into()),
+			]));
+
+			// schedulePeriodicCall(address,address,uint256,uint256,uint256,uint256,uint256,uint256,bytes) ->
+			// 0x1f0ebd96
+			// from
+			// target
+			// value
+			// gas_limit
+			// storage_limit
+			// min_delay
+			// period
+			// count
+			// input
+			let mut input = hex!("1f0ebd96").to_vec();
+			input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Address(aca_evm_address()),
+				Token::Uint(0.into()),
+				Token::Uint(300_000.into()),
+				Token::Uint(100.into()),
+				Token::Uint(1.into()),
+				Token::Uint(2.into()),
+				Token::Uint(3.into()),
+				Token::Bytes(transfer),
+			]));
+
+			let resp = SchedulePrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			let task_id = ethabi::decode(&[ethabi::ParamType::Bytes], &resp.output).unwrap()[0]
+				.clone()
+				.into_bytes()
+				.unwrap();
+			assert_eq!(
+				PeriodicTaskInfo::decode(&mut &task_id[..]),
+				Ok(PeriodicTaskInfo {
+					task: TaskInfo {
+						prefix: b"SchedulePeriodicCall".to_vec(),
+						id: 0,
+						sender: alice_evm_addr(),
+						#[cfg(not(feature = "with-ethereum-compatibility"))]
+						fee: 300_000,
+						#[cfg(feature = "with-ethereum-compatibility")]
+						fee: 0,
+					},
+					first: 3,
+					period: 2,
+					count: 3,
+				})
+			);
+
+			let event = TestEvent::Scheduler(pallet_scheduler::Event::<Test>::Scheduled { when: 3, index: 0 });
+			assert!(System::events().iter().any(|record| record.event == event));
+
+			// getTaskStatus(bytes) -> 0x8c809265
+			let mut status_input = hex!("8c809265").to_vec();
+			status_input.extend_from_slice(&ethabi::encode(&[Token::Bytes(task_id.clone())]));
+			// getNextExecutionBlock(bytes) -> 0x3e3f1052
+			let mut next_input = hex!("3e3f1052").to_vec();
+			next_input.extend_from_slice(&ethabi::encode(&[Token::Bytes(task_id.clone())]));
+
+			let resp = SchedulePrecompile::execute(&status_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, ethabi::encode(&[Token::Bool(true), Token::Uint(3.into())]));
+			let resp = SchedulePrecompile::execute(&next_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(3u32));
+
+			let from_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice_evm_addr());
+			let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob_evm_addr());
+			#[cfg(not(feature = "with-ethereum-compatibility"))]
+			assert_eq!(Balances::reserved_balance(from_account.clone()), 900_000);
+
+			run_to_block(3);
+			assert_eq!(Balances::free_balance(to_account.clone()), 1000000001000);
+			#[cfg(not(feature = "with-ethereum-compatibility"))]
+			assert_eq!(Balances::reserved_balance(from_account.clone()), 600_000);
+
+			let resp = SchedulePrecompile::execute(&status_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, ethabi::encode(&[Token::Bool(true), Token::Uint(2.into())]));
+			let resp = SchedulePrecompile::execute(&next_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(5u32));
+
+			// rescheduleCall(address,uint256,bytes) -> 0x28302f34
+			let mut reschedule_input = hex!("28302f34").to_vec();
+			reschedule_input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Uint(2.into()),
+				Token::Bytes(task_id.clone()),
+			]));
+			assert_eq!(
+				SchedulePrecompile::execute(&reschedule_input, Some(10_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "Cannot reschedule periodic call".into(),
+					cost: target_gas_limit(Some(10_000)).unwrap()
+				})
+			);
+
+			// cancelCall(address,bytes) -> 0x93e32661
+			let mut cancel_input = hex!("93e32661").to_vec();
+			cancel_input.extend_from_slice(&ethabi::encode(&[
+				Token::Address(alice_evm_addr()),
+				Token::Bytes(task_id.clone()),
+			]));
+			let resp = SchedulePrecompile::execute(&cancel_input, None, &context, false).unwrap();
+			assert_eq!(resp.exit_status, ExitSucceed::Returned);
+			let event = TestEvent::Scheduler(pallet_scheduler::Event::<Test>::Canceled { when: 5, index: 0 });
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(Balances::reserved_balance(from_account), 0);
+
+			let resp = SchedulePrecompile::execute(&status_input, None, &context, false).unwrap();
+			assert_eq!(
+				resp.output,
+				ethabi::encode(&[Token::Bool(false), Token::Uint(0.into())])
+			);
+			let resp = SchedulePrecompile::execute(&next_input, None, &context, false).unwrap();
+			assert_eq!(resp.output, Output::encode_uint(0u32));
+
+			run_to_block(7);
+			assert_eq!(Balances::free_balance(to_account), 1000000001000);
+		});
+	}
+
 	#[test]
 	fn task_id_max_and_min() {
 		let task_id = TaskInfo {
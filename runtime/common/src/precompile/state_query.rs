@@ -0,0 +1,323 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT, Output},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use frame_support::{log, traits::Get};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitSucceed,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::Balance;
+use sp_runtime::{
+	traits::{Convert, UniqueSaturatedInto},
+	RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The StateQuery precompile
+///
+/// Read-only access to a whitelist of runtime states.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get current block number.
+/// - Get current era index of the relay chain known by Homa.
+/// - Get collateral params. Rest `input` bytes: `currency_id`.
+/// - Get account nonce. Rest `input` bytes: `who`.
+pub struct StateQueryPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetBlockNumber = "getBlockNumber()",
+	GetRelayChainCurrentEra = "getRelayChainCurrentEra()",
+	GetCollateralParams = "getCollateralParams(address)",
+	GetNonce = "getNonce(address)",
+}
+
+impl<Runtime> Precompile for StateQueryPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_homa::Config + module_cdp_engine::Config,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, _context: &Context, _is_static: bool) -> PrecompileResult {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		match action {
+			Action::GetBlockNumber => {
+				let block_number: u64 = frame_system::Pallet::<Runtime>::block_number().unique_saturated_into();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(block_number),
+					logs: Default::default(),
+				})
+			}
+			Action::GetRelayChainCurrentEra => {
+				let era = module_homa::Pallet::<Runtime>::relay_chain_current_era();
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(era),
+					logs: Default::default(),
+				})
+			}
+			Action::GetCollateralParams => {
+				let currency_id = input.currency_id_at(1)?;
+
+				log::debug!(
+					target: "evm",
+					"state_query: get_collateral_params, currency_id: {:?}",
+					currency_id
+				);
+
+				// the params which are not set are returned as zero
+				let params = module_cdp_engine::Pallet::<Runtime>::collateral_params(currency_id).unwrap_or_default();
+				let output: Vec<Balance> = vec![
+					params.maximum_total_debit_value,
+					params
+						.interest_rate_per_sec
+						.map(|rate| rate.into_inner().into_inner())
+						.unwrap_or_default(),
+					params
+						.liquidation_ratio
+						.map(|ratio| ratio.into_inner())
+						.unwrap_or_default(),
+					params
+						.liquidation_penalty
+						.map(|rate| rate.into_inner().into_inner())
+						.unwrap_or_default(),
+					params
+						.required_collateral_ratio
+						.map(|ratio| ratio.into_inner())
+						.unwrap_or_default(),
+				];
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint_tuple(output),
+					logs: Default::default(),
+				})
+			}
+			Action::GetNonce => {
+				let who = input.account_id_at(1)?;
+				let nonce: u64 = frame_system::Pallet::<Runtime>::account_nonce(&who).unique_saturated_into();
+
+				log::debug!(
+					target: "evm",
+					"state_query: get_nonce, who: {:?}, nonce: {:?}",
+					who, nonce
+				);
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: Output::encode_uint(nonce),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + module_homa::Config + module_cdp_engine::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost: u64 = match action {
+			Action::GetBlockNumber => {
+				// System::Number (r: 1)
+				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+			}
+			Action::GetRelayChainCurrentEra => {
+				// Homa::RelayChainCurrentEra (r: 1)
+				WeightToGas::convert(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+			}
+			Action::GetCollateralParams => {
+				let currency_id = input.currency_id_at(1)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+				// CdpEngine::CollateralParams (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				read_currency.saturating_add(WeightToGas::convert(weight))
+			}
+			Action::GetNonce => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				// System::Account (r: 1)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+				read_account.saturating_add(WeightToGas::convert(weight))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::{
+		alice, alice_evm_addr, new_test_ext, CDPEngine, Homa, HomaAdmin, One, Origin, System, Test, DOT,
+	};
+	use frame_support::assert_ok;
+	use hex_literal::hex;
+	use module_support::{Rate, Ratio};
+	use orml_traits::Change;
+	use sp_runtime::FixedPointNumber;
+
+	type StateQueryPrecompile = super::StateQueryPrecompile<Test>;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn get_block_number_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(123);
+
+			// getBlockNumber() -> 0x42cbb15c
+			let input = hex! {"42cbb15c"};
+
+			let expected_output = hex! {"00000000000000000000000000000000 0000000000000000000000000000007b"}.to_vec();
+
+			let res = StateQueryPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output);
+		});
+	}
+
+	#[test]
+	fn get_relay_chain_current_era_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Homa::reset_current_era(Origin::signed(HomaAdmin::get()), 10));
+
+			// getRelayChainCurrentEra() -> 0x5ca00723
+			let input = hex! {"5ca00723"};
+
+			let expected_output = hex! {"00000000000000000000000000000000 0000000000000000000000000000000a"}.to_vec();
+
+			let res = StateQueryPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output);
+		});
+	}
+
+	#[test]
+	fn get_collateral_params_works() {
+		new_test_ext().execute_with(|| {
+			// getCollateralParams(address) -> 0x1de25815
+			// currency_id
+			let input = hex! {"
+				1de25815
+				000000000000000000000000 0000000000000000000100000000000000000002
+			"};
+
+			// not set
+			let res = StateQueryPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, [0u8; 160].to_vec());
+
+			assert_ok!(CDPEngine::set_collateral_params(
+				Origin::signed(One::get()),
+				DOT,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(1_000_000_000)
+			));
+
+			// `maximum_total_debit_value`: 1_000_000_000
+			// `interest_rate_per_sec`: `FixedU128` for 1/100_000
+			// `liquidation_ratio`: `FixedU128` for 3/2
+			// `liquidation_penalty`: `FixedU128` for 2/10
+			// `required_collateral_ratio`: `FixedU128` for 9/5
+			let expected_output = hex! {"
+				00000000000000000000000000000000 0000000000000000000000003b9aca00
+				00000000000000000000000000000000 0000000000000000000009184e72a000
+				00000000000000000000000000000000 000000000000000014d1120d7b160000
+				00000000000000000000000000000000 000000000000000002c68af0bb140000
+				00000000000000000000000000000000 000000000000000018fae27693b40000
+			"};
+
+			let res = StateQueryPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output.to_vec());
+		});
+	}
+
+	#[test]
+	fn get_nonce_works() {
+		new_test_ext().execute_with(|| {
+			System::inc_account_nonce(&alice());
+			System::inc_account_nonce(&alice());
+
+			// getNonce(address) -> 0x2d0335ab
+			// who
+			let input = hex! {"
+				2d0335ab
+				000000000000000000000000 1000000000000000000000000000000000000001
+			"};
+
+			let expected_output = hex! {"00000000000000000000000000000000 00000000000000000000000000000002"}.to_vec();
+
+			let res = StateQueryPrecompile::execute(&input, None, &context(), false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(res.output, expected_output);
+		});
+	}
+}
@@ -84,3 +84,29 @@ fn precompile_filter_does_not_work_on_non_system_contracts() {
 			.is_none());
 	});
 }
+
+#[test]
+fn precompile_filter_does_not_work_on_nft_mirrors() {
+	new_test_ext().execute_with(|| {
+		let mirror = primitives::evm::nft_class_to_evm_address(0);
+		assert!(PrecompilesValue::get().is_precompile(mirror));
+
+		let mut non_system = [0u8; 20];
+		non_system[0] = 1;
+
+		let non_system_caller_context = Context {
+			address: mirror,
+			caller: non_system.into(),
+			apparent_value: 0.into(),
+		};
+
+		// balanceOf(address) -> 0x70a08231
+		let mut input = hex!("70a08231").to_vec();
+		input.extend_from_slice(&[0u8; 32]);
+		let res = PrecompilesValue::get()
+			.execute(mirror, &input, None, &non_system_caller_context, false)
+			.unwrap()
+			.unwrap();
+		assert_eq!(res.output, [0u8; 32].to_vec());
+	});
+}
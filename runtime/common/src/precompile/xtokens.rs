@@ -0,0 +1,326 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	input::{Input, InputPricer, InputT},
+	target_gas_limit,
+};
+use crate::WeightToGas;
+use codec::DecodeLimit;
+use ethabi::Token;
+use frame_support::{log, traits::Get};
+use module_evm::{
+	precompiles::Precompile,
+	runner::state::{PrecompileFailure, PrecompileOutput, PrecompileResult},
+	Context, ExitError, ExitRevert, ExitSucceed, Log,
+};
+use module_support::AddressMapping;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use orml_traits::XcmTransfer;
+use primitives::{Balance, CurrencyId};
+use sp_core::H256;
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{latest::MultiLocation, VersionedMultiLocation};
+
+/// The maximum depth of the nested junctions when decoding the destination.
+const MAX_DECODE_DEPTH: u32 = 32;
+
+/// `Transferred(address,address,uint256,bytes)`
+pub const TRANSFERRED_EVENT: [u8; 32] =
+	*module_evm_utility_macro::keccak256!("Transferred(address,address,uint256,bytes)");
+
+/// The Xtokens precompile
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Transfer from the caller. Rest `input` bytes: `currency_id`, `amount`, `dest_weight`,
+///   `dest_len`, `dest`. `dest` is the SCALE encoded `VersionedMultiLocation`.
+pub struct XtokensPrecompile<R>(PhantomData<R>);
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Transfer = "transfer(address,uint256,uint64,bytes)",
+}
+
+impl<Runtime> Precompile for XtokensPrecompile<Runtime>
+where
+	Runtime: module_evm::Config + orml_xtokens::Config,
+	orml_xtokens::Pallet<Runtime>: XcmTransfer<Runtime::AccountId, Balance, CurrencyId>,
+{
+	fn execute(input: &[u8], target_gas: Option<u64>, context: &Context, is_static: bool) -> PrecompileResult {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(
+			input,
+			target_gas_limit(target_gas),
+		);
+
+		let gas_cost = Pricer::<Runtime>::cost(&input)?;
+
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(PrecompileFailure::Error {
+					exit_status: ExitError::OutOfGas,
+				});
+			}
+		}
+
+		let action = input.action()?;
+
+		if is_static && action == Action::Transfer {
+			return Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: "cannot modify state in static call".into(),
+				cost: target_gas_limit(target_gas).unwrap_or_default(),
+			});
+		}
+
+		match action {
+			Action::Transfer => {
+				let from = Runtime::AddressMapping::get_account_id(&context.caller);
+				let currency_id = input.currency_id_at(1)?;
+				let amount = input.balance_at(2)?;
+				let dest_weight = input.u64_at(3)?;
+				// solidity abi encode bytes will add an length at input[4]
+				let dest_len = input.u32_at(5)?;
+				let dest_bytes = input.bytes_at(6, dest_len as usize)?;
+
+				let dest: MultiLocation =
+					VersionedMultiLocation::decode_all_with_depth_limit(MAX_DECODE_DEPTH, &mut &dest_bytes[..])
+						.ok()
+						.and_then(|dest| dest.try_into().ok())
+						.ok_or_else(|| PrecompileFailure::Revert {
+							exit_status: ExitRevert::Reverted,
+							output: "invalid dest".into(),
+							cost: target_gas_limit(target_gas).unwrap_or_default(),
+						})?;
+
+				log::debug!(
+					target: "evm",
+					"xtokens: transfer, from: {:?}, currency_id: {:?}, amount: {:?}, dest: {:?}, dest_weight: {:?}",
+					from, currency_id, amount, dest, dest_weight
+				);
+
+				<orml_xtokens::Pallet<Runtime> as XcmTransfer<Runtime::AccountId, Balance, CurrencyId>>::transfer(
+					from,
+					currency_id,
+					amount,
+					dest,
+					dest_weight,
+				)
+				.map_err(|e| PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: Into::<&str>::into(e).as_bytes().to_vec(),
+					cost: target_gas_limit(target_gas).unwrap_or_default(),
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: gas_cost,
+					output: vec![],
+					logs: vec![Log {
+						address: context.address,
+						topics: vec![H256::from(TRANSFERRED_EVENT), H256::from(context.caller)],
+						data: ethabi::encode(&[
+							Token::Address(input.evm_address_at(1)?),
+							Token::Uint(amount.into()),
+							Token::Bytes(dest_bytes),
+						]),
+					}],
+				})
+			}
+		}
+	}
+}
+
+struct Pricer<R>(PhantomData<R>);
+
+impl<Runtime> Pricer<Runtime>
+where
+	Runtime: module_evm::Config + orml_xtokens::Config,
+{
+	const BASE_COST: u64 = 200;
+
+	fn cost(
+		input: &Input<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>,
+	) -> Result<u64, PrecompileFailure> {
+		let action = input.action()?;
+
+		let cost: u64 = match action {
+			Action::Transfer => {
+				let read_account = InputPricer::<Runtime>::read_accounts(1);
+				let currency_id = input.currency_id_at(1)?;
+				let read_currency = InputPricer::<Runtime>::read_currency(currency_id);
+
+				// local execution of the XCM message:
+				// withdraw the asset from the sender and deposit or burn it (r: 4, w: 4)
+				// send the message (r: 2, w: 2)
+				let weight = <Runtime as frame_system::Config>::DbWeight::get()
+					.reads_writes(6, 6)
+					.saturating_add(<Runtime as orml_xtokens::Config>::BaseXcmWeight::get());
+
+				read_account
+					.saturating_add(read_currency)
+					.saturating_add(WeightToGas::convert(weight))
+			}
+		};
+		Ok(Self::BASE_COST.saturating_add(cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::{
+		mock::{alice, alice_evm_addr, new_test_ext, Currencies, Event, Origin, System, Test, DOT},
+		XTOKENS,
+	};
+	use codec::Encode;
+	use frame_support::assert_ok;
+	use hex_literal::hex;
+	use orml_traits::MultiCurrency;
+	use sp_core::H160;
+	use xcm::latest::prelude::*;
+
+	type XtokensPrecompile = super::XtokensPrecompile<Test>;
+
+	#[test]
+	fn transferred_event_works() {
+		assert_eq!(
+			TRANSFERRED_EVENT,
+			sp_io::hashing::keccak_256(b"Transferred(address,address,uint256,bytes)")
+		);
+	}
+
+	fn transfer_input(dest: &[u8]) -> Vec<u8> {
+		// transfer(address,uint256,uint64,bytes) -> 0xb5991c87
+		// currency_id
+		// amount
+		// dest_weight
+		// dest
+		let mut input = hex!("b5991c87").to_vec();
+		input.extend_from_slice(&ethabi::encode(&[
+			Token::Address(H160::from(hex!("0000000000000000000100000000000000000002"))),
+			Token::Uint(1_000_000_000u128.into()),
+			Token::Uint(1_000_000u64.into()),
+			Token::Bytes(dest.to_vec()),
+		]));
+		input
+	}
+
+	#[test]
+	fn transfer_works() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: XTOKENS,
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), DOT, 1_000_000_000));
+
+			let dest = VersionedMultiLocation::V1(MultiLocation::new(
+				1,
+				X1(AccountId32 {
+					network: NetworkId::Any,
+					id: [1u8; 32],
+				}),
+			))
+			.encode();
+			let input = transfer_input(&dest);
+
+			let res = XtokensPrecompile::execute(&input, None, &context, false).unwrap();
+			assert_eq!(res.exit_status, ExitSucceed::Returned);
+			assert_eq!(
+				res.logs,
+				vec![Log {
+					address: XTOKENS,
+					topics: vec![H256::from(TRANSFERRED_EVENT), H256::from(alice_evm_addr()),],
+					data: ethabi::encode(&[
+						Token::Address(H160::from(hex!("0000000000000000000100000000000000000002"))),
+						Token::Uint(1_000_000_000u128.into()),
+						Token::Bytes(dest),
+					]),
+				}]
+			);
+			assert!(System::events().iter().any(|record| matches!(
+				record.event,
+				Event::XTokens(orml_xtokens::Event::TransferredMultiAssets { .. })
+			)));
+		});
+	}
+
+	#[test]
+	fn transfer_with_invalid_dest_fails() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: XTOKENS,
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), DOT, 1_000_000_000));
+			let input = transfer_input(&[0xff, 0xff]);
+
+			assert_eq!(
+				XtokensPrecompile::execute(&input, Some(100_000_000), &context, false),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "invalid dest".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+			assert_eq!(Currencies::free_balance(DOT, &alice()), 1_000_000_000);
+		});
+	}
+
+	#[test]
+	fn transfer_in_static_call_fails() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: XTOKENS,
+				caller: alice_evm_addr(),
+				apparent_value: Default::default(),
+			};
+
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), DOT, 1_000_000_000));
+
+			let dest = VersionedMultiLocation::V1(MultiLocation::new(
+				1,
+				X1(AccountId32 {
+					network: NetworkId::Any,
+					id: [1u8; 32],
+				}),
+			))
+			.encode();
+			let input = transfer_input(&dest);
+
+			assert_eq!(
+				XtokensPrecompile::execute(&input, Some(100_000_000), &context, true),
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: "cannot modify state in static call".into(),
+					cost: target_gas_limit(Some(100_000_000)).unwrap(),
+				})
+			);
+			assert_eq!(Currencies::free_balance(DOT, &alice()), 1_000_000_000);
+		});
+	}
+}
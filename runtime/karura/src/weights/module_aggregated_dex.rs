@@ -84,4 +84,36 @@ impl<T: frame_system::Config> module_aggregated_dex::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
 	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AggregatedDex AggregatedSwapPaths (r:1 w:0)
+	// Storage: StableAsset Pools (r:1 w:0)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn split_swap_with_exact_supply(r: u32, ) -> Weight {
+		(19_836_000 as Weight)
+			// Standard Error: 386_000
+			.saturating_add((164_207_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AggregatedDex AggregatedSwapPaths (r:1 w:0)
+	// Storage: StableAsset Pools (r:1 w:0)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn split_swap_with_exact_target(r: u32, ) -> Weight {
+		(22_471_000 as Weight)
+			// Standard Error: 453_000
+			.saturating_add((171_938_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
 }
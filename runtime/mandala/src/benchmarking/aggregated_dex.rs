@@ -24,12 +24,33 @@ use runtime_common::{BNC, RENBTC, VSKSM};
 use sp_std::prelude::*;
 
 use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 
 use orml_benchmarking::runtime_benchmarks;
 
 const CURRENCY_LIST: [CurrencyId; 7] = [NATIVE, STABLECOIN, LIQUID, STAKING, BNC, RENBTC, VSKSM];
 
+// inject liquidity for `r` routes from NATIVE to STABLECOIN which don't share any pool: the direct
+// path and the paths through the swap joints.
+fn inject_split_swap_liquidity(maker: AccountId, r: u32) -> Result<(), &'static str> {
+	let amount = 10_000 * dollar(NATIVE);
+	inject_liquidity(maker.clone(), NATIVE, STABLECOIN, amount, amount, false)?;
+
+	let joints = <Runtime as module_aggregated_dex::Config>::DexSwapJointList::get()
+		.into_iter()
+		.filter(|joint| !joint.is_empty() && !joint.contains(&NATIVE) && !joint.contains(&STABLECOIN));
+	for joint in joints.take(r.saturating_sub(1) as usize) {
+		let mut path = vec![NATIVE];
+		path.extend(joint);
+		path.push(STABLECOIN);
+		for pair in path.windows(2) {
+			inject_liquidity(maker.clone(), pair[0], pair[1], amount, amount, false)?;
+		}
+	}
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_aggregated_dex }
 
@@ -89,6 +110,24 @@ runtime_benchmarks! {
 			);
 		}
 	}: _(RawOrigin::Root, updates)
+
+	split_swap_with_exact_supply {
+		let r in 1 .. <Runtime as module_aggregated_dex::Config>::MaxSplitSwapRoutes::get();
+
+		let maker: AccountId = account("maker", 0, 0);
+		let taker: AccountId = whitelisted_caller();
+		inject_split_swap_liquidity(maker, r)?;
+		set_balance(NATIVE, &taker, 10_000 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(taker), NATIVE, STABLECOIN, 1_000 * dollar(NATIVE), 0)
+
+	split_swap_with_exact_target {
+		let r in 1 .. <Runtime as module_aggregated_dex::Config>::MaxSplitSwapRoutes::get();
+
+		let maker: AccountId = account("maker", 0, 0);
+		let taker: AccountId = whitelisted_caller();
+		inject_split_swap_liquidity(maker, r)?;
+		set_balance(NATIVE, &taker, 10_000 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(taker), NATIVE, STABLECOIN, 1_000 * dollar(STABLECOIN), 10_000 * dollar(NATIVE))
 }
 
 #[cfg(test)]
@@ -0,0 +1,223 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::{dollar, set_balance, LIQUID, NATIVE, STABLECOIN, STAKING};
+use crate::{AccountId, Balance, ConcentratedDex, CurrencyId, Event, OrmlNFT, Runtime, System, NFT};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use primitives::{
+	nft::{ClassProperty, Properties},
+	TradingPair,
+};
+use runtime_common::Price;
+use sp_runtime::{traits::One, DispatchResult};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const TICK_SPACING: u32 = 10;
+// the pools are created at `Price::one()`, so the raw amounts of both currencies must be equal.
+const UNIT: Balance = 1_000_000_000_000;
+
+const CURRENCY_LIST: [CurrencyId; 4] = [STABLECOIN, STAKING, LIQUID, NATIVE];
+
+fn assert_last_event(generic_event: Event) {
+	System::assert_last_event(generic_event.into());
+}
+
+fn create_position_class() -> Result<u32, &'static str> {
+	let owner: AccountId = account("class_owner", 0, SEED);
+	set_balance(NATIVE, &owner, 1_000 * dollar(NATIVE));
+
+	let class_id = OrmlNFT::next_class_id();
+	NFT::create_class(
+		RawOrigin::Signed(owner).into(),
+		vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Mintable),
+		Default::default(),
+	)
+	.map_err(|e| e.error)?;
+	Ok(class_id)
+}
+
+fn new_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+	ConcentratedDex::create_pool(
+		RawOrigin::Root.into(),
+		currency_id_a,
+		currency_id_b,
+		Price::one(),
+		(3, 1000),
+		TICK_SPACING,
+	)
+}
+
+fn inject_position(
+	maker: &AccountId,
+	currency_id_a: CurrencyId,
+	currency_id_b: CurrencyId,
+	tick_lower: i32,
+	tick_upper: i32,
+	amount: Balance,
+) -> Result<u64, &'static str> {
+	set_balance(NATIVE, maker, 100_000 * dollar(NATIVE));
+	if currency_id_a != NATIVE {
+		set_balance(currency_id_a, maker, amount);
+	}
+	if currency_id_b != NATIVE {
+		set_balance(currency_id_b, maker, amount);
+	}
+
+	let class_id = ConcentratedDex::position_class_id().ok_or("position class not set")?;
+	let position_id = OrmlNFT::next_token_id(class_id);
+	ConcentratedDex::mint_position(
+		RawOrigin::Signed(maker.clone()).into(),
+		currency_id_a,
+		currency_id_b,
+		tick_lower,
+		tick_upper,
+		amount,
+		amount,
+		0,
+	)?;
+	Ok(position_id)
+}
+
+fn setup_pools(maker: &AccountId, path: &[CurrencyId]) -> Result<(), &'static str> {
+	let class_id = create_position_class()?;
+	ConcentratedDex::set_position_class(RawOrigin::Root.into(), class_id)?;
+	for currency_ids in path.windows(2) {
+		new_pool(currency_ids[0], currency_ids[1])?;
+		inject_position(maker, currency_ids[0], currency_ids[1], -5000, 5000, 1_000 * UNIT)?;
+	}
+	Ok(())
+}
+
+// nested positions around the initial price of the first pool of `path`, a swap moving the
+// price by more than `count * TICK_SPACING` ticks in either direction crosses `count` ticks.
+fn inject_crossing_positions(maker: &AccountId, path: &[CurrencyId], count: u32) -> Result<(), &'static str> {
+	for i in 1..=count as i32 {
+		let tick = i * TICK_SPACING as i32;
+		inject_position(maker, path[0], path[1], -tick, tick, UNIT)?;
+	}
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_concentrated_dex }
+
+	set_position_class {
+		let class_id = create_position_class()?;
+	}: _(RawOrigin::Root, class_id)
+	verify {
+		assert_last_event(module_concentrated_dex::Event::PositionClassSet { class_id }.into());
+	}
+
+	create_pool {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, STAKING).unwrap();
+	}: _(RawOrigin::Root, STABLECOIN, STAKING, Price::one(), (3, 1000), TICK_SPACING)
+	verify {
+		assert!(ConcentratedDex::pools(trading_pair).is_some());
+	}
+
+	mint_position {
+		let maker: AccountId = whitelisted_caller();
+		let class_id = create_position_class()?;
+		ConcentratedDex::set_position_class(RawOrigin::Root.into(), class_id)?;
+		new_pool(STABLECOIN, STAKING)?;
+
+		let amount_a = 10_000 * dollar(STABLECOIN);
+		let amount_b = 10_000 * dollar(STAKING);
+		set_balance(STABLECOIN, &maker, amount_a);
+		set_balance(STAKING, &maker, amount_b);
+		set_balance(NATIVE, &maker, 1_000 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(maker), STABLECOIN, STAKING, -1000, 1000, amount_a, amount_b, 0)
+
+	decrease_liquidity {
+		let maker: AccountId = whitelisted_caller();
+		setup_pools(&maker, &[STABLECOIN, STAKING])?;
+		let position_id = OrmlNFT::next_token_id(ConcentratedDex::position_class_id().unwrap()) - 1;
+		let liquidity = ConcentratedDex::positions(position_id).unwrap().liquidity;
+	}: _(RawOrigin::Signed(maker), position_id, liquidity / 2, 0, 0)
+
+	collect_fees {
+		let maker: AccountId = whitelisted_caller();
+		let taker: AccountId = account("taker", 0, SEED);
+		setup_pools(&maker, &[STABLECOIN, STAKING])?;
+		let position_id = OrmlNFT::next_token_id(ConcentratedDex::position_class_id().unwrap()) - 1;
+
+		set_balance(STABLECOIN, &taker, 1_000 * UNIT);
+		ConcentratedDex::swap_with_exact_supply(
+			RawOrigin::Signed(taker).into(),
+			vec![STABLECOIN, STAKING],
+			100 * UNIT,
+			0,
+		)?;
+	}: _(RawOrigin::Signed(maker), position_id)
+
+	burn_position {
+		let maker: AccountId = whitelisted_caller();
+		setup_pools(&maker, &[STABLECOIN, STAKING])?;
+		let position_id = OrmlNFT::next_token_id(ConcentratedDex::position_class_id().unwrap()) - 1;
+		let liquidity = ConcentratedDex::positions(position_id).unwrap().liquidity;
+		ConcentratedDex::decrease_liquidity(RawOrigin::Signed(maker.clone()).into(), position_id, liquidity, 0, 0)?;
+	}: _(RawOrigin::Signed(maker), position_id)
+	verify {
+		assert!(ConcentratedDex::positions(position_id).is_none());
+	}
+
+	swap_with_exact_supply {
+		let u in 2 .. <Runtime as module_concentrated_dex::Config>::TradingPathLimit::get();
+		let c in 0 .. <Runtime as module_concentrated_dex::Config>::MaxTickCrossings::get();
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let path: Vec<CurrencyId> = CURRENCY_LIST[..u as usize].to_vec();
+		setup_pools(&maker, &path)?;
+		inject_crossing_positions(&maker, &path, c)?;
+
+		set_balance(path[0], &taker, 10_000 * UNIT);
+	}: swap_with_exact_supply(RawOrigin::Signed(taker), path.clone(), 500 * UNIT, 0)
+	verify {
+		let path_limit: u32 = <Runtime as module_concentrated_dex::Config>::TradingPathLimit::get();
+		// would panic the benchmark anyways, must add new currencies to CURRENCY_LIST for benchmarking to work
+		assert!(path_limit <= CURRENCY_LIST.len() as u32);
+	}
+
+	swap_with_exact_target {
+		let u in 2 .. <Runtime as module_concentrated_dex::Config>::TradingPathLimit::get();
+		let c in 0 .. <Runtime as module_concentrated_dex::Config>::MaxTickCrossings::get();
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let path: Vec<CurrencyId> = CURRENCY_LIST[..u as usize].to_vec();
+		setup_pools(&maker, &path)?;
+		inject_crossing_positions(&maker, &path, c)?;
+
+		set_balance(path[0], &taker, 10_000 * UNIT);
+	}: swap_with_exact_target(RawOrigin::Signed(taker), path.clone(), 400 * UNIT, 10_000 * UNIT)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
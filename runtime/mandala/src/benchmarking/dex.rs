@@ -24,8 +24,8 @@ use module_dex::TradingPairStatus;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::TradingPair;
-use runtime_common::{BNC, RENBTC, VSKSM};
-use sp_runtime::traits::UniqueSaturatedInto;
+use runtime_common::{Ratio, BNC, RENBTC, VSKSM};
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -358,6 +358,21 @@ runtime_benchmarks! {
 
 		System::set_block_number(ExtendedProvisioningBlocks::get() + 1);
 	}: _(RawOrigin::Signed(whitelisted_caller()), trading_pair.first(), trading_pair.second())
+
+	set_exchange_fee {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second(), Some((1, 1000)))
+	verify {
+		assert_last_event(module_dex::Event::ExchangeFeeUpdated{trading_pair, exchange_fee: Some((1, 1000))}.into());
+	}
+
+	set_protocol_fee {
+		let receiver: AccountId = account("receiver", 0, SEED);
+		let protocol_fee = Some((Ratio::saturating_from_rational(1, 6), receiver));
+	}: _(RawOrigin::Root, protocol_fee.clone())
+	verify {
+		assert_last_event(module_dex::Event::ProtocolFeeUpdated{protocol_fee}.into());
+	}
 }
 
 #[cfg(test)]
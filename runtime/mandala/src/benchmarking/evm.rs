@@ -19,10 +19,10 @@
 use crate::{AccountId, Event, EvmAccounts, Origin, Runtime, System, EVM};
 
 use super::utils::{dollar, set_balance, NATIVE};
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, traits::Get, weights::Weight};
 use frame_system::RawOrigin;
-use module_evm::MaxCodeSize;
-use module_support::AddressMapping;
+use module_evm::{CodeDeposits, ContractLastUsedAt, EvmTask, MaxCodeSize, MaxMigrationSize, ParkedCodeDeposits};
+use module_support::{AddressMapping, DispatchableTask};
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
 use sp_core::{H160, H256};
 use sp_io::hashing::keccak_256;
@@ -244,6 +244,80 @@ runtime_benchmarks! {
 
 		whitelist_account!(alice_account);
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	reclaim_abandoned_contract {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		System::set_block_number(1 + <Runtime as module_evm::Config>::AbandonedContractPeriod::get());
+	}: {
+		let result = EvmTask::<Runtime>::ReclaimAbandoned { start_key: None }.dispatch(Weight::MAX);
+		assert!(result.finished);
+	}
+	verify {
+		assert!(!ContractLastUsedAt::<Runtime>::contains_key(&contract));
+	}
+
+	propose_code_upgrade {
+		let c in 0..MaxMigrationSize::get();
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+
+		let migration = vec![0; c as usize];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, code_hash, migration)
+
+	cancel_code_upgrade {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+		EVM::propose_code_upgrade(Origin::signed(alice_account_id()), contract, code_hash, vec![])?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	refund_code_deposit {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+		frame_support::assert_ok!(EVM::create(Origin::signed(alice_account.clone()), FACTORY_CONTRACT.to_vec(), 0, 1000000000, 1000000000, vec![]));
+		let result = EvmTask::<Runtime>::RebalanceCodeDeposit { code_hash, start_key: None }.dispatch(Weight::MAX);
+		assert!(result.finished);
+		// the deposit of the contract is parked for alice until a new contract covers the code
+		EVM::selfdestruct(Origin::signed(alice_account.clone()), contract)?;
+		frame_support::assert_ok!(EVM::create(Origin::signed(alice_account.clone()), FACTORY_CONTRACT.to_vec(), 0, 1000000000, 1000000000, vec![]));
+		let depositor = EvmAccounts::eth_address(&alice());
+	}: {
+		let result = EvmTask::<Runtime>::RebalanceCodeDeposit { code_hash, start_key: None }.dispatch(Weight::MAX);
+		assert!(result.finished);
+	}
+	verify {
+		assert_eq!(ParkedCodeDeposits::<Runtime>::get(code_hash, depositor), 0);
+	}
+
+	rebalance_code_deposit {
+		let alice_account = alice_account_id();
+
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+		let contract = deploy_contract(alice_account_id())?;
+		let code_hash = EVM::code_hash_at_address(&contract);
+		frame_support::assert_ok!(EVM::create(Origin::signed(alice_account.clone()), FACTORY_CONTRACT.to_vec(), 0, 1000000000, 1000000000, vec![]));
+	}: {
+		let result = EvmTask::<Runtime>::RebalanceCodeDeposit { code_hash, start_key: None }.dispatch(Weight::MAX);
+		assert!(result.finished);
+	}
+	verify {
+		assert_eq!(CodeDeposits::<Runtime>::get(code_hash).map(|code_deposit| code_deposit.rebalancing), Some(false));
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,119 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::{dollar, inject_liquidity, set_balance, NATIVE, STABLECOIN};
+use crate::{AccountId, Event, LimitOrder, Runtime, System};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::{Get, OnIdle};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use runtime_common::Price;
+use sp_runtime::{
+	traits::{One, Saturating},
+	FixedPointNumber,
+};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn assert_last_event(generic_event: Event) {
+	System::assert_last_event(generic_event.into());
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_limit_order }
+
+	place_order {
+		let caller: AccountId = whitelisted_caller();
+		let supply_amount = 10 * dollar(NATIVE);
+		set_balance(NATIVE, &caller, 100 * dollar(NATIVE));
+	}: _(RawOrigin::Signed(caller.clone()), vec![NATIVE, STABLECOIN], supply_amount, Price::one(), Some(100u32))
+	verify {
+		assert_last_event(module_limit_order::Event::OrderPlaced {
+			order_id: 0,
+			owner: caller,
+			path: vec![NATIVE, STABLECOIN],
+			supply_amount,
+			limit_price: Price::one(),
+			expiry: Some(100),
+		}.into());
+	}
+
+	cancel_order {
+		let caller: AccountId = whitelisted_caller();
+		let supply_amount = 10 * dollar(NATIVE);
+		set_balance(NATIVE, &caller, 100 * dollar(NATIVE));
+		LimitOrder::place_order(RawOrigin::Signed(caller.clone()).into(), vec![NATIVE, STABLECOIN], supply_amount, Price::one(), None)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert_last_event(module_limit_order::Event::OrderCancelled {
+			order_id: 0,
+			owner: caller,
+			refund_amount: supply_amount,
+		}.into());
+	}
+
+	// worst case: every checked order is partially filled, which quotes the full amount, the minimum
+	// amount and every bisection step before the swap
+	check_orders {
+		let u in 0 .. <Runtime as module_limit_order::Config>::MaxOrdersCheckedPerBlock::get();
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let supply_amount = 200 * dollar(NATIVE);
+		inject_liquidity(maker, NATIVE, STABLECOIN, 10_000 * dollar(NATIVE), 10_000 * dollar(STABLECOIN), false)?;
+		set_balance(NATIVE, &caller, 10_000 * dollar(NATIVE));
+		for _ in 0 .. u {
+			LimitOrder::place_order(
+				RawOrigin::Signed(caller.clone()).into(),
+				vec![NATIVE, STABLECOIN],
+				supply_amount,
+				Price::one(),
+				None,
+			)?;
+		}
+
+		// each fill takes about 1% of the pool and moves the price down by about 1.99%, set the
+		// limit prices in the check order so that each order can only fill about half of it.
+		let order_ids: Vec<_> = module_limit_order::Orders::<Runtime>::iter_keys().collect();
+		for (k, order_id) in order_ids.into_iter().enumerate() {
+			let limit_price = Price::saturating_from_rational(999 * 99, 100_000)
+				.saturating_mul(Price::saturating_from_rational(9801, 10_000).saturating_pow(k));
+			module_limit_order::Orders::<Runtime>::mutate(order_id, |maybe_order| {
+				if let Some(order) = maybe_order {
+					order.limit_price = limit_price;
+				}
+			});
+		}
+	}: {
+		LimitOrder::on_idle(System::block_number(), u64::MAX);
+	}
+	verify {
+		assert_eq!(module_limit_order::Orders::<Runtime>::iter().count(), u as usize);
+		assert!(module_limit_order::Orders::<Runtime>::iter_values().all(|order| order.supply_amount < supply_amount));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -32,6 +32,7 @@ pub mod auction_manager;
 pub mod cdp_engine;
 pub mod cdp_treasury;
 pub mod collator_selection;
+pub mod concentrated_dex;
 pub mod currencies;
 pub mod dex;
 pub mod dex_oracle;
@@ -43,6 +44,7 @@ pub mod homa;
 pub mod honzon;
 pub mod idle_scheduler;
 pub mod incentives;
+pub mod limit_order;
 pub mod nominees_election;
 pub mod nutsfinance_stable_asset;
 pub mod prices;
@@ -55,7 +55,9 @@ use module_currencies::{BasicCurrencyAdapter, Currency};
 use module_evm::{runner::RunnerExtended, CallInfo, CreateInfo, EvmChainId, EvmTask};
 use module_evm_accounts::EvmAddressMapping;
 use module_relaychain::RelayChainCallBuilder;
-use module_support::{AssetIdMapping, DispatchableTask, ExchangeRateProvider, FractionalRate, PoolId};
+use module_support::{
+	AssetIdMapping, DispatchableTask, ExchangeRateProvider, FractionalRate, PoolId, SwapLimit, SwapRoute,
+};
 use module_transaction_payment::TargetedFeeAdjustment;
 use scale_info::TypeInfo;
 
@@ -66,7 +68,10 @@ use orml_traits::{
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use primitives::{
 	define_combined_task,
-	evm::{AccessListItem, EthereumTransactionMessage},
+	evm::{
+		tracing::{TraceOutcome, TracerConfig},
+		AccessListInfo, AccessListItem, BundledCall, EthereumTransactionMessage, StateOverride, StorageDeposit,
+	},
 	task::TaskResult,
 	unchecked_extrinsic::AcalaUncheckedExtrinsic,
 };
@@ -163,6 +168,8 @@ parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"aca/trsy");
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const ConcentratedDEXPalletId: PalletId = PalletId(*b"aca/cdex");
+	pub const LimitOrderPalletId: PalletId = PalletId(*b"aca/lmto");
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const CDPEnginePalletId: PalletId = PalletId(*b"aca/cdpe");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
@@ -191,6 +198,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		TreasuryPalletId::get().into_account_truncating(),
 		LoansPalletId::get().into_account_truncating(),
 		DEXPalletId::get().into_account_truncating(),
+		ConcentratedDEXPalletId::get().into_account_truncating(),
+		LimitOrderPalletId::get().into_account_truncating(),
 		CDPTreasuryPalletId::get().into_account_truncating(),
 		HonzonTreasuryPalletId::get().into_account_truncating(),
 		HomaTreasuryPalletId::get().into_account_truncating(),
@@ -922,6 +931,8 @@ impl module_currencies::Config for Runtime {
 	type GasToWeight = GasToWeight;
 	type SweepOrigin = EnsureRootOrOneGeneralCouncil;
 	type OnDust = module_currencies::TransferDust<Runtime, TreasuryAccount>;
+	type DataDepositPerByte = DataDepositPerByte;
+	type DepositCurrency = Balances;
 }
 
 pub struct EnsureRootOrTreasury;
@@ -1179,14 +1190,45 @@ impl module_dex::Config for Runtime {
 }
 
 impl module_aggregated_dex::Config for Runtime {
-	type DEX = Dex;
+	type DEX = AcalaDex;
 	type StableAsset = RebasedStableAsset;
 	type GovernanceOrigin = EnsureRootOrHalfGeneralCouncil;
 	type DexSwapJointList = AlternativeSwapPathJointList;
 	type SwapPathLimit = ConstU32<3>;
+	type MaxSplitSwapRoutes = ConstU32<3>;
 	type WeightInfo = weights::module_aggregated_dex::WeightInfo<Runtime>;
 }
 
+impl module_concentrated_dex::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type TradingPathLimit = TradingPathLimit;
+	type MaxInitializedTicks = ConstU32<1000>;
+	type MaxTickCrossings = ConstU32<100>;
+	type MinimumLiquidity = ConstU128<1_000_000_000_000>;
+	type PalletId = ConcentratedDEXPalletId;
+	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_concentrated_dex::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub LimitOrderDeposit: Balance = dollar(ACA);
+}
+
+impl module_limit_order::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type NativeCurrency = Balances;
+	type OrderDeposit = LimitOrderDeposit;
+	type DEX = AcalaDex;
+	type MinimumOrderAmount =
+		ExistentialDepositsTimesOneHundred<GetNativeCurrencyId, NativeTokenExistentialDeposit, ExistentialDeposits>;
+	type TradingPathLimit = TradingPathLimit;
+	type MaxOrdersCheckedPerBlock = ConstU32<20>;
+	type PalletId = LimitOrderPalletId;
+	type WeightInfo = weights::module_limit_order::WeightInfo<Runtime>;
+}
+
 pub type RebasedStableAsset = module_support::RebasedStableAsset<
 	StableAsset,
 	ConvertBalanceHoma,
@@ -1195,6 +1237,10 @@ pub type RebasedStableAsset = module_support::RebasedStableAsset<
 
 pub type AcalaSwap = module_aggregated_dex::AggregatedSwap<Runtime>;
 
+pub type AcalaSplitSwap = module_aggregated_dex::SplitSwap<Runtime>;
+
+pub type AcalaDex = module_concentrated_dex::DexWithConcentratedPools<Runtime, Dex>;
+
 impl module_dex_oracle::Config for Runtime {
 	type DEX = Dex;
 	type Time = Timestamp;
@@ -1213,7 +1259,7 @@ impl module_cdp_treasury::Config for Runtime {
 	type AuctionManagerHandler = AuctionManager;
 	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type DEX = Dex;
-	type Swap = AcalaSwap;
+	type Swap = AcalaSplitSwap;
 	type MaxAuctionsCount = ConstU32<50>;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
@@ -1260,7 +1306,7 @@ impl module_transaction_payment::Config for Runtime {
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
-	type OnTransactionPayment = DealWithFees;
+	type OnTransactionPayment = module_evm::BurnBaseFee<Runtime, DealWithFees>;
 	type AlternativeFeeSwapDeposit = NativeTokenExistentialDeposit;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type TipPerWeightStep = TipPerWeightStep;
@@ -1493,6 +1539,8 @@ impl InstanceFilter<Call> for ProxyType {
 						| Call::Dex(module_dex::Call::swap_with_exact_target { .. })
 						| Call::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_supply { .. })
 						| Call::AggregatedDex(module_aggregated_dex::Call::swap_with_exact_target { .. })
+						| Call::AggregatedDex(module_aggregated_dex::Call::split_swap_with_exact_supply { .. })
+						| Call::AggregatedDex(module_aggregated_dex::Call::split_swap_with_exact_target { .. })
 				)
 			}
 			ProxyType::Loan => {
@@ -1572,7 +1620,27 @@ impl ecosystem_renvm_bridge::Config for Runtime {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
-	pub PrecompilesValue: AllPrecompiles<Runtime, module_transaction_pause::PausedPrecompileFilter<Runtime>> = AllPrecompiles::<_, _>::mandala();
+	pub const AbandonedContractPeriod: BlockNumber = 365 * DAYS;
+	pub const CodeUpgradeDelay: BlockNumber = 7 * DAYS;
+	pub MinBaseFeePerGas: Balance = microcent(ACA) / 100;
+	pub BlockGasTarget: u64 = runtime_common::EvmLimits::<Runtime>::max_gas_limit() / 2;
+	pub PrecompilesValue: AllPrecompiles<Runtime, module_transaction_pause::PausedPrecompileFilter<Runtime>, DispatchPrecompileCallFilter> = AllPrecompiles::<_, _, _>::mandala();
+}
+
+/// The runtime calls which can be dispatched by the contracts through the dispatch precompile.
+pub struct DispatchPrecompileCallFilter;
+impl Contains<Call> for DispatchPrecompileCallFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Currencies(module_currencies::Call::transfer { .. })
+				| Call::Incentives(
+					module_incentives::Call::deposit_dex_share { .. }
+						| module_incentives::Call::withdraw_dex_share { .. }
+						| module_incentives::Call::claim_rewards { .. }
+				)
+		)
+	}
 }
 
 #[cfg(feature = "with-ethereum-compatibility")]
@@ -1623,7 +1691,8 @@ impl module_evm::Config for Runtime {
 	type StorageDepositPerByte = StorageDepositPerByte;
 	type TxFeePerGas = TxFeePerGas;
 	type Event = Event;
-	type PrecompilesType = AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>>;
+	type PrecompilesType =
+		AllPrecompiles<Self, module_transaction_pause::PausedPrecompileFilter<Self>, DispatchPrecompileCallFilter>;
 	type PrecompilesValue = PrecompilesValue;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
@@ -1632,6 +1701,10 @@ impl module_evm::Config for Runtime {
 	type DeveloperDeposit = DeveloperDeposit;
 	type PublicationFee = PublicationFee;
 	type TreasuryAccount = TreasuryAccount;
+	type AbandonedContractPeriod = AbandonedContractPeriod;
+	type CodeUpgradeDelay = CodeUpgradeDelay;
+	type MinBaseFeePerGas = MinBaseFeePerGas;
+	type BlockGasTarget = BlockGasTarget;
 	type FreePublicationOrigin = EnsureRootOrHalfGeneralCouncil;
 	type Runner = module_evm::runner::stack::Runner<Self>;
 	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
@@ -1803,6 +1876,10 @@ impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtr
 					return Err(InvalidTransaction::Stale);
 				}
 
+				if !EVM::is_base_fee_covered(TxFeePerGas::get()) {
+					return Err(InvalidTransaction::Payment);
+				}
+
 				let (_, _, _, _, mortality, check_nonce, _, _, charge) = extra.clone();
 
 				if mortality != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
@@ -1968,6 +2045,8 @@ construct_runtime!(
 		Dex: module_dex = 111,
 		DexOracle: module_dex_oracle = 112,
 		AggregatedDex: module_aggregated_dex = 113,
+		ConcentratedDex: module_concentrated_dex = 114,
+		LimitOrder: module_limit_order = 115,
 
 		// Honzon
 		AuctionManager: module_auction_manager = 120,
@@ -2065,6 +2144,8 @@ mod benches {
 		[nutsfinance_stable_asset, benchmarking::nutsfinance_stable_asset]
 		[module_idle_scheduler, benchmarking::idle_scheduler]
 		[module_aggregated_dex, benchmarking::aggregated_dex]
+		[module_concentrated_dex, benchmarking::concentrated_dex]
+		[module_limit_order, benchmarking::limit_order]
 	);
 }
 
@@ -2202,11 +2283,26 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_aggregated_dex_runtime_api::AggregatedDexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_split_swap_routes(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			limit: SwapLimit<Balance>,
+		) -> Option<Vec<SwapRoute<CurrencyId, Balance>>> {
+			AggregatedDex::get_split_swap_routes(supply_currency_id, target_currency_id, limit)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn block_limits() -> BlockLimits {
 			BlockLimits {
 				max_gas_limit: runtime_common::EvmLimits::<Runtime>::max_gas_limit(),
 				max_storage_limit: runtime_common::EvmLimits::<Runtime>::max_storage_limit(),
+				base_fee_per_gas: EVM::base_fee_per_gas(),
 			}
 		}
 
@@ -2218,8 +2314,13 @@ impl_runtime_apis! {
 			gas_limit: u64,
 			storage_limit: u32,
 			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
 			_estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
 			<Runtime as module_evm::Config>::Runner::rpc_call(
 				from,
 				from,
@@ -2240,8 +2341,13 @@ impl_runtime_apis! {
 			gas_limit: u64,
 			storage_limit: u32,
 			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
 			_estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
 			<Runtime as module_evm::Config>::Runner::rpc_create(
 				from,
 				data,
@@ -2253,6 +2359,57 @@ impl_runtime_apis! {
 			)
 		}
 
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+		) -> Result<AccessListInfo, sp_runtime::DispatchError> {
+			<Runtime as module_evm::Config>::Runner::rpc_create_access_list(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				<Runtime as module_evm::Config>::config(),
+			)
+		}
+
+		fn storage_deposits(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			state_override: Option<StateOverride>,
+		) -> Result<Vec<StorageDeposit>, sp_runtime::DispatchError> {
+			if let Some(state_override) = state_override {
+				EVM::apply_state_override(state_override)?;
+			}
+
+			<Runtime as module_evm::Config>::Runner::rpc_storage_deposits(
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+				<Runtime as module_evm::Config>::config(),
+			)
+		}
+
+		fn call_bundle(calls: Vec<BundledCall<Balance>>) -> Vec<Result<CallInfo, sp_runtime::DispatchError>> {
+			<Runtime as module_evm::Config>::Runner::rpc_call_bundle(calls, <Runtime as module_evm::Config>::config())
+		}
+
 		fn get_estimate_resources_request(extrinsic: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError> {
 			let utx = UncheckedExtrinsic::decode_all_with_depth_limit(sp_api::MAX_EXTRINSIC_DEPTH, &mut &*extrinsic)
 				.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter extrinsic, decode failed"))?;
@@ -2287,6 +2444,70 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				<Runtime as module_evm::Config>::Runner::rpc_call(
+					from,
+					from,
+					to,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+					<Runtime as module_evm::Config>::config(),
+				)
+			})?;
+			result?;
+			Ok(outcome)
+		}
+
+		fn trace_create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				<Runtime as module_evm::Config>::Runner::rpc_create(
+					from,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default().into_iter().map(|v| (v.address, v.storage_keys)).collect(),
+					<Runtime as module_evm::Config>::config(),
+				)
+			})?;
+			result?;
+			Ok(outcome)
+		}
+
+		fn trace_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+			tracer: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError> {
+			let (result, outcome) = module_evm::runner::tracing::trace(tracer, || {
+				Executive::apply_extrinsic(extrinsic)
+			})?;
+			result.map_err(|_| sp_runtime::DispatchError::Other("Invalid extrinsic"))?;
+			Ok(outcome)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
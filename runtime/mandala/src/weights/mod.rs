@@ -25,6 +25,7 @@ pub mod module_auction_manager;
 pub mod module_cdp_engine;
 pub mod module_cdp_treasury;
 pub mod module_collator_selection;
+pub mod module_concentrated_dex;
 pub mod module_currencies;
 pub mod module_dex;
 pub mod module_dex_oracle;
@@ -34,6 +35,7 @@ pub mod module_evm_accounts;
 pub mod module_homa;
 pub mod module_honzon;
 pub mod module_incentives;
+pub mod module_limit_order;
 pub mod module_nft;
 pub mod module_nominees_election;
 pub mod module_prices;
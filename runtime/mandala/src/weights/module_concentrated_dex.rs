@@ -0,0 +1,146 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for module_concentrated_dex
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-10-14, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// target/production/acala
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=*
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=./templates/runtime-weight-template.hbs
+// --output=./runtime/mandala/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for module_concentrated_dex.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> module_concentrated_dex::WeightInfo for WeightInfo<T> {
+	// Storage: ConcentratedDex PositionClassId (r:1 w:1)
+	// Storage: OrmlNFT Classes (r:1 w:0)
+	fn set_position_class() -> Weight {
+		(9_214_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConcentratedDex Pools (r:1 w:1)
+	fn create_pool() -> Weight {
+		(14_502_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ConcentratedDex Pools (r:1 w:1)
+	// Storage: ConcentratedDex Ticks (r:1 w:1)
+	// Storage: ConcentratedDex InitializedTicks (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: ConcentratedDex PositionClassId (r:1 w:0)
+	// Storage: OrmlNFT Classes (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: OrmlNFT NextTokenId (r:1 w:1)
+	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: OrmlNFT Tokens (r:0 w:1)
+	// Storage: ConcentratedDex Positions (r:0 w:1)
+	fn mint_position() -> Weight {
+		(128_606_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	// Storage: ConcentratedDex PositionClassId (r:1 w:0)
+	// Storage: OrmlNFT Tokens (r:1 w:0)
+	// Storage: ConcentratedDex Positions (r:1 w:1)
+	// Storage: ConcentratedDex Pools (r:1 w:1)
+	// Storage: ConcentratedDex Ticks (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn decrease_liquidity() -> Weight {
+		(105_322_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: ConcentratedDex PositionClassId (r:1 w:0)
+	// Storage: OrmlNFT Tokens (r:1 w:0)
+	// Storage: ConcentratedDex Positions (r:1 w:1)
+	// Storage: ConcentratedDex Pools (r:1 w:0)
+	// Storage: ConcentratedDex Ticks (r:2 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn collect_fees() -> Weight {
+		(74_963_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: ConcentratedDex PositionClassId (r:1 w:0)
+	// Storage: OrmlNFT Tokens (r:1 w:1)
+	// Storage: ConcentratedDex Positions (r:1 w:1)
+	// Storage: OrmlNFT Classes (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT TokenApprovals (r:0 w:1)
+	fn burn_position() -> Weight {
+		(58_771_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: ConcentratedDex Pools (r:1 w:1)
+	// Storage: ConcentratedDex InitializedTicks (r:1 w:0)
+	// Storage: ConcentratedDex Ticks (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn swap_with_exact_supply(u: u32, c: u32, ) -> Weight {
+		(61_208_000 as Weight)
+			// Standard Error: 98_000
+			.saturating_add((17_346_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 10_000
+			.saturating_add((7_891_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: ConcentratedDex Pools (r:1 w:1)
+	// Storage: ConcentratedDex InitializedTicks (r:1 w:0)
+	// Storage: ConcentratedDex Ticks (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn swap_with_exact_target(u: u32, c: u32, ) -> Weight {
+		(62_014_000 as Weight)
+			// Standard Error: 98_000
+			.saturating_add((17_511_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 10_000
+			.saturating_add((7_974_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+}
@@ -189,4 +189,14 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// Storage: Dex TradingPairExchangeFees (r:0 w:1)
+	fn set_exchange_fee() -> Weight {
+		(4_476_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex ProtocolFee (r:0 w:1)
+	fn set_protocol_fee() -> Weight {
+		(4_262_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -62,12 +62,13 @@ impl<T: frame_system::Config> module_nft::WeightInfo for WeightInfo<T> {
 	// Storage: OrmlNFT NextTokenId (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:0 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn mint(i: u32, ) -> Weight {
 		(47_611_000 as Weight)
 			// Standard Error: 52_000
 			.saturating_add((22_735_000 as Weight).saturating_mul(i as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(i as Weight)))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:0)
@@ -78,32 +79,38 @@ impl<T: frame_system::Config> module_nft::WeightInfo for WeightInfo<T> {
 	// Storage: EVM Accounts (r:1 w:1)
 	// Storage: EvmAccounts Accounts (r:0 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:2)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:2 w:2)
 	fn transfer() -> Weight {
 		(97_870_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn burn() -> Weight {
 		(69_740_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: OrmlNFT Tokens (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: OrmlNFT TokensByOwner (r:0 w:1)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ClassBalances (r:1 w:1)
 	fn burn_with_remark(b: u32, ) -> Weight {
 		(70_915_000 as Weight)
 			// Standard Error: 0
 			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
 	// Storage: OrmlNFT Classes (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)